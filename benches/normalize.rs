@@ -0,0 +1,51 @@
+//! Benchmarks for the normalization hot path
+//!
+//! Run with `cargo bench --bench normalize`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use path_utils::{normalize_into, normalize_path_cow, normalize_path_str};
+
+const INPUTS: [(&str, &str); 4] = [
+    ("clean", "src/components/widgets/button/mod.rs"),
+    ("backslashes", "src\\components\\widgets\\button\\mod.rs"),
+    ("messy", "//src//components\\\\widgets/./button//mod.rs/"),
+    ("short", "a.txt"),
+];
+
+fn bench_normalize_path_str(c: &mut Criterion) {
+    let mut group = c.benchmark_group("normalize_path_str");
+    for (name, path) in INPUTS {
+        group.bench_function(name, |b| b.iter(|| normalize_path_str(black_box(path))));
+    }
+    group.finish();
+}
+
+fn bench_normalize_path_cow(c: &mut Criterion) {
+    let mut group = c.benchmark_group("normalize_path_cow");
+    for (name, path) in INPUTS {
+        group.bench_function(name, |b| b.iter(|| normalize_path_cow(black_box(path))));
+    }
+    group.finish();
+}
+
+fn bench_normalize_into(c: &mut Criterion) {
+    let mut group = c.benchmark_group("normalize_into");
+    let mut buf = String::new();
+    for (name, path) in INPUTS {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                normalize_into(black_box(path), &mut buf);
+                buf.len()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_normalize_path_str,
+    bench_normalize_path_cow,
+    bench_normalize_into
+);
+criterion_main!(benches);
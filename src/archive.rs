@@ -0,0 +1,209 @@
+//! Archive entry name utilities
+//!
+//! Helpers for producing entry names that archive tools will read back the way
+//! they were written.
+
+use crate::error::{PathError, Result};
+use alloc::format;
+use alloc::string::ToString;
+
+/// The upper half (0x80-0xFF) of IBM code page 437; the lower half matches ASCII
+const CP437_HIGH: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å', //
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ', //
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»', //
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐', //
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧', //
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀', //
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩', //
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{A0}',
+];
+
+/// The maximum entry name length, limited by the 16-bit length field in ZIP headers
+const ZIP_MAX_NAME_BYTES: usize = u16::MAX as usize;
+
+/// How a ZIP entry name must be encoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZipEncoding {
+    /// Every character exists in code page 437, so the name can be stored
+    /// without the UTF-8 flag (general purpose bit 11) and older tools will
+    /// decode it correctly
+    Cp437Safe,
+    /// The name contains characters outside code page 437 and must be stored
+    /// as UTF-8 with the UTF-8 flag set
+    RequiresUtf8,
+}
+
+/// Determine whether a ZIP entry name needs the UTF-8 flag
+///
+/// Tools that predate the UTF-8 flag decode names as code page 437. Names made
+/// only of characters in that code page (including all printable ASCII and
+/// common accented Latin letters) are safe to store as CP437.
+///
+/// Note that a CP437-safe name still has to be *encoded* as CP437 if written
+/// without the flag; non-ASCII characters have different byte values in UTF-8.
+///
+/// # Examples
+/// ```
+/// use path_utils::{zip_name_encoding, ZipEncoding};
+///
+/// assert_eq!(zip_name_encoding("docs/readme.txt"), ZipEncoding::Cp437Safe);
+/// assert_eq!(zip_name_encoding("docs/café.txt"), ZipEncoding::Cp437Safe);
+/// assert_eq!(zip_name_encoding("docs/文件.txt"), ZipEncoding::RequiresUtf8);
+/// ```
+pub fn zip_name_encoding(name: &str) -> ZipEncoding {
+    if name.chars().all(is_cp437) {
+        ZipEncoding::Cp437Safe
+    } else {
+        ZipEncoding::RequiresUtf8
+    }
+}
+
+fn is_cp437(c: char) -> bool {
+    // Control characters are excluded: in CP437 display contexts 0x01-0x1F are
+    // glyphs, but archive tools treat them as control codes
+    (' '..='~').contains(&c) || CP437_HIGH.contains(&c)
+}
+
+/// Validate a name for use as a ZIP archive entry
+///
+/// Enforces the naming rules from the ZIP specification (APPNOTE.TXT 4.4.17):
+/// - No drive letter and no leading slash
+/// - Only forward slashes as separators
+/// - At most 65535 bytes
+///
+/// Null bytes are rejected since most readers treat names as C strings, and
+/// `..` components are rejected to prevent "zip slip" extraction attacks.
+///
+/// # Examples
+/// ```
+/// use path_utils::validate_zip_name;
+///
+/// assert!(validate_zip_name("docs/readme.txt").is_ok());
+/// assert!(validate_zip_name("docs\\readme.txt").is_err());
+/// assert!(validate_zip_name("/etc/passwd").is_err());
+/// assert!(validate_zip_name("../evil.sh").is_err());
+/// ```
+pub fn validate_zip_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        return Err(PathError::EmptyPath);
+    }
+
+    let bytes = name.as_bytes();
+    if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+        return Err(PathError::DriveLetterPath {
+            path: name.to_string(),
+        });
+    }
+
+    if name.starts_with('/') {
+        return Err(PathError::validation_failed(
+            "zip_absolute",
+            format!("ZIP entry names must be relative: {}", name),
+        ));
+    }
+
+    if name.contains(['\\', '\0']) {
+        return Err(PathError::InvalidCharacters {
+            path: name.to_string(),
+        });
+    }
+
+    if name.split('/').any(|component| component == "..") {
+        return Err(PathError::PathTraversal {
+            path: name.to_string(),
+        });
+    }
+
+    if name.len() > ZIP_MAX_NAME_BYTES {
+        return Err(PathError::validation_failed(
+            "zip_name_length",
+            format!(
+                "ZIP entry name is {} bytes long, exceeding the maximum of {}",
+                name.len(),
+                ZIP_MAX_NAME_BYTES
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zip_name_encoding() {
+        // Printable ASCII is always CP437-safe
+        assert_eq!(zip_name_encoding("src/main.rs"), ZipEncoding::Cp437Safe);
+        assert_eq!(
+            zip_name_encoding("a b~!@#$%^&()_+-=[]{};',.txt"),
+            ZipEncoding::Cp437Safe
+        );
+
+        // Characters from the upper half of CP437
+        assert_eq!(zip_name_encoding("Über/naïve.txt"), ZipEncoding::Cp437Safe);
+        assert_eq!(zip_name_encoding("½ß±.txt"), ZipEncoding::Cp437Safe);
+
+        // Characters outside CP437
+        assert_eq!(zip_name_encoding("文件.txt"), ZipEncoding::RequiresUtf8);
+        assert_eq!(zip_name_encoding("файл.txt"), ZipEncoding::RequiresUtf8);
+        assert_eq!(zip_name_encoding("€uro.txt"), ZipEncoding::RequiresUtf8);
+        assert_eq!(zip_name_encoding("tab\there"), ZipEncoding::RequiresUtf8);
+    }
+
+    #[test]
+    fn test_cp437_table_is_complete() {
+        assert_eq!(CP437_HIGH.len(), 128);
+        for (i, c) in CP437_HIGH.iter().enumerate() {
+            assert!(!c.is_ascii(), "entry {:#x} should not be ASCII", 0x80 + i);
+            assert!(
+                !CP437_HIGH[..i].contains(c),
+                "entry {:#x} is duplicated",
+                0x80 + i
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_zip_name() {
+        assert!(validate_zip_name("readme.txt").is_ok());
+        assert!(validate_zip_name("docs/guide/intro.md").is_ok());
+        assert!(validate_zip_name("docs/").is_ok()); // directory entry
+        assert!(validate_zip_name("文件.txt").is_ok());
+
+        assert!(matches!(validate_zip_name(""), Err(PathError::EmptyPath)));
+        assert!(matches!(
+            validate_zip_name("C:/Windows/win.ini"),
+            Err(PathError::DriveLetterPath { .. })
+        ));
+        assert!(matches!(
+            validate_zip_name("/etc/passwd"),
+            Err(PathError::ValidationFailed {
+                rule: "zip_absolute",
+                ..
+            })
+        ));
+        assert!(matches!(
+            validate_zip_name("docs\\readme.txt"),
+            Err(PathError::InvalidCharacters { .. })
+        ));
+        assert!(matches!(
+            validate_zip_name("file\0null"),
+            Err(PathError::InvalidCharacters { .. })
+        ));
+        assert!(matches!(
+            validate_zip_name("docs/../../evil.sh"),
+            Err(PathError::PathTraversal { .. })
+        ));
+        assert!(matches!(
+            validate_zip_name(&"a".repeat(ZIP_MAX_NAME_BYTES + 1)),
+            Err(PathError::ValidationFailed {
+                rule: "zip_name_length",
+                ..
+            })
+        ));
+        assert!(validate_zip_name(&"a".repeat(ZIP_MAX_NAME_BYTES)).is_ok());
+    }
+}
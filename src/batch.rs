@@ -0,0 +1,536 @@
+//! Operations over collections of paths
+//!
+//! Helpers that look at a batch of paths as a whole, for planning and reporting
+//! before anything is written.
+
+use crate::compare::{canonical_key_with, PathComparison};
+use crate::normalize::{normalize_path_str, normalize_path_str_preserving_root};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// Find the directory that receives the most direct children in a batch
+///
+/// Each path is normalized, then counted as a child of its parent directory
+/// (`""` for top-level entries). Duplicate paths are counted once. Returns the
+/// busiest directory and its child count, or `("", 0)` for an empty batch; ties
+/// go to the directory seen first.
+///
+/// Some filesystems degrade badly with huge directories, so this is useful as a
+/// capacity-planning check before extracting a large batch.
+///
+/// # Examples
+/// ```
+/// use path_utils::max_entries_per_directory;
+///
+/// let paths = ["logs/a.log", "logs/b.log", "logs\\c.log", "src/main.rs"];
+/// assert_eq!(max_entries_per_directory(&paths), ("logs".to_string(), 3));
+/// ```
+pub fn max_entries_per_directory(paths: &[&str]) -> (String, usize) {
+    let mut children: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut order = Vec::new();
+
+    for path in paths {
+        let normalized = normalize_path_str(path);
+        if normalized.is_empty() {
+            continue;
+        }
+
+        let (parent, name) = match normalized.rsplit_once('/') {
+            Some((parent, name)) => (parent.to_string(), name.to_string()),
+            None => (String::new(), normalized),
+        };
+
+        if !children.contains_key(&parent) {
+            order.push(parent.clone());
+        }
+        children.entry(parent).or_default().insert(name);
+    }
+
+    let mut busiest = (String::new(), 0);
+    for parent in order {
+        let count = children[&parent].len();
+        if count > busiest.1 {
+            busiest = (parent, count);
+        }
+    }
+    busiest
+}
+
+/// Split a batch of paths into what a depth-limited tree view shows and hides
+///
+/// Returns `(kept, pruned)`. Paths at or above `max_depth` components are kept
+/// as-is. Deeper paths are listed in `pruned`, and their ancestor at
+/// `max_depth` is added to `kept` in their place, so collapsed directories
+/// still appear. Both lists are normalized, deduplicated, and in first-seen order.
+///
+/// # Examples
+/// ```
+/// use path_utils::prune_to_depth;
+///
+/// let (kept, pruned) = prune_to_depth(&["README.md", "src/a/b.rs", "src/a/c.rs"], 2);
+/// assert_eq!(kept, ["README.md", "src/a"]);
+/// assert_eq!(pruned, ["src/a/b.rs", "src/a/c.rs"]);
+/// ```
+pub fn prune_to_depth(paths: &[&str], max_depth: usize) -> (Vec<String>, Vec<String>) {
+    let mut kept = Vec::new();
+    let mut pruned = Vec::new();
+    let mut seen_kept = BTreeSet::new();
+    let mut seen_pruned = BTreeSet::new();
+
+    for path in paths {
+        let normalized = normalize_path_str(path);
+        if normalized.is_empty() {
+            continue;
+        }
+
+        let components: Vec<&str> = normalized.split('/').collect();
+        if components.len() <= max_depth {
+            if seen_kept.insert(normalized.clone()) {
+                kept.push(normalized);
+            }
+            continue;
+        }
+
+        let truncated = components[..max_depth].join("/");
+        if !truncated.is_empty() && seen_kept.insert(truncated.clone()) {
+            kept.push(truncated);
+        }
+        if seen_pruned.insert(normalized.clone()) {
+            pruned.push(normalized);
+        }
+    }
+
+    (kept, pruned)
+}
+
+/// Find the deepest directory shared by every path in a batch
+///
+/// Each path is normalized and compared component by component. Returns `None`
+/// for an empty batch, when the paths share no leading component, or when
+/// absolute and relative paths are mixed. Absolute paths keep their leading
+/// `/`, and `Some("/")` is returned when the root is all they share. A single
+/// path is its own common ancestor.
+///
+/// # Examples
+/// ```
+/// use path_utils::common_ancestor;
+///
+/// assert_eq!(common_ancestor(&["a/b/c", "a/b/d", "a/b/e/f"]), Some("a/b".to_string()));
+/// assert_eq!(common_ancestor(&["/srv/www", "/srv/db"]), Some("/srv".to_string()));
+/// assert_eq!(common_ancestor(&["a/b", "x/y"]), None);
+/// ```
+pub fn common_ancestor(paths: &[&str]) -> Option<String> {
+    let (first, rest) = paths.split_first()?;
+    let absolute = first.starts_with(['/', '\\']);
+    if rest
+        .iter()
+        .any(|path| path.starts_with(['/', '\\']) != absolute)
+    {
+        return None;
+    }
+
+    let first = normalize_path_str(first);
+    let mut common: Vec<&str> = first.split('/').filter(|s| !s.is_empty()).collect();
+
+    for path in rest {
+        let normalized = normalize_path_str(path);
+        let shared = common
+            .iter()
+            .zip(normalized.split('/'))
+            .take_while(|(a, b)| *a == b)
+            .count();
+        common.truncate(shared);
+    }
+
+    match (absolute, common.is_empty()) {
+        (true, _) => Some(format!("/{}", common.join("/"))),
+        (false, true) => None,
+        (false, false) => Some(common.join("/")),
+    }
+}
+
+/// Count the leading components shared by every path in a batch
+///
+/// Compares the normalized paths the same way as [`common_ancestor`] and
+/// returns the number of components in the shared prefix, for trimming it
+/// from each path in a display. Returns 0 for an empty batch, when the paths
+/// share no leading component, or when absolute and relative paths are
+/// mixed. A leading `/` is not counted as a component.
+///
+/// # Examples
+/// ```
+/// use path_utils::longest_common_path_len;
+///
+/// let paths = ["proj/src/a.rs", "proj/src/b.rs", "proj/src/util/c.rs"];
+/// assert_eq!(longest_common_path_len(&paths), 2);
+/// assert_eq!(longest_common_path_len(&["a/b", "x/y"]), 0);
+/// ```
+pub fn longest_common_path_len(paths: &[&str]) -> usize {
+    let Some((first, rest)) = paths.split_first() else {
+        return 0;
+    };
+    let absolute = first.starts_with(['/', '\\']);
+    if rest
+        .iter()
+        .any(|path| path.starts_with(['/', '\\']) != absolute)
+    {
+        return 0;
+    }
+
+    let first = normalize_path_str(first);
+    let first: Vec<&str> = first.split('/').filter(|s| !s.is_empty()).collect();
+    rest.iter().fold(first.len(), |shared, path| {
+        let normalized = normalize_path_str(path);
+        first[..shared]
+            .iter()
+            .zip(normalized.split('/'))
+            .take_while(|(a, b)| *a == b)
+            .count()
+    })
+}
+
+/// Find paths that would overwrite each other on a case-insensitive filesystem
+///
+/// Each path is normalized, and two paths collide when their normalized forms
+/// are equal ignoring ASCII case, like `README.md` and `readme.md`. Paths that
+/// normalize to exactly the same string are the same file, not a collision.
+/// Returns every colliding pair of normalized paths, earlier path first, in the
+/// order the collisions are found.
+///
+/// Only ASCII letters are folded. macOS and Windows also fold non-ASCII
+/// letters (`É` and `é` collide there), using tables that differ between the
+/// two and from Unicode's own case folding, so those collisions are not
+/// reported.
+///
+/// # Examples
+/// ```
+/// use path_utils::find_case_collisions;
+///
+/// let collisions = find_case_collisions(&["README.md", "src/lib.rs", "readme.md"]);
+/// assert_eq!(collisions, [("README.md".to_string(), "readme.md".to_string())]);
+/// ```
+pub fn find_case_collisions(paths: &[&str]) -> Vec<(String, String)> {
+    let mut seen: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut collisions = Vec::new();
+
+    for path in paths {
+        let normalized = normalize_path_str(path);
+        let spellings = seen.entry(normalized.to_ascii_lowercase()).or_default();
+        if spellings.contains(&normalized) {
+            continue;
+        }
+
+        for earlier in spellings.iter() {
+            collisions.push((earlier.clone(), normalized.clone()));
+        }
+        spellings.push(normalized);
+    }
+
+    collisions
+}
+
+/// Remove paths that are spelled differently but name the same location
+///
+/// Equivalent to [`dedupe_paths_with`] with [`PathComparison::CaseSensitive`],
+/// so only separators and redundant slashes are looked past.
+///
+/// # Examples
+/// ```
+/// use path_utils::dedupe_paths;
+///
+/// assert_eq!(dedupe_paths(&["a/b", "a\\b", "a//b", "c"]), ["a/b", "c"]);
+/// ```
+pub fn dedupe_paths(paths: &[&str]) -> Vec<String> {
+    dedupe_paths_with(paths, PathComparison::CaseSensitive)
+}
+
+/// Remove paths that name the same location, comparing case as `comparison` says
+///
+/// Each path is normalized with
+/// [`normalize_path_str_preserving_root`], and two paths are duplicates when
+/// [`paths_equal_with`](crate::paths_equal_with) considers them equal, so a
+/// leading root still matters (`/a` and `a` are both kept). Returns the
+/// normalized form of the first spelling of each path, in first-seen order.
+/// Paths that normalize to nothing are dropped.
+///
+/// # Examples
+/// ```
+/// use path_utils::{dedupe_paths_with, PathComparison};
+///
+/// let paths = ["Docs/Readme.md", "docs/README.md", "src/lib.rs"];
+/// assert_eq!(
+///     dedupe_paths_with(&paths, PathComparison::CaseInsensitive),
+///     ["Docs/Readme.md", "src/lib.rs"]
+/// );
+/// ```
+pub fn dedupe_paths_with(paths: &[&str], comparison: PathComparison) -> Vec<String> {
+    let mut seen = BTreeSet::new();
+    let mut unique = Vec::new();
+
+    for path in paths {
+        let normalized = normalize_path_str_preserving_root(path);
+        if normalized.is_empty() {
+            continue;
+        }
+        if seen.insert(canonical_key_with(&normalized, comparison)) {
+            unique.push(normalized);
+        }
+    }
+
+    unique
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_entries_per_directory() {
+        let paths = [
+            "src/main.rs",
+            "src/lib.rs",
+            "assets/img/a.png",
+            "assets/img/b.png",
+            "assets/img/c.png",
+            "assets/img/d.png",
+            "README.md",
+            "Cargo.toml",
+            "assets/style.css",
+        ];
+        assert_eq!(
+            max_entries_per_directory(&paths),
+            ("assets/img".to_string(), 4)
+        );
+    }
+
+    #[test]
+    fn test_max_entries_per_directory_normalizes_and_dedupes() {
+        // The same file written three different ways counts once
+        let paths = ["a/x", "a\\x", "a//x", "b/y", "b/z"];
+        assert_eq!(max_entries_per_directory(&paths), ("b".to_string(), 2));
+
+        // Top-level entries belong to the empty directory
+        let paths = ["one", "two", "/three", "dir/four"];
+        assert_eq!(max_entries_per_directory(&paths), (String::new(), 3));
+    }
+
+    #[test]
+    fn test_max_entries_per_directory_edge_cases() {
+        assert_eq!(max_entries_per_directory(&[]), (String::new(), 0));
+        assert_eq!(max_entries_per_directory(&["", "/"]), (String::new(), 0));
+
+        // Ties go to the first directory seen
+        let paths = ["b/1", "a/1", "a/2", "b/2"];
+        assert_eq!(max_entries_per_directory(&paths), ("b".to_string(), 2));
+    }
+
+    #[test]
+    fn test_prune_to_depth() {
+        let paths = [
+            "Cargo.toml",
+            "src/lib.rs",
+            "src/normalize/mod.rs",
+            "src/normalize/scan/fast.rs",
+            "tests/fixtures/deep/a.txt",
+            "tests/fixtures/deep/b.txt",
+            "tests/run.rs",
+        ];
+        let (kept, pruned) = prune_to_depth(&paths, 2);
+
+        assert_eq!(
+            kept,
+            [
+                "Cargo.toml",
+                "src/lib.rs",
+                "src/normalize",
+                "tests/fixtures",
+                "tests/run.rs",
+            ]
+        );
+        assert_eq!(
+            pruned,
+            [
+                "src/normalize/mod.rs",
+                "src/normalize/scan/fast.rs",
+                "tests/fixtures/deep/a.txt",
+                "tests/fixtures/deep/b.txt",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_prune_to_depth_edge_cases() {
+        // Inputs are normalized and deduplicated
+        let (kept, pruned) = prune_to_depth(&["a\\b\\c", "/a/b/c", "a//b"], 2);
+        assert_eq!(kept, ["a/b"]);
+        assert_eq!(pruned, ["a/b/c"]);
+
+        // Depth zero hides everything
+        let (kept, pruned) = prune_to_depth(&["a", "b/c"], 0);
+        assert!(kept.is_empty());
+        assert_eq!(pruned, ["a", "b/c"]);
+
+        let (kept, pruned) = prune_to_depth(&[], 3);
+        assert!(kept.is_empty() && pruned.is_empty());
+    }
+
+    #[test]
+    fn test_common_ancestor() {
+        assert_eq!(
+            common_ancestor(&["a/b/c", "a/b/d", "a/b/e/f"]),
+            Some("a/b".to_string())
+        );
+
+        // Inputs are normalized before comparing
+        assert_eq!(
+            common_ancestor(&["a\\b\\c", "a//b/d/"]),
+            Some("a/b".to_string())
+        );
+
+        // Components are compared whole, not as string prefixes
+        assert_eq!(
+            common_ancestor(&["src/lib.rs", "src/libs/x.rs"]),
+            Some("src".to_string())
+        );
+
+        // A single path is its own ancestor
+        assert_eq!(
+            common_ancestor(&["a/b/c.txt"]),
+            Some("a/b/c.txt".to_string())
+        );
+        assert_eq!(common_ancestor(&["/a/b"]), Some("/a/b".to_string()));
+    }
+
+    #[test]
+    fn test_common_ancestor_outlier() {
+        // One outlier collapses the shared prefix
+        assert_eq!(
+            common_ancestor(&["proj/src/a.rs", "proj/src/b.rs", "proj/docs/c.md"]),
+            Some("proj".to_string())
+        );
+        assert_eq!(
+            common_ancestor(&["proj/src/a.rs", "proj/src/b.rs", "other/c.md"]),
+            None
+        );
+        assert_eq!(
+            common_ancestor(&["/srv/www", "/srv/db", "/home"]),
+            Some("/".to_string())
+        );
+    }
+
+    #[test]
+    fn test_common_ancestor_edge_cases() {
+        assert_eq!(common_ancestor(&[]), None);
+        assert_eq!(common_ancestor(&["", "a"]), None);
+
+        // Mixed absolute and relative paths share nothing meaningful
+        assert_eq!(common_ancestor(&["/a/b", "a/b"]), None);
+        assert_eq!(common_ancestor(&["a/b", "\\a\\b"]), None);
+    }
+
+    #[test]
+    fn test_find_case_collisions() {
+        let paths = [
+            "README.md",
+            "src/Main.rs",
+            "docs/guide.md",
+            "readme.md",
+            "SRC\\main.rs",
+        ];
+        assert_eq!(
+            find_case_collisions(&paths),
+            [
+                ("README.md".to_string(), "readme.md".to_string()),
+                ("src/Main.rs".to_string(), "SRC/main.rs".to_string()),
+            ]
+        );
+
+        // Duplicates of a spelling don't add pairs
+        assert_eq!(find_case_collisions(&["a", "A", "a/", "/A"]).len(), 1);
+
+        // Three spellings of one name give every pair
+        assert_eq!(
+            find_case_collisions(&["x.TXT", "X.txt", "x.txt"]),
+            [
+                ("x.TXT".to_string(), "X.txt".to_string()),
+                ("x.TXT".to_string(), "x.txt".to_string()),
+                ("X.txt".to_string(), "x.txt".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_case_collisions_none() {
+        assert!(find_case_collisions(&[]).is_empty());
+        assert!(find_case_collisions(&["a/b.txt", "a/c.txt", "b/b.txt"]).is_empty());
+
+        // Different spellings of the same path are one file
+        assert!(find_case_collisions(&["a/b", "a\\b", "/a//b/"]).is_empty());
+
+        // Directory and file names differ only in case, but at different depths
+        assert!(find_case_collisions(&["Docs", "docs/readme"]).is_empty());
+    }
+
+    #[test]
+    fn test_find_case_collisions_ascii_only() {
+        // Case-insensitive filesystems fold these, but only ASCII is compared
+        assert!(find_case_collisions(&["\u{c9}t\u{e9}.txt", "\u{e9}t\u{e9}.txt"]).is_empty());
+        assert_eq!(
+            find_case_collisions(&["\u{e9}T\u{e9}.txt", "\u{e9}t\u{e9}.txt"]).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_longest_common_path_len() {
+        // Full overlap
+        assert_eq!(longest_common_path_len(&["a/b/c", "a/b/c"]), 3);
+        assert_eq!(longest_common_path_len(&["a/b/c"]), 3);
+        assert_eq!(longest_common_path_len(&["a\\b", "a//b/"]), 2);
+
+        // Partial overlap
+        assert_eq!(longest_common_path_len(&["a/b/c", "a/b/d", "a/b/e/f"]), 2);
+        assert_eq!(longest_common_path_len(&["a/b/c", "a/b"]), 2);
+        assert_eq!(longest_common_path_len(&["/srv/www", "/srv/db"]), 1);
+        assert_eq!(longest_common_path_len(&["src/lib.rs", "src/libs/x.rs"]), 1);
+
+        // Disjoint
+        assert_eq!(longest_common_path_len(&["a/b", "x/y"]), 0);
+        assert_eq!(longest_common_path_len(&["a/b", "a/c", "z"]), 0);
+        assert_eq!(longest_common_path_len(&["/a/b", "a/b"]), 0);
+        assert_eq!(longest_common_path_len(&["/srv", "/home"]), 0);
+        assert_eq!(longest_common_path_len(&[]), 0);
+    }
+
+    #[test]
+    fn test_dedupe_paths() {
+        let paths = ["a/b", "a\\b", "a//b", "/a/b", "a/b/", "\\a\\b", "a/c"];
+        assert_eq!(dedupe_paths(&paths), ["a/b", "/a/b", "a/c"]);
+
+        // First-seen order is kept, and survivors are normalized
+        let paths = ["z\\1", "y", "z/1", "x//2", "y"];
+        assert_eq!(dedupe_paths(&paths), ["z/1", "y", "x/2"]);
+
+        // Case is significant unless asked otherwise
+        assert_eq!(dedupe_paths(&["A/b", "a/B"]), ["A/b", "a/B"]);
+
+        assert!(dedupe_paths(&[]).is_empty());
+        assert!(dedupe_paths(&["", ""]).is_empty());
+        assert_eq!(dedupe_paths(&["/", "\\\\", "//"]), ["/"]);
+    }
+
+    #[test]
+    fn test_dedupe_paths_case_insensitive() {
+        let paths = ["README.md", "src\\Lib.rs", "readme.MD", "SRC/lib.rs", "b"];
+        assert_eq!(
+            dedupe_paths_with(&paths, PathComparison::CaseInsensitive),
+            ["README.md", "src/Lib.rs", "b"]
+        );
+        assert_eq!(
+            dedupe_paths_with(&paths, PathComparison::CaseSensitive).len(),
+            5
+        );
+    }
+}
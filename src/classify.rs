@@ -0,0 +1,198 @@
+//! Lexical path classification
+//!
+//! Functions that describe what a path string looks like without touching the
+//! filesystem.
+
+use crate::normalize::normalized_components;
+
+/// Heuristic guess at what a path string refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathKindGuess {
+    /// Ends with a separator, or is a `.`/`..` component
+    Directory,
+    /// Final component has a file extension
+    File,
+    /// Nothing in the string indicates either way
+    Ambiguous,
+}
+
+/// Guess whether a path string refers to a file or a directory
+///
+/// This is a heuristic only, for display logic and pre-flight decisions (such as
+/// which directories to create) when the path doesn't exist yet:
+/// - A trailing `/` or `\` means [`PathKindGuess::Directory`]
+/// - A final component with an extension (`main.rs`) means [`PathKindGuess::File`]
+/// - Anything else, including dotfiles like `.config`, is [`PathKindGuess::Ambiguous`]
+///
+/// Nothing stops a directory from being named `archive.d` or a file from having
+/// no extension, so callers must be prepared for the guess to be wrong.
+///
+/// # Examples
+/// ```
+/// use path_utils::{guess_path_kind, PathKindGuess};
+///
+/// assert_eq!(guess_path_kind("foo/"), PathKindGuess::Directory);
+/// assert_eq!(guess_path_kind("foo.txt"), PathKindGuess::File);
+/// assert_eq!(guess_path_kind("foo"), PathKindGuess::Ambiguous);
+/// ```
+pub fn guess_path_kind(path: &str) -> PathKindGuess {
+    if path.ends_with(['/', '\\']) {
+        return PathKindGuess::Directory;
+    }
+
+    let file_name = path.rsplit(['/', '\\']).next().unwrap_or("");
+    if file_name == "." || file_name == ".." {
+        return PathKindGuess::Directory;
+    }
+
+    match file_extension(file_name) {
+        Some(_) => PathKindGuess::File,
+        None => PathKindGuess::Ambiguous,
+    }
+}
+
+/// Where a path string is anchored, judged from its leading characters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathKind {
+    /// No root or drive, such as `src/main.rs`
+    Relative,
+    /// A single leading separator, such as `/etc`
+    AbsoluteRoot,
+    /// A drive prefix, such as `C:\Windows`
+    DriveAbsolute,
+    /// Two leading separators, such as `\\server\share`
+    Unc,
+}
+
+/// Classify a path as relative, rooted, drive-based or UNC without validating it
+///
+/// Only the first few characters are inspected, so this is a cheap,
+/// allocation-free branch for routing logic. `/` and `\` are treated alike.
+/// Any drive prefix counts as [`PathKind::DriveAbsolute`], including a
+/// drive-relative `C:foo`: it is anchored to a drive and can't be joined onto
+/// another path safely.
+///
+/// # Examples
+/// ```
+/// use path_utils::{classify, PathKind};
+///
+/// assert_eq!(classify("src/x"), PathKind::Relative);
+/// assert_eq!(classify("/etc"), PathKind::AbsoluteRoot);
+/// assert_eq!(classify("C:\\x"), PathKind::DriveAbsolute);
+/// assert_eq!(classify("\\\\server\\share"), PathKind::Unc);
+/// ```
+pub fn classify(path: &str) -> PathKind {
+    let bytes = path.as_bytes();
+    let is_separator = |index: usize| matches!(bytes.get(index), Some(b'/' | b'\\'));
+
+    if is_separator(0) && is_separator(1) {
+        PathKind::Unc
+    } else if is_separator(0) {
+        PathKind::AbsoluteRoot
+    } else if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+        PathKind::DriveAbsolute
+    } else {
+        PathKind::Relative
+    }
+}
+
+/// Check whether any component of a path is hidden by the Unix dotfile convention
+///
+/// A component is hidden when it starts with `.` and is not exactly `.` or
+/// `..`, so everything inside a hidden directory counts as hidden too. The path
+/// is split as by [`normalized_components`](crate::normalized_components). On
+/// Windows, hiding is a file attribute rather than part of the name, so this is
+/// only an approximation there.
+///
+/// # Examples
+/// ```
+/// use path_utils::is_hidden;
+///
+/// assert!(is_hidden("a/.git/config"));
+/// assert!(is_hidden(".env"));
+/// assert!(!is_hidden("a/b.txt"));
+/// ```
+pub fn is_hidden(path: &str) -> bool {
+    normalized_components(path)
+        .any(|component| component.starts_with('.') && component != "." && component != "..")
+}
+
+/// The text after the last `.` in a file name, if it has an extension
+///
+/// A leading dot marks a hidden name, not an extension, so `.bashrc` has none,
+/// and neither does a name ending in a dot.
+pub(crate) fn file_extension(file_name: &str) -> Option<&str> {
+    match file_name.rfind('.') {
+        Some(index) if index > 0 && index + 1 < file_name.len() => Some(&file_name[index + 1..]),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify("src/x"), PathKind::Relative);
+        assert_eq!(classify(""), PathKind::Relative);
+        assert_eq!(classify("./a"), PathKind::Relative);
+        assert_eq!(classify("1:/a"), PathKind::Relative);
+
+        assert_eq!(classify("/etc"), PathKind::AbsoluteRoot);
+        assert_eq!(classify("\\Windows"), PathKind::AbsoluteRoot);
+        assert_eq!(classify("/"), PathKind::AbsoluteRoot);
+
+        assert_eq!(classify("C:\\x"), PathKind::DriveAbsolute);
+        assert_eq!(classify("d:/data"), PathKind::DriveAbsolute);
+        assert_eq!(classify("C:foo"), PathKind::DriveAbsolute);
+
+        assert_eq!(classify("\\\\server\\share"), PathKind::Unc);
+        assert_eq!(classify("//server/share"), PathKind::Unc);
+        assert_eq!(classify("\\/mixed"), PathKind::Unc);
+    }
+
+    #[test]
+    fn test_guess_path_kind() {
+        // Trailing separators
+        assert_eq!(guess_path_kind("foo/"), PathKindGuess::Directory);
+        assert_eq!(guess_path_kind("a/b\\"), PathKindGuess::Directory);
+        assert_eq!(guess_path_kind("archive.d/"), PathKindGuess::Directory);
+        assert_eq!(guess_path_kind("a/.."), PathKindGuess::Directory);
+        assert_eq!(guess_path_kind("."), PathKindGuess::Directory);
+
+        // Extensions
+        assert_eq!(guess_path_kind("foo.txt"), PathKindGuess::File);
+        assert_eq!(guess_path_kind("src/main.rs"), PathKindGuess::File);
+        assert_eq!(guess_path_kind("dist\\bundle.min.js"), PathKindGuess::File);
+
+        // No signal either way
+        assert_eq!(guess_path_kind("foo"), PathKindGuess::Ambiguous);
+        assert_eq!(guess_path_kind("src/Makefile"), PathKindGuess::Ambiguous);
+        assert_eq!(guess_path_kind(".config"), PathKindGuess::Ambiguous);
+        assert_eq!(guess_path_kind("file."), PathKindGuess::Ambiguous);
+        assert_eq!(guess_path_kind(""), PathKindGuess::Ambiguous);
+    }
+
+    #[test]
+    fn test_is_hidden() {
+        assert!(is_hidden(".env"));
+        assert!(is_hidden("/.bashrc"));
+        assert!(is_hidden("a/.git/config"));
+        assert!(is_hidden("a\\b\\.cache\\x\\y.bin"));
+        assert!(is_hidden("src/.hidden"));
+        assert!(is_hidden(".../x"));
+        assert!(is_hidden("a/.git/"));
+    }
+
+    #[test]
+    fn test_is_hidden_visible_paths() {
+        assert!(!is_hidden("a/b.txt"));
+        assert!(!is_hidden("archive.tar.gz"));
+        assert!(!is_hidden("./src/main.rs"));
+        assert!(!is_hidden("../sibling/file"));
+        assert!(!is_hidden("a/./b/../c"));
+        assert!(!is_hidden(""));
+        assert!(!is_hidden("/"));
+    }
+}
@@ -0,0 +1,434 @@
+//! Lexical path comparison
+//!
+//! Equality, ordering and hashing keys that look past differences in spelling
+//! (separators, redundant slashes and, where the platform ignores it, case)
+//! without touching the filesystem.
+
+use crate::normalize::normalize_path_str_preserving_root;
+use crate::relative::split_root;
+use alloc::string::String;
+use core::cmp::Ordering;
+
+/// How letter case is treated when comparing paths
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PathComparison {
+    /// Follow the target platform: case-insensitive on Windows, case-sensitive
+    /// elsewhere
+    #[default]
+    Platform,
+    /// `A` and `a` are different characters, as on most Unix filesystems
+    CaseSensitive,
+    /// `A` and `a` are the same character, as on NTFS and (by default) APFS
+    CaseInsensitive,
+}
+
+impl PathComparison {
+    /// Whether this comparison ignores case on the current platform
+    pub fn is_case_insensitive(self) -> bool {
+        match self {
+            PathComparison::Platform => cfg!(windows),
+            PathComparison::CaseSensitive => false,
+            PathComparison::CaseInsensitive => true,
+        }
+    }
+}
+
+/// Check whether two paths refer to the same location, using the platform's case rules
+///
+/// Equivalent to [`paths_equal_with`] with [`PathComparison::Platform`].
+///
+/// # Examples
+/// ```
+/// use path_utils::paths_equal;
+///
+/// assert!(paths_equal("src//main.rs", "src\\main.rs"));
+/// assert!(!paths_equal("/src/main.rs", "src/main.rs"));
+/// assert_eq!(paths_equal("SRC/Main.rs", "src/main.rs"), cfg!(windows));
+/// ```
+pub fn paths_equal(a: &str, b: &str) -> bool {
+    paths_equal_with(a, b, PathComparison::Platform)
+}
+
+/// Check whether two paths refer to the same location
+///
+/// Two paths are equal exactly when their [`canonical_key_with`] keys are, so
+/// separators and redundant slashes don't matter but a leading root does:
+/// `/a` and `a` are different paths. With a case-insensitive `comparison`,
+/// letters are compared by their Unicode lowercase forms. The comparison is
+/// purely lexical: `.` and `..` are not resolved and symlinks are not followed.
+///
+/// # Examples
+/// ```
+/// use path_utils::{paths_equal_with, PathComparison};
+///
+/// assert!(paths_equal_with("A\\B\\C", "a/b/c", PathComparison::CaseInsensitive));
+/// assert!(!paths_equal_with("A\\B\\C", "a/b/c", PathComparison::CaseSensitive));
+/// ```
+pub fn paths_equal_with(a: &str, b: &str, comparison: PathComparison) -> bool {
+    canonical_key_with(a, comparison) == canonical_key_with(b, comparison)
+}
+
+/// A string to use as a map key for a path, using the platform's case rules
+///
+/// Equivalent to [`canonical_key_with`] with [`PathComparison::Platform`].
+///
+/// # Examples
+/// ```
+/// use path_utils::canonical_key;
+///
+/// assert_eq!(canonical_key("src\\main.rs"), canonical_key("src//main.rs"));
+/// ```
+pub fn canonical_key(path: &str) -> String {
+    canonical_key_with(path, PathComparison::Platform)
+}
+
+/// A string to use as a map key for a path
+///
+/// The path is normalized with
+/// [`normalize_path_str_preserving_root`](crate::normalize_path_str_preserving_root),
+/// and lowercased when `comparison` is case-insensitive, so paths that
+/// [`paths_equal_with`] considers equal get the same key and hash the same in
+/// a `HashMap` or `BTreeMap`. On Windows, `a\B` and `a/b` share a key.
+///
+/// This is lexical only, not filesystem canonicalization: `.` and `..` are
+/// kept, symlinks are not resolved, and the path need not exist. The key is
+/// meant for lookups, not for opening files.
+///
+/// # Examples
+/// ```
+/// use path_utils::{canonical_key_with, PathComparison};
+///
+/// let key = canonical_key_with("A\\B//c.TXT", PathComparison::CaseInsensitive);
+/// assert_eq!(key, "a/b/c.txt");
+/// ```
+pub fn canonical_key_with(path: &str, comparison: PathComparison) -> String {
+    let normalized = normalize_path_str_preserving_root(path);
+    if comparison.is_case_insensitive() {
+        normalized.chars().flat_map(char::to_lowercase).collect()
+    } else {
+        normalized
+    }
+}
+
+/// Order two paths as a tree, component by component
+///
+/// Both paths are normalized with
+/// [`normalize_path_str_preserving_root`], then compared one component at a
+/// time, so a directory sorts directly before its contents and siblings sort
+/// by name: `a` < `a/b` < `a/c` < `ab`. A plain string sort would put `a.txt`
+/// between `a` and `a/b`, since `.` sorts before `/`. Components are compared
+/// by code point, case-sensitively. Relative paths sort before rooted ones,
+/// which sort before drive paths, ordered by letter regardless of case. Apart
+/// from the drive letter, two paths compare equal exactly when
+/// [`paths_equal_with`] with [`PathComparison::CaseSensitive`] says they are.
+///
+/// # Examples
+/// ```
+/// use path_utils::compare_paths;
+///
+/// let mut paths = vec!["a.txt", "a/b.txt", "a", "a\\a.txt"];
+/// paths.sort_by(|a, b| compare_paths(a, b));
+/// assert_eq!(paths, ["a", "a\\a.txt", "a/b.txt", "a.txt"]);
+/// ```
+pub fn compare_paths(a: &str, b: &str) -> Ordering {
+    compare_paths_by(a, b, str::cmp)
+}
+
+/// Order two paths as a tree, comparing numbers in names by value
+///
+/// The same as [`compare_paths`], except that within each component, runs of
+/// ASCII digits are compared as numbers, so `img2.png` sorts before
+/// `img10.png` and `v1.9` before `v1.10`. Everything else is compared by code
+/// point. When two numbers are equal but spelled with a different number of
+/// leading zeros (`01` and `1`), the comparison moves on, and only if nothing
+/// else differs does the shorter spelling sort first. The digit runs are
+/// scanned in place, without allocating, however long they are.
+///
+/// # Examples
+/// ```
+/// use path_utils::compare_paths_natural;
+///
+/// let mut frames = vec!["frame10.png", "frame9.png", "frame100.png"];
+/// frames.sort_by(|a, b| compare_paths_natural(a, b));
+/// assert_eq!(frames, ["frame9.png", "frame10.png", "frame100.png"]);
+/// ```
+pub fn compare_paths_natural(a: &str, b: &str) -> Ordering {
+    compare_paths_by(a, b, compare_natural)
+}
+
+/// Compare normalized paths by root, then component by component with `compare_component`
+fn compare_paths_by(a: &str, b: &str, compare_component: fn(&str, &str) -> Ordering) -> Ordering {
+    let (a, b) = (
+        normalize_path_str_preserving_root(a),
+        normalize_path_str_preserving_root(b),
+    );
+    let (root_a, rest_a) = split_root(&a);
+    let (root_b, rest_b) = split_root(&b);
+
+    root_a.cmp(&root_b).then_with(|| {
+        let mut components_a = rest_a.split('/').filter(|c| !c.is_empty());
+        let mut components_b = rest_b.split('/').filter(|c| !c.is_empty());
+        loop {
+            match (components_a.next(), components_b.next()) {
+                (None, None) => return Ordering::Equal,
+                (None, Some(_)) => return Ordering::Less,
+                (Some(_), None) => return Ordering::Greater,
+                (Some(a), Some(b)) => match compare_component(a, b) {
+                    Ordering::Equal => {}
+                    unequal => return unequal,
+                },
+            }
+        }
+    })
+}
+
+/// Compare two names, treating runs of ASCII digits as numbers
+fn compare_natural(a: &str, b: &str) -> Ordering {
+    let (a_bytes, b_bytes) = (a.as_bytes(), b.as_bytes());
+    let (mut i, mut j) = (0, 0);
+
+    while i < a_bytes.len() && j < b_bytes.len() {
+        if a_bytes[i].is_ascii_digit() && b_bytes[j].is_ascii_digit() {
+            let a_end = digit_run_end(a_bytes, i);
+            let b_end = digit_run_end(b_bytes, j);
+            match compare_numbers(&a[i..a_end], &b[j..b_end]) {
+                Ordering::Equal => {}
+                unequal => return unequal,
+            }
+            i = a_end;
+            j = b_end;
+        } else {
+            // Outside digit runs, compare a character at a time
+            let a_char = a[i..].chars().next().unwrap_or_default();
+            let b_char = b[j..].chars().next().unwrap_or_default();
+            match a_char.cmp(&b_char) {
+                Ordering::Equal => {}
+                unequal => return unequal,
+            }
+            i += a_char.len_utf8();
+            j += b_char.len_utf8();
+        }
+    }
+
+    (a_bytes.len() - i)
+        .cmp(&(b_bytes.len() - j))
+        // Equal apart from leading zeros: fall back to the spelling
+        .then_with(|| a.len().cmp(&b.len()))
+        .then_with(|| a.cmp(b))
+}
+
+/// The index just past the run of ASCII digits starting at `start`
+fn digit_run_end(bytes: &[u8], start: usize) -> usize {
+    bytes[start..]
+        .iter()
+        .position(|b| !b.is_ascii_digit())
+        .map_or(bytes.len(), |len| start + len)
+}
+
+/// Compare two runs of ASCII digits by value, however long they are
+fn compare_numbers(a: &str, b: &str) -> Ordering {
+    let a = a.trim_start_matches('0');
+    let b = b.trim_start_matches('0');
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths_equal_separators() {
+        assert!(paths_equal("a/b/c", "a\\b\\c"));
+        assert!(paths_equal("a//b///c/", "a/b/c"));
+        assert!(paths_equal("C:\\Users\\me", "C:/Users//me/"));
+        assert!(paths_equal("/srv//www", "\\srv\\www"));
+        assert!(paths_equal("", ""));
+
+        assert!(!paths_equal("a/b", "a/c"));
+        assert!(!paths_equal("a/b", "a/b/c"));
+        assert!(!paths_equal("/a", "a"));
+
+        // Dot segments are compared as written
+        assert!(!paths_equal("a/./b", "a/b"));
+    }
+
+    #[test]
+    fn test_paths_equal_case() {
+        assert!(paths_equal_with(
+            "A/B/C",
+            "a\\b\\c",
+            PathComparison::CaseInsensitive
+        ));
+        assert!(paths_equal_with(
+            "Données/ÉTÉ.txt",
+            "données/été.TXT",
+            PathComparison::CaseInsensitive
+        ));
+        assert!(!paths_equal_with(
+            "A/B/C",
+            "a/b/c",
+            PathComparison::CaseSensitive
+        ));
+        assert!(!paths_equal_with(
+            "a/b",
+            "a/c",
+            PathComparison::CaseInsensitive
+        ));
+
+        if cfg!(windows) {
+            assert!(paths_equal("A/B/C", "a/b/c"));
+        } else {
+            assert!(!paths_equal("A/B/C", "a/b/c"));
+        }
+    }
+
+    #[test]
+    fn test_path_comparison() {
+        assert_eq!(PathComparison::default(), PathComparison::Platform);
+        assert_eq!(
+            PathComparison::Platform.is_case_insensitive(),
+            cfg!(windows)
+        );
+        assert!(PathComparison::CaseInsensitive.is_case_insensitive());
+        assert!(!PathComparison::CaseSensitive.is_case_insensitive());
+    }
+
+    #[test]
+    fn test_canonical_key() {
+        assert_eq!(
+            canonical_key_with("a\\B", PathComparison::CaseInsensitive),
+            canonical_key_with("a/b", PathComparison::CaseInsensitive)
+        );
+        assert_eq!(
+            canonical_key_with("/srv//www/", PathComparison::CaseSensitive),
+            "/srv/www"
+        );
+        assert_eq!(
+            canonical_key_with("C:\\Temp", PathComparison::CaseInsensitive),
+            "c:/temp"
+        );
+        assert_eq!(canonical_key("a\\b//c"), canonical_key("a/b/c"));
+        assert_eq!(canonical_key("a\\B") == canonical_key("a/b"), cfg!(windows));
+    }
+
+    #[test]
+    fn test_canonical_key_distinct_paths() {
+        use alloc::collections::BTreeSet;
+
+        let paths = ["a/b", "a/c", "/a/b", "a/b/c", "ab", "a/./b", "a b"];
+        for comparison in [
+            PathComparison::CaseSensitive,
+            PathComparison::CaseInsensitive,
+        ] {
+            let keys: BTreeSet<String> = paths
+                .iter()
+                .map(|path| canonical_key_with(path, comparison))
+                .collect();
+            assert_eq!(keys.len(), paths.len());
+        }
+
+        assert_ne!(
+            canonical_key_with("A", PathComparison::CaseSensitive),
+            canonical_key_with("a", PathComparison::CaseSensitive)
+        );
+    }
+
+    #[test]
+    fn test_compare_paths_tree_order() {
+        let ordered = ["a", "a/b", "a/b/c", "a/c", "a.txt", "ab", "b"];
+        for (i, a) in ordered.iter().enumerate() {
+            for (j, b) in ordered.iter().enumerate() {
+                assert_eq!(compare_paths(a, b), i.cmp(&j), "{:?} vs {:?}", a, b);
+            }
+        }
+
+        let mut paths = ["ab", "a/c", "a.txt", "a/b", "a"];
+        paths.sort_by(|a, b| compare_paths(a, b));
+        assert_eq!(paths, ["a", "a/b", "a/c", "a.txt", "ab"]);
+    }
+
+    #[test]
+    fn test_compare_paths_separators() {
+        assert_eq!(compare_paths("a\\b", "a/b"), Ordering::Equal);
+        assert_eq!(compare_paths("a//b/", "a/b"), Ordering::Equal);
+        assert_eq!(compare_paths("a\\b", "a/c"), Ordering::Less);
+        assert_eq!(compare_paths("a\\c", "a//b"), Ordering::Greater);
+
+        // Relative, then rooted, then drive paths
+        assert_eq!(compare_paths("z", "/a"), Ordering::Less);
+        assert_eq!(compare_paths("/z", "C:/a"), Ordering::Less);
+        assert_eq!(compare_paths("c:\\a", "C:/a"), Ordering::Equal);
+        assert_eq!(compare_paths("C:/a", "D:/a"), Ordering::Less);
+
+        // Case-sensitive, by code point
+        assert_eq!(compare_paths("B", "a"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_compare_paths_agrees_with_paths_equal() {
+        let paths = [
+            "a/b", "a\\b", "/a/b", "a/./b", "A/b", "", "/", "C:/x", "C:\\x",
+        ];
+        for a in paths {
+            for b in paths {
+                assert_eq!(
+                    compare_paths(a, b) == Ordering::Equal,
+                    paths_equal_with(a, b, PathComparison::CaseSensitive),
+                    "{:?} vs {:?}",
+                    a,
+                    b
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_compare_paths_natural() {
+        assert_eq!(
+            compare_paths_natural("img2.png", "img10.png"),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_paths_natural("img10.png", "img2.png"),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare_paths_natural("img2.png", "img2.png"),
+            Ordering::Equal
+        );
+
+        // Plain comparison would put these the other way round
+        assert_eq!(compare_paths("img2.png", "img10.png"), Ordering::Greater);
+
+        let mut names = [
+            "v1.10/a", "v1.9/a", "v1.9/b", "v10/a", "v2", "v1.9", "release", "a1b10", "a1b2",
+        ];
+        names.sort_by(|a, b| compare_paths_natural(a, b));
+        assert_eq!(
+            names,
+            ["a1b2", "a1b10", "release", "v1.9", "v1.9/a", "v1.9/b", "v1.10/a", "v2", "v10/a"]
+        );
+    }
+
+    #[test]
+    fn test_compare_paths_natural_mixed() {
+        // Digits against letters compare by code point
+        assert_eq!(compare_paths_natural("1a", "a1"), Ordering::Less);
+        assert_eq!(compare_paths_natural("file", "file1"), Ordering::Less);
+
+        // Leading zeros only break ties
+        assert_eq!(compare_paths_natural("01", "1"), Ordering::Greater);
+        assert_eq!(compare_paths_natural("01b", "1a"), Ordering::Greater);
+        assert_eq!(compare_paths_natural("001", "2"), Ordering::Less);
+
+        // Numbers too long for any integer type
+        let big = format!("{}1", "9".repeat(40));
+        let bigger = format!("1{}", "0".repeat(41));
+        assert_eq!(compare_paths_natural(&big, &bigger), Ordering::Less);
+
+        // Still tree-ordered, and separators don't matter
+        assert_eq!(compare_paths_natural("a10\\b", "a10/b"), Ordering::Equal);
+        assert_eq!(compare_paths_natural("a2/z", "a10"), Ordering::Less);
+        assert_eq!(compare_paths_natural("é2", "é10"), Ordering::Less);
+    }
+}
@@ -0,0 +1,234 @@
+//! Component accessors for string paths
+//!
+//! String counterparts to `Path::file_stem`, `Path::extension` and
+//! `Path::parent` that follow the crate's own separator and extension rules
+//! rather than the host platform's.
+
+use crate::classify::file_extension;
+use crate::error::{PathError, Result};
+use crate::filename::is_replaced_in_filename;
+use crate::normalize::{normalize_path_str, normalized_components};
+use crate::validate::validate_path;
+use alloc::string::{String, ToString};
+
+/// Split the final component of a path into its stem and extension
+///
+/// Both `/` and `\` are separators, and empty components from trailing or
+/// repeated separators are skipped, so `a/b.txt/` splits `b.txt`. The
+/// extension is the text after the last `.`: only the last extension counts
+/// (`b.tar.gz` gives `gz`), a leading dot marks a hidden name rather than an
+/// extension (`.bashrc` has none), and a name ending in a dot has none. The
+/// returned slices borrow from `path`.
+///
+/// # Examples
+/// ```
+/// use path_utils::split_extension;
+///
+/// assert_eq!(split_extension("a/b.tar.gz"), ("b.tar", Some("gz")));
+/// assert_eq!(split_extension("home/.bashrc"), (".bashrc", None));
+/// assert_eq!(split_extension("Makefile"), ("Makefile", None));
+/// ```
+pub fn split_extension(path: &str) -> (&str, Option<&str>) {
+    let file_name = normalized_components(path).last().unwrap_or("");
+    match file_extension(file_name) {
+        Some(extension) => (
+            &file_name[..file_name.len() - extension.len() - 1],
+            Some(extension),
+        ),
+        None => (file_name, None),
+    }
+}
+
+/// Replace the extension of the final component, validating the new one
+///
+/// The path is normalized first, then the final component's extension, as
+/// [`split_extension`] finds it, is replaced with `extension`, or `extension`
+/// is appended if there was none. An empty `extension` removes the existing
+/// one. The result is checked with [`validate_path`].
+///
+/// # Errors
+/// - [`PathError::ContainsSeparator`] if `extension` contains `/` or `\`
+/// - [`PathError::InvalidCharacters`] if `extension` contains a `.`, a `:`, a
+///   control character or any other character [`sanitize_filename`] would
+///   replace
+/// - [`PathError::EmptyPath`] if the path has no components
+/// - Any error from [`validate_path`] for the resulting path
+///
+/// [`sanitize_filename`]: crate::sanitize_filename
+///
+/// # Examples
+/// ```
+/// use path_utils::with_extension;
+///
+/// assert_eq!(with_extension("a/b.txt", "md").unwrap(), "a/b.md");
+/// assert_eq!(with_extension("a/README", "md").unwrap(), "a/README.md");
+/// assert!(with_extension("a/b.txt", "../x").is_err());
+/// ```
+pub fn with_extension(path: &str, extension: &str) -> Result<String> {
+    if extension.contains(['/', '\\']) {
+        return Err(PathError::ContainsSeparator {
+            name: extension.to_string(),
+        });
+    }
+    if extension
+        .chars()
+        .any(|c| c == '.' || is_replaced_in_filename(c))
+    {
+        return Err(PathError::InvalidCharacters {
+            path: extension.to_string(),
+        });
+    }
+
+    let normalized = normalize_path_str(path);
+    if normalized.is_empty() {
+        return Err(PathError::EmptyPath);
+    }
+
+    let (stem, _) = split_extension(&normalized);
+    let parent_len = normalized.rfind('/').map_or(0, |index| index + 1);
+    let mut result = String::with_capacity(parent_len + stem.len() + extension.len() + 1);
+    result.push_str(&normalized[..parent_len]);
+    result.push_str(stem);
+    if !extension.is_empty() {
+        result.push('.');
+        result.push_str(extension);
+    }
+
+    validate_path(&result)?;
+    Ok(result)
+}
+
+/// The normalized parent directory of a path
+///
+/// The path is normalized with [`normalize_path_str`] and its final component
+/// dropped, so the result uses forward slashes and has no leading or trailing
+/// separator. Returns `None` when there is no parent to name: for an empty
+/// path or a single component. Like normalization, this is purely lexical, so
+/// the parent of `a/..` is `a`.
+///
+/// # Examples
+/// ```
+/// use path_utils::parent_path;
+///
+/// assert_eq!(parent_path("a/b/c.txt").as_deref(), Some("a/b"));
+/// assert_eq!(parent_path("c.txt"), None);
+/// ```
+pub fn parent_path(path: &str) -> Option<String> {
+    let mut normalized = normalize_path_str(path);
+    let parent_len = normalized.rfind('/')?;
+    normalized.truncate(parent_len);
+    Some(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_extension() {
+        assert_eq!(split_extension("a/b.tar.gz"), ("b.tar", Some("gz")));
+        assert_eq!(split_extension("report.pdf"), ("report", Some("pdf")));
+        assert_eq!(split_extension("src\\main.rs"), ("main", Some("rs")));
+        assert_eq!(split_extension("docs/guide.md/"), ("guide", Some("md")));
+
+        // Only the final component is considered
+        assert_eq!(split_extension("v1.2/README"), ("README", None));
+    }
+
+    #[test]
+    fn test_split_extension_without_extension() {
+        assert_eq!(split_extension("Makefile"), ("Makefile", None));
+        assert_eq!(split_extension(".bashrc"), (".bashrc", None));
+        assert_eq!(split_extension("home/.gitignore"), (".gitignore", None));
+        assert_eq!(split_extension("trailing."), ("trailing.", None));
+        assert_eq!(split_extension(""), ("", None));
+        assert_eq!(split_extension("/"), ("", None));
+
+        // A dotfile can still have an extension of its own
+        assert_eq!(split_extension(".env.local"), (".env", Some("local")));
+    }
+
+    #[test]
+    fn test_with_extension() {
+        assert_eq!(with_extension("a/b.txt", "md").unwrap(), "a/b.md");
+        assert_eq!(
+            with_extension("archive.tar.gz", "zst").unwrap(),
+            "archive.tar.zst"
+        );
+        assert_eq!(
+            with_extension("/docs\\guide.txt", "html").unwrap(),
+            "docs/guide.html"
+        );
+
+        // Appended when there is no extension
+        assert_eq!(with_extension("a/README", "md").unwrap(), "a/README.md");
+        assert_eq!(with_extension(".env", "bak").unwrap(), ".env.bak");
+
+        // An empty extension removes the existing one
+        assert_eq!(with_extension("a/b.txt", "").unwrap(), "a/b");
+    }
+
+    #[test]
+    fn test_with_extension_rejects_unsafe_extensions() {
+        for extension in ["txt/evil", "../x", "a\\b"] {
+            assert!(
+                matches!(
+                    with_extension("a/b.txt", extension),
+                    Err(PathError::ContainsSeparator { .. })
+                ),
+                "should be rejected: {:?}",
+                extension
+            );
+        }
+
+        for extension in ["tar.gz", ".", "ex:e", "ph<p", "t\0xt", "exe\u{202E}"] {
+            assert!(
+                matches!(
+                    with_extension("a/b.txt", extension),
+                    Err(PathError::InvalidCharacters { .. })
+                ),
+                "should be rejected: {:?}",
+                extension
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_extension_validates_path() {
+        assert!(matches!(
+            with_extension("", "md"),
+            Err(PathError::EmptyPath)
+        ));
+        assert!(matches!(
+            with_extension("../b.txt", "md"),
+            Err(PathError::PathTraversal { .. })
+        ));
+        assert!(matches!(
+            with_extension("a/CON.txt", "md"),
+            Err(PathError::ReservedFilename { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parent_path() {
+        assert_eq!(parent_path("a/b/c.txt").as_deref(), Some("a/b"));
+        assert_eq!(parent_path("a/b").as_deref(), Some("a"));
+        assert_eq!(
+            parent_path("/src\\bin//main.rs").as_deref(),
+            Some("src/bin")
+        );
+
+        // Trailing separators don't count as a component
+        assert_eq!(parent_path("a/b/").as_deref(), Some("a"));
+        assert_eq!(parent_path("a/b//").as_deref(), Some("a"));
+    }
+
+    #[test]
+    fn test_parent_path_without_parent() {
+        assert_eq!(parent_path("c.txt"), None);
+        assert_eq!(parent_path("/c.txt"), None);
+        assert_eq!(parent_path("dir/"), None);
+        assert_eq!(parent_path(""), None);
+        assert_eq!(parent_path("/"), None);
+    }
+}
@@ -1,9 +1,36 @@
 //! Error types for path utility operations
 
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use thiserror::Error;
 
 /// The error type for path utility operations
+///
+/// New variants are added as new checks are introduced, so the enum is
+/// `#[non_exhaustive]` and a `match` outside this crate needs a wildcard arm.
+/// Matching on [`category`](PathError::category) instead keeps handling
+/// correct for variants added later.
+///
+/// # Examples
+/// ```
+/// use path_utils::{validate_path, ErrorCategory, PathError};
+///
+/// fn describe(error: &PathError) -> &'static str {
+///     match error {
+///         PathError::EmptyPath => "nothing to check",
+///         PathError::PathTraversal { .. } => "escapes its directory",
+///         other => match other.category() {
+///             ErrorCategory::Security => "unsafe",
+///             _ => "invalid",
+///         },
+///     }
+/// }
+///
+/// assert_eq!(describe(&validate_path("../x").unwrap_err()), "escapes its directory");
+/// assert_eq!(describe(&validate_path("a<b").unwrap_err()), "unsafe");
+/// ```
 #[derive(Error, Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum PathError {
     /// Path traversal attack detected (contains .. components)
     #[error("Path traversal detected: {path} - relative paths with '..' are not allowed")]
@@ -25,26 +52,496 @@ pub enum PathError {
     #[error("Drive letter paths are not allowed: {path}")]
     DriveLetterPath { path: String },
 
+    /// Absolute path where a relative one was required
+    #[error("Absolute paths are not allowed: {path}")]
+    AbsolutePath { path: String },
+
+    /// Windows UNC path (`\\server\share`), which can point at another machine
+    #[error("UNC paths are not allowed: {path}")]
+    UncPath { path: String },
+
+    /// Path longer than the allowed maximum, measured in bytes
+    #[error("Path is too long: {length} bytes exceeds the maximum of {max}")]
+    PathTooLong { length: usize, max: usize },
+
+    /// Path with more components than the allowed maximum
+    #[error("Path is too deep: {depth} components exceeds the maximum of {max}")]
+    PathTooDeep { depth: usize, max: usize },
+
+    /// A single path component longer than the allowed maximum, measured in bytes
+    #[error("Path component is too long: {component} is {length} bytes")]
+    ComponentTooLong { component: String, length: usize },
+
+    /// A symlink where a path was required to consist of real directories and files
+    #[error("Symlink in path: {path}")]
+    SymlinkInPath { path: String },
+
+    /// File extension rejected by the extension policy
+    #[error("Forbidden file extension: {extension} in path {path}")]
+    ForbiddenExtension { extension: String, path: String },
+
+    /// A single file name that contains a path separator
+    #[error("File name contains a path separator: {name}")]
+    ContainsSeparator { name: String },
+
     /// General path validation failure
-    #[error("Path validation failed: {message}")]
-    ValidationFailed { message: String },
+    ///
+    /// `rule` is a short, stable identifier for the check that failed, such as
+    /// `"zip_name_length"`, for callers that need to branch on it.
+    #[error("Path validation failed ({rule}): {message}")]
+    ValidationFailed { rule: &'static str, message: String },
 
     /// Path construction failure
     #[error("Path construction failed: {message}")]
     ConstructionFailed { message: String },
 
+    /// Several violations found at once, in the order they were found
+    ///
+    /// Returned by [`validate_path_all`](crate::validate_path_all) when more
+    /// than one rule fails. Use [`iter_all`](PathError::iter_all) to go through
+    /// the individual errors.
+    #[error("{}", join_messages(.0))]
+    Multiple(Vec<PathError>),
+
     /// I/O error during path operations
+    ///
+    /// [`kind`](PathError::kind) returns the original [`std::io::ErrorKind`],
+    /// so callers can tell a missing directory (`NotFound`) from a permission
+    /// problem.
     #[error("I/O error: {message}")]
-    IoError { message: String },
+    IoError { message: String, kind: IoErrorKind },
 }
 
-impl From<std::io::Error> for PathError {
-    fn from(err: std::io::Error) -> Self {
+/// The original error kind of a [`PathError::IoError`]
+///
+/// Opaque, so that `IoError` has the same fields with and without the `std`
+/// feature. Read it with [`PathError::kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IoErrorKind {
+    #[cfg(feature = "std")]
+    kind: std::io::ErrorKind,
+}
+
+/// Broad classes of [`PathError`], for deciding how to respond to one
+///
+/// A server would typically answer [`Security`](Self::Security) and
+/// [`InvalidInput`](Self::InvalidInput) with a client error such as 400, and
+/// the other two with a server error such as 500.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// The path was rejected as unsafe: traversal, absolute paths, drive
+    /// letters, UNC paths, forbidden characters, reserved names, symlinks,
+    /// forbidden extensions or separators in a file name
+    Security,
+    /// The path was rejected as malformed rather than dangerous: empty, too
+    /// long or deep, or failing a general validation rule
+    InvalidInput,
+    /// An I/O operation failed
+    Io,
+    /// A path could not be constructed from valid inputs
+    Construction,
+}
+
+impl PathError {
+    /// The broad class of this error
+    ///
+    /// # Examples
+    /// ```
+    /// use path_utils::{validate_path, ErrorCategory};
+    ///
+    /// let err = validate_path("../etc/passwd").unwrap_err();
+    /// assert_eq!(err.category(), ErrorCategory::Security);
+    /// ```
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            PathError::PathTraversal { .. }
+            | PathError::DriveLetterPath { .. }
+            | PathError::AbsolutePath { .. }
+            | PathError::UncPath { .. }
+            | PathError::InvalidCharacters { .. }
+            | PathError::ReservedFilename { .. }
+            | PathError::SymlinkInPath { .. }
+            | PathError::ForbiddenExtension { .. }
+            | PathError::ContainsSeparator { .. } => ErrorCategory::Security,
+            PathError::EmptyPath
+            | PathError::PathTooLong { .. }
+            | PathError::PathTooDeep { .. }
+            | PathError::ComponentTooLong { .. }
+            | PathError::ValidationFailed { .. } => ErrorCategory::InvalidInput,
+            PathError::IoError { .. } => ErrorCategory::Io,
+            PathError::ConstructionFailed { .. } => ErrorCategory::Construction,
+            PathError::Multiple(errors) => {
+                if errors.iter().any(PathError::is_security_violation) {
+                    ErrorCategory::Security
+                } else {
+                    errors
+                        .first()
+                        .map_or(ErrorCategory::InvalidInput, PathError::category)
+                }
+            }
+        }
+    }
+
+    /// The path this error refers to, if it carries one
+    ///
+    /// Returns the `path` field of the variants that have one, for logging
+    /// without matching on every variant. Errors that describe something else,
+    /// such as a length or a single component, return `None`, and
+    /// [`Multiple`](PathError::Multiple) returns the first path among its errors.
+    ///
+    /// # Examples
+    /// ```
+    /// use path_utils::validate_path;
+    ///
+    /// let err = validate_path("logs/CON.txt").unwrap_err();
+    /// assert_eq!(err.offending_path(), Some("logs/CON.txt"));
+    /// ```
+    pub fn offending_path(&self) -> Option<&str> {
+        match self {
+            PathError::PathTraversal { path }
+            | PathError::InvalidCharacters { path }
+            | PathError::DriveLetterPath { path }
+            | PathError::AbsolutePath { path }
+            | PathError::UncPath { path }
+            | PathError::ReservedFilename { path, .. }
+            | PathError::SymlinkInPath { path }
+            | PathError::ForbiddenExtension { path, .. }
+            | PathError::ContainsSeparator { name: path } => Some(path),
+            PathError::EmptyPath
+            | PathError::PathTooLong { .. }
+            | PathError::PathTooDeep { .. }
+            | PathError::ComponentTooLong { .. }
+            | PathError::ValidationFailed { .. }
+            | PathError::ConstructionFailed { .. }
+            | PathError::IoError { .. } => None,
+            PathError::Multiple(errors) => errors.iter().find_map(PathError::offending_path),
+        }
+    }
+
+    /// The individual errors: the contents of [`Multiple`](PathError::Multiple), or just `self`
+    ///
+    /// Lets callers handle every violation the same way whether one rule
+    /// failed or several.
+    ///
+    /// # Examples
+    /// ```
+    /// use path_utils::{validate_path_all, ValidationOptions};
+    ///
+    /// let options = ValidationOptions::new();
+    /// let err = validate_path_all("logs/a<b/CON", &options).unwrap_err();
+    /// assert_eq!(err.iter_all().count(), 2);
+    ///
+    /// let err = validate_path_all("a<b", &options).unwrap_err();
+    /// assert_eq!(err.iter_all().count(), 1);
+    /// ```
+    pub fn iter_all(&self) -> impl Iterator<Item = &PathError> {
+        let errors = match self {
+            PathError::Multiple(errors) => errors.as_slice(),
+            other => core::slice::from_ref(other),
+        };
+        errors.iter()
+    }
+
+    /// Build a [`ValidationFailed`](PathError::ValidationFailed) error for a named rule
+    ///
+    /// Useful for [`TargetProfile`](crate::TargetProfile) implementations and
+    /// other custom checks that want to report failures the same way the
+    /// built-in rules do.
+    ///
+    /// # Examples
+    /// ```
+    /// use path_utils::PathError;
+    ///
+    /// let err = PathError::validation_failed("no_spaces", "a b contains a space");
+    /// assert_eq!(err.rule(), Some("no_spaces"));
+    /// assert_eq!(err.to_string(), "Path validation failed (no_spaces): a b contains a space");
+    /// ```
+    pub fn validation_failed(rule: &'static str, message: impl Into<String>) -> Self {
+        PathError::ValidationFailed {
+            rule,
+            message: message.into(),
+        }
+    }
+
+    /// Create an [`IoError`](PathError::IoError) with its own message and the original kind
+    #[cfg(feature = "std")]
+    pub(crate) fn io_error(message: impl Into<String>, kind: std::io::ErrorKind) -> Self {
         PathError::IoError {
-            message: err.to_string(),
+            message: message.into(),
+            kind: IoErrorKind { kind },
+        }
+    }
+
+    /// The original [`std::io::ErrorKind`], for [`IoError`](PathError::IoError) errors
+    ///
+    /// # Examples
+    /// ```
+    /// use path_utils::PathError;
+    /// use std::io;
+    ///
+    /// let err = PathError::from(io::Error::from(io::ErrorKind::NotFound));
+    /// assert_eq!(err.kind(), Some(io::ErrorKind::NotFound));
+    /// assert_eq!(PathError::EmptyPath.kind(), None);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn kind(&self) -> Option<std::io::ErrorKind> {
+        match self {
+            PathError::IoError { kind, .. } => Some(kind.kind),
+            _ => None,
+        }
+    }
+
+    /// The identifier of the failed rule, for [`ValidationFailed`](PathError::ValidationFailed) errors
+    pub fn rule(&self) -> Option<&'static str> {
+        match self {
+            PathError::ValidationFailed { rule, .. } => Some(rule),
+            _ => None,
         }
     }
+
+    /// Whether the path was rejected for security reasons
+    ///
+    /// Shorthand for `self.category() == ErrorCategory::Security`.
+    pub fn is_security_violation(&self) -> bool {
+        self.category() == ErrorCategory::Security
+    }
+}
+
+/// The messages of several errors, separated by `; `
+fn join_messages(errors: &[PathError]) -> String {
+    errors
+        .iter()
+        .map(|error| error.to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for PathError {
+    fn from(err: std::io::Error) -> Self {
+        PathError::io_error(err.to_string(), err.kind())
+    }
 }
 
 /// Result type for path utility operations
-pub type Result<T> = std::result::Result<T, PathError>;
\ No newline at end of file
+pub type Result<T> = core::result::Result<T, PathError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    const IO_NOT_FOUND: IoErrorKind = IoErrorKind {
+        #[cfg(feature = "std")]
+        kind: std::io::ErrorKind::NotFound,
+    };
+
+    #[test]
+    fn test_category() {
+        let path = || "a/b".to_string();
+        let cases = [
+            (
+                PathError::PathTraversal { path: path() },
+                ErrorCategory::Security,
+            ),
+            (
+                PathError::DriveLetterPath { path: path() },
+                ErrorCategory::Security,
+            ),
+            (
+                PathError::AbsolutePath { path: path() },
+                ErrorCategory::Security,
+            ),
+            (PathError::UncPath { path: path() }, ErrorCategory::Security),
+            (
+                PathError::InvalidCharacters { path: path() },
+                ErrorCategory::Security,
+            ),
+            (
+                PathError::ReservedFilename {
+                    filename: "CON".to_string(),
+                    path: path(),
+                },
+                ErrorCategory::Security,
+            ),
+            (
+                PathError::SymlinkInPath { path: path() },
+                ErrorCategory::Security,
+            ),
+            (
+                PathError::ForbiddenExtension {
+                    extension: "exe".to_string(),
+                    path: path(),
+                },
+                ErrorCategory::Security,
+            ),
+            (
+                PathError::ContainsSeparator { name: path() },
+                ErrorCategory::Security,
+            ),
+            (PathError::EmptyPath, ErrorCategory::InvalidInput),
+            (
+                PathError::PathTooLong { length: 10, max: 5 },
+                ErrorCategory::InvalidInput,
+            ),
+            (
+                PathError::PathTooDeep { depth: 10, max: 5 },
+                ErrorCategory::InvalidInput,
+            ),
+            (
+                PathError::ComponentTooLong {
+                    component: path(),
+                    length: 300,
+                },
+                ErrorCategory::InvalidInput,
+            ),
+            (
+                PathError::validation_failed("test", path()),
+                ErrorCategory::InvalidInput,
+            ),
+            (
+                PathError::ConstructionFailed { message: path() },
+                ErrorCategory::Construction,
+            ),
+            (
+                PathError::IoError {
+                    message: path(),
+                    kind: IO_NOT_FOUND,
+                },
+                ErrorCategory::Io,
+            ),
+        ];
+
+        let cases = cases.into_iter().chain([
+            (
+                PathError::Multiple(vec![
+                    PathError::PathTooLong { length: 10, max: 5 },
+                    PathError::ReservedFilename {
+                        filename: "CON".to_string(),
+                        path: path(),
+                    },
+                ]),
+                ErrorCategory::Security,
+            ),
+            (
+                PathError::Multiple(vec![
+                    PathError::PathTooLong { length: 10, max: 5 },
+                    PathError::PathTooDeep { depth: 10, max: 5 },
+                ]),
+                ErrorCategory::InvalidInput,
+            ),
+        ]);
+        for (err, category) in cases {
+            assert_eq!(err.category(), category, "{:?}", err);
+            assert_eq!(
+                err.is_security_violation(),
+                category == ErrorCategory::Security,
+                "{:?}",
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_offending_path() {
+        let with_path = [
+            PathError::PathTraversal {
+                path: "../a".to_string(),
+            },
+            PathError::InvalidCharacters {
+                path: "../a".to_string(),
+            },
+            PathError::DriveLetterPath {
+                path: "../a".to_string(),
+            },
+            PathError::AbsolutePath {
+                path: "../a".to_string(),
+            },
+            PathError::UncPath {
+                path: "../a".to_string(),
+            },
+            PathError::ReservedFilename {
+                filename: "CON".to_string(),
+                path: "../a".to_string(),
+            },
+            PathError::SymlinkInPath {
+                path: "../a".to_string(),
+            },
+            PathError::ForbiddenExtension {
+                extension: "exe".to_string(),
+                path: "../a".to_string(),
+            },
+            PathError::ContainsSeparator {
+                name: "../a".to_string(),
+            },
+        ];
+        for err in &with_path {
+            assert_eq!(err.offending_path(), Some("../a"), "{:?}", err);
+        }
+
+        let without_path = [
+            PathError::EmptyPath,
+            PathError::PathTooLong { length: 10, max: 5 },
+            PathError::PathTooDeep { depth: 10, max: 5 },
+            PathError::ComponentTooLong {
+                component: "abc".to_string(),
+                length: 300,
+            },
+            PathError::validation_failed("test", "../a"),
+            PathError::ConstructionFailed {
+                message: "../a".to_string(),
+            },
+            PathError::IoError {
+                message: "../a".to_string(),
+                kind: IO_NOT_FOUND,
+            },
+        ];
+        for err in &without_path {
+            assert_eq!(err.offending_path(), None, "{:?}", err);
+        }
+    }
+
+    #[test]
+    fn test_validation_failed_rule() {
+        let err = PathError::validation_failed("zip_name_length", "too long");
+        assert_eq!(
+            err,
+            PathError::ValidationFailed {
+                rule: "zip_name_length",
+                message: "too long".to_string(),
+            }
+        );
+        assert_eq!(err.rule(), Some("zip_name_length"));
+        assert_eq!(
+            err.to_string(),
+            "Path validation failed (zip_name_length): too long"
+        );
+
+        assert_eq!(PathError::EmptyPath.rule(), None);
+    }
+
+    #[test]
+    fn test_multiple() {
+        let err = PathError::Multiple(vec![
+            PathError::PathTooLong { length: 10, max: 5 },
+            PathError::InvalidCharacters {
+                path: "a<b".to_string(),
+            },
+        ]);
+        assert_eq!(
+            err.to_string(),
+            "Path is too long: 10 bytes exceeds the maximum of 5; \
+             Invalid characters detected in path: a<b"
+        );
+        assert_eq!(err.offending_path(), Some("a<b"));
+        assert_eq!(err.rule(), None);
+
+        let inner: Vec<&PathError> = err.iter_all().collect();
+        assert_eq!(inner.len(), 2);
+        assert!(matches!(inner[0], PathError::PathTooLong { .. }));
+
+        // A single error iterates over itself
+        let single = PathError::EmptyPath;
+        assert_eq!(single.iter_all().collect::<Vec<_>>(), [&single]);
+    }
+}
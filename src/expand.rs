@@ -0,0 +1,412 @@
+//! Explicit expansion of user-supplied path shorthands
+//!
+//! Nothing else in the crate expands `~` or environment variables, since doing
+//! so behind the caller's back would let a path reach places its author never
+//! wrote down. These functions are opt-in for when that's what the user meant.
+
+use crate::error::{PathError, Result};
+use crate::normalize::normalize_path_str_preserving_root;
+use crate::validate::validate_path;
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+/// Expand a leading `~` to the current user's home directory
+///
+/// Only a bare `~` or one followed by a separator (`~/projects`, or `~\projects`)
+/// is expanded. A tilde anywhere else, as in `foo/~/bar` or `backup~`, and the
+/// `~user` form are left alone, and so is every path without one. The home
+/// directory is read from `HOME`, or on Windows from `USERPROFILE` first.
+///
+/// The rest of the path is appended as written; run the result through
+/// validation if it came from an untrusted source.
+///
+/// # Errors
+/// [`PathError::ConstructionFailed`] if the path needs expanding and no home
+/// directory is set.
+///
+/// # Examples
+/// ```
+/// use path_utils::expand_tilde;
+/// use std::path::PathBuf;
+///
+/// assert_eq!(expand_tilde("src/~/lib.rs").unwrap(), PathBuf::from("src/~/lib.rs"));
+///
+/// // Fails only when no home directory is set
+/// if let Ok(expanded) = expand_tilde("~/notes.txt") {
+///     assert!(expanded.ends_with("notes.txt"));
+/// }
+/// ```
+pub fn expand_tilde(path: &str) -> Result<PathBuf> {
+    expand_tilde_with_home(path, home_dir())
+}
+
+/// Expand a leading `~` against a given home directory
+fn expand_tilde_with_home(path: &str, home: Option<OsString>) -> Result<PathBuf> {
+    let rest = match path.strip_prefix('~') {
+        Some("") => "",
+        Some(rest) if rest.starts_with(['/', '\\']) => rest,
+        _ => return Ok(PathBuf::from(path)),
+    };
+
+    let home = home.ok_or_else(|| PathError::ConstructionFailed {
+        message: format!("Cannot expand {:?}: no home directory is set", path),
+    })?;
+
+    // A rest starting with a separator would replace the home directory on join
+    let rest = rest.trim_start_matches(['/', '\\']);
+    let mut expanded = PathBuf::from(home);
+    if !rest.is_empty() {
+        expanded.push(rest);
+    }
+    Ok(expanded)
+}
+
+/// The current user's home directory from the environment, if set and non-empty
+fn home_dir() -> Option<OsString> {
+    let vars: &[&str] = if cfg!(windows) {
+        &["USERPROFILE", "HOME"]
+    } else {
+        &["HOME"]
+    };
+    vars.iter()
+        .filter_map(std::env::var_os)
+        .find(|home| !home.is_empty())
+}
+
+/// What [`expand_env_with`] does with a variable reference it won't expand
+///
+/// A reference is not expanded when the variable isn't in the allow-list, or
+/// is unset or empty.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnknownVariable {
+    /// Leave the reference in the path as written
+    #[default]
+    Keep,
+    /// Fail with a [`ValidationFailed`](PathError::ValidationFailed) error
+    /// for the `env_variable` rule
+    Reject,
+}
+
+/// Expand allow-listed environment variables in a path
+///
+/// Equivalent to [`expand_env_with`] with [`UnknownVariable::Keep`], so
+/// references to other variables stay in the path as written.
+///
+/// # Examples
+/// ```
+/// use path_utils::expand_env;
+///
+/// // Not in the allow-list, so left alone even though it is set
+/// if std::env::var_os("PATH").is_some() {
+///     assert_eq!(expand_env("$PATH/bin", &[]).unwrap(), "$PATH/bin");
+/// }
+/// ```
+pub fn expand_env(path: &str, allowed: &[&str]) -> Result<String> {
+    expand_env_with(path, allowed, UnknownVariable::Keep)
+}
+
+/// Expand allow-listed environment variables in a path, choosing how to treat the rest
+///
+/// Recognizes `$NAME`, `${NAME}` and `%NAME%`, where a name is ASCII letters,
+/// digits and `_`, not starting with a digit. A reference is replaced by the
+/// variable's value only if the name appears in `allowed` (compared exactly)
+/// and the variable is set to a non-empty value; `unknown` decides what happens
+/// to every other reference. A `$` or `%` that doesn't start a reference is
+/// kept as is.
+///
+/// The allow-list is what makes this safe to run on untrusted input: without
+/// it, a config file could read any secret in the environment into a path. A
+/// variable's value is still outside the caller's control, so the expanded
+/// path is checked with [`validate_path`], which rejects `..` and the other
+/// unsafe patterns a value could introduce, and then normalized with
+/// [`normalize_path_str_preserving_root`], keeping a leading `/` or drive.
+///
+/// # Errors
+/// - [`PathError::ValidationFailed`] with rule `env_variable` for a reference
+///   that isn't expanded, under [`UnknownVariable::Reject`]
+/// - [`PathError::ConstructionFailed`] if a value isn't valid UTF-8
+/// - Any error from [`validate_path`] on the expanded path
+///
+/// # Examples
+/// ```
+/// use path_utils::{expand_env_with, UnknownVariable};
+///
+/// let result = expand_env_with("$PATH_UTILS_UNSET/x", &["PATH_UTILS_UNSET"], UnknownVariable::Reject);
+/// assert_eq!(result.unwrap_err().rule(), Some("env_variable"));
+/// ```
+pub fn expand_env_with(path: &str, allowed: &[&str], unknown: UnknownVariable) -> Result<String> {
+    expand_env_from(path, allowed, unknown, |name| std::env::var_os(name))
+}
+
+/// Expand variable references, looking values up with `lookup`
+fn expand_env_from(
+    path: &str,
+    allowed: &[&str],
+    unknown: UnknownVariable,
+    lookup: impl Fn(&str) -> Option<OsString>,
+) -> Result<String> {
+    let mut expanded = String::with_capacity(path.len());
+    let mut rest = path;
+
+    while let Some(start) = rest.find(['$', '%']) {
+        expanded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let Some((name, reference_len)) = parse_reference(rest) else {
+            // A lone `$` or `%` is literal
+            expanded.push_str(&rest[..1]);
+            rest = &rest[1..];
+            continue;
+        };
+        let reference = &rest[..reference_len];
+
+        let value = if allowed.contains(&name) {
+            lookup(name).filter(|value| !value.is_empty())
+        } else {
+            None
+        };
+        match (value, unknown) {
+            (Some(value), _) => {
+                let value = value
+                    .into_string()
+                    .map_err(|_| PathError::ConstructionFailed {
+                        message: format!("Value of {} is not valid UTF-8", reference),
+                    })?;
+                expanded.push_str(&value);
+            }
+            (None, UnknownVariable::Keep) => expanded.push_str(reference),
+            (None, UnknownVariable::Reject) => {
+                return Err(PathError::validation_failed(
+                    "env_variable",
+                    format!("{} is not an allowed, non-empty variable", reference),
+                ));
+            }
+        }
+        rest = &rest[reference_len..];
+    }
+    expanded.push_str(rest);
+
+    validate_path(&expanded)?;
+    Ok(normalize_path_str_preserving_root(&expanded))
+}
+
+/// Parse a `$NAME`, `${NAME}` or `%NAME%` reference at the start of `s`
+///
+/// Returns the name and the length of the whole reference.
+fn parse_reference(s: &str) -> Option<(&str, usize)> {
+    let (name_start, closing) = if s.starts_with("${") {
+        (2, Some('}'))
+    } else if s.starts_with('$') {
+        (1, None)
+    } else {
+        (1, Some('%'))
+    };
+
+    let name_len = variable_name_len(&s[name_start..]);
+    if name_len == 0 {
+        return None;
+    }
+    let name_end = name_start + name_len;
+    let name = &s[name_start..name_end];
+
+    match closing {
+        None => Some((name, name_end)),
+        Some(closing) if s[name_end..].starts_with(closing) => Some((name, name_end + 1)),
+        Some(_) => None,
+    }
+}
+
+/// The length of the variable name at the start of `s`, or 0 if there is none
+fn variable_name_len(s: &str) -> usize {
+    if s.starts_with(|c: char| c.is_ascii_digit()) {
+        return 0;
+    }
+    s.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(s.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn home() -> Option<OsString> {
+        Some(OsString::from("/home/user"))
+    }
+
+    #[test]
+    fn test_expand_tilde() {
+        assert_eq!(
+            expand_tilde_with_home("~/x", home()).unwrap(),
+            PathBuf::from("/home/user/x")
+        );
+        assert_eq!(
+            expand_tilde_with_home("~/projects/app/", home()).unwrap(),
+            PathBuf::from("/home/user/projects/app/")
+        );
+        assert_eq!(
+            expand_tilde_with_home("~", home()).unwrap(),
+            PathBuf::from("/home/user")
+        );
+        assert_eq!(
+            expand_tilde_with_home("~/", home()).unwrap(),
+            PathBuf::from("/home/user")
+        );
+        assert_eq!(
+            expand_tilde_with_home("~\\docs", home()).unwrap(),
+            PathBuf::from("/home/user").join("docs")
+        );
+
+        // Extra separators can't replace the home directory
+        assert_eq!(
+            expand_tilde_with_home("~//etc/passwd", home()).unwrap(),
+            PathBuf::from("/home/user/etc/passwd")
+        );
+    }
+
+    #[test]
+    fn test_expand_tilde_leaves_other_paths() {
+        for path in [
+            "src/main.rs",
+            "/etc/hosts",
+            "foo/~/bar",
+            "backup~",
+            "~user/x",
+            "~~",
+            "",
+        ] {
+            assert_eq!(
+                expand_tilde_with_home(path, home()).unwrap(),
+                PathBuf::from(path)
+            );
+            // Without a home directory, these still succeed
+            assert_eq!(
+                expand_tilde_with_home(path, None).unwrap(),
+                PathBuf::from(path)
+            );
+        }
+    }
+
+    #[test]
+    fn test_expand_tilde_without_home() {
+        assert!(matches!(
+            expand_tilde_with_home("~/x", None),
+            Err(PathError::ConstructionFailed { .. })
+        ));
+        assert!(expand_tilde_with_home("~", None).is_err());
+    }
+
+    #[test]
+    fn test_expand_tilde_reads_environment() {
+        match home_dir() {
+            Some(home) => assert_eq!(expand_tilde("~/x").unwrap(), PathBuf::from(home).join("x")),
+            None => assert!(expand_tilde("~/x").is_err()),
+        }
+    }
+
+    fn lookup(name: &str) -> Option<OsString> {
+        match name {
+            "PROJECT" => Some("work/app".into()),
+            "SECRET" => Some("hunter2".into()),
+            "ESCAPE" => Some("../../etc".into()),
+            "ROOT" => Some("/srv/data".into()),
+            "EMPTY" => Some("".into()),
+            _ => None,
+        }
+    }
+
+    fn expand(path: &str, allowed: &[&str], unknown: UnknownVariable) -> Result<String> {
+        expand_env_from(path, allowed, unknown, lookup)
+    }
+
+    #[test]
+    fn test_expand_env_allowed() {
+        let allowed = ["PROJECT", "ROOT"];
+        for path in ["$PROJECT/src", "${PROJECT}/src", "%PROJECT%\\src"] {
+            assert_eq!(
+                expand(path, &allowed, UnknownVariable::Keep).unwrap(),
+                "work/app/src"
+            );
+        }
+        assert_eq!(
+            expand("$ROOT/$PROJECT", &allowed, UnknownVariable::Reject).unwrap(),
+            "/srv/data/work/app"
+        );
+        assert_eq!(
+            expand("a${PROJECT}b", &allowed, UnknownVariable::Keep).unwrap(),
+            "awork/appb"
+        );
+    }
+
+    #[test]
+    fn test_expand_env_disallowed() {
+        // Set, but not in the allow-list
+        assert_eq!(
+            expand("$SECRET/x", &["PROJECT"], UnknownVariable::Keep).unwrap(),
+            "$SECRET/x"
+        );
+        assert_eq!(
+            expand("%SECRET%/x", &["PROJECT"], UnknownVariable::Keep).unwrap(),
+            "%SECRET%/x"
+        );
+        let err = expand("$SECRET/x", &["PROJECT"], UnknownVariable::Reject).unwrap_err();
+        assert_eq!(err.rule(), Some("env_variable"));
+
+        // Allowed but unset or empty
+        for path in ["$MISSING/x", "$EMPTY/x"] {
+            assert_eq!(
+                expand(path, &["MISSING", "EMPTY"], UnknownVariable::Keep).unwrap(),
+                path
+            );
+            assert!(expand(path, &["MISSING", "EMPTY"], UnknownVariable::Reject).is_err());
+        }
+    }
+
+    #[test]
+    fn test_expand_env_rejects_traversal_in_value() {
+        for unknown in [UnknownVariable::Keep, UnknownVariable::Reject] {
+            assert!(matches!(
+                expand("$ESCAPE/passwd", &["ESCAPE"], unknown),
+                Err(PathError::PathTraversal { .. })
+            ));
+        }
+
+        // Traversal written directly is rejected too
+        assert!(expand("$PROJECT/../x", &["PROJECT"], UnknownVariable::Keep).is_err());
+    }
+
+    #[test]
+    fn test_expand_env_literal_markers() {
+        for path in [
+            "price$",
+            "100%",
+            "$1/x",
+            "${}",
+            "${PROJECT",
+            "%PROJECT",
+            "a%%b",
+        ] {
+            assert_eq!(
+                expand(path, &["PROJECT"], UnknownVariable::Reject).unwrap(),
+                path
+            );
+        }
+        assert_eq!(
+            expand("$$PROJECT", &["PROJECT"], UnknownVariable::Keep).unwrap(),
+            "$work/app"
+        );
+    }
+
+    #[test]
+    fn test_expand_env_reads_environment() {
+        assert_eq!(
+            expand_env("$PATH_UTILS_NOT_SET/x", &["PATH_UTILS_NOT_SET"]).unwrap(),
+            "$PATH_UTILS_NOT_SET/x"
+        );
+        assert!(expand_env_with(
+            "$PATH_UTILS_NOT_SET/x",
+            &["PATH_UTILS_NOT_SET"],
+            UnknownVariable::Reject
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,281 @@
+//! File name construction
+//!
+//! Helpers that build new file names, always returning names that pass
+//! [`validate_path`](crate::validate_path).
+
+use crate::classify::file_extension;
+use crate::error::{PathError, Result};
+use crate::options::DEFAULT_MAX_COMPONENT_LENGTH;
+use crate::reserved::{is_dots_and_whitespace, ReservedNames};
+use crate::validate::{validate_path, DECEPTIVE_FORMAT_CHARS, FORBIDDEN_CHARS};
+use alloc::format;
+use alloc::string::String;
+
+/// The name used when nothing usable is left of the input
+pub(crate) const PLACEHOLDER_NAME: &str = "unnamed";
+
+/// Build a content-addressed file name from a hash, keeping the original extension
+///
+/// The hash must be non-empty hexadecimal (as produced by SHA-256, BLAKE3 and
+/// similar) and is lowercased, so the same content always gets the same name.
+/// The extension is taken from the last component of `original_name` using the
+/// crate's extension rules: only the last extension counts (`archive.tar.gz`
+/// gives `gz`), dotfiles like `.env` have none, and trailing dots and spaces
+/// are ignored. The result is `{hash}.{ext}`, or just `{hash}` without an
+/// extension, and is checked with [`validate_path`].
+///
+/// # Examples
+/// ```
+/// use path_utils::content_addressed_name;
+///
+/// assert_eq!(content_addressed_name("9F86D081", "Photo.JPG").unwrap(), "9f86d081.JPG");
+/// assert_eq!(content_addressed_name("9f86d081", "README").unwrap(), "9f86d081");
+/// assert!(content_addressed_name("not-hex", "a.txt").is_err());
+/// ```
+pub fn content_addressed_name(content_hash: &str, original_name: &str) -> Result<String> {
+    if content_hash.is_empty() || !content_hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(PathError::validation_failed(
+            "content_hash",
+            format!("content hash must be hexadecimal, got {:?}", content_hash),
+        ));
+    }
+
+    let file_name = original_name
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or("")
+        .trim_end_matches(['.', ' ']);
+    let hash = content_hash.to_ascii_lowercase();
+
+    let name = match file_extension(file_name) {
+        Some(extension) => format!("{}.{}", hash, extension),
+        None => hash,
+    };
+
+    validate_path(&name)?;
+    Ok(name)
+}
+
+/// Turn an arbitrary string into a usable single-component file name
+///
+/// Unlike [`sanitize_directory_file_path`](crate::sanitize_directory_file_path),
+/// this repairs the input instead of rejecting it, and never fails:
+///
+/// - Forbidden characters (`< > | ? * "`), `:` (which names an NTFS stream),
+///   control characters, invisible format characters and both path
+///   separators become `replacement`
+/// - Leading whitespace and trailing dots and whitespace are stripped, so a
+///   traversal name such as `..` or `...` is emptied, while dots inside a
+///   name, as in `v1..2`, are kept
+/// - A reserved name gets `_file` appended to its base name (`CON.txt` becomes
+///   `CON_file.txt`)
+/// - The result is cut to 255 bytes
+/// - If nothing is left, the result is `unnamed`
+///
+/// If `replacement` would itself be rejected, or is a dot or whitespace, `_`
+/// is used instead. The result always passes [`validate_path`].
+///
+/// # Examples
+/// ```
+/// use path_utils::sanitize_filename;
+///
+/// assert_eq!(sanitize_filename("a<b>c", '_'), "a_b_c");
+/// assert_eq!(sanitize_filename("../../etc/passwd", '-'), "..-..-etc-passwd");
+/// assert_eq!(sanitize_filename("CON", '_'), "CON_file");
+/// ```
+pub fn sanitize_filename(name: &str, replacement: char) -> String {
+    let replacement = if replacement == '.'
+        || replacement.is_whitespace()
+        || is_replaced_in_filename(replacement)
+    {
+        '_'
+    } else {
+        replacement
+    };
+
+    let name = name.trim_start().trim_end_matches(is_trailing_trimmed);
+    let mut sanitized = String::with_capacity(name.len());
+    for c in name.chars() {
+        if is_replaced_in_filename(c) {
+            sanitized.push(replacement);
+        } else {
+            sanitized.push(c);
+        }
+    }
+    trim_trailing_dots_and_whitespace(&mut sanitized);
+
+    if ReservedNames::default().matches(&sanitized) {
+        let base_end = sanitized.find('.').unwrap_or(sanitized.len());
+        sanitized.insert_str(base_end, "_file");
+    }
+
+    if sanitized.len() > DEFAULT_MAX_COMPONENT_LENGTH {
+        let mut end = DEFAULT_MAX_COMPONENT_LENGTH;
+        while !sanitized.is_char_boundary(end) {
+            end -= 1;
+        }
+        sanitized.truncate(end);
+        trim_trailing_dots_and_whitespace(&mut sanitized);
+    }
+
+    if sanitized.is_empty() || is_dots_and_whitespace(&sanitized) {
+        return PLACEHOLDER_NAME.into();
+    }
+    sanitized
+}
+
+/// Whether [`sanitize_filename`] replaces a character
+pub(crate) fn is_replaced_in_filename(c: char) -> bool {
+    c.is_control()
+        || c == '/'
+        || c == '\\'
+        || c == ':'
+        || FORBIDDEN_CHARS.contains(&c)
+        || DECEPTIVE_FORMAT_CHARS.contains(&c)
+}
+
+/// Whether a character is stripped from the end of a sanitized name
+fn is_trailing_trimmed(c: char) -> bool {
+    c == '.' || c.is_whitespace()
+}
+
+/// Remove trailing dots and whitespace in place
+fn trim_trailing_dots_and_whitespace(name: &mut String) {
+    let trimmed_len = name.trim_end_matches(is_trailing_trimmed).len();
+    name.truncate(trimmed_len);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HASH: &str = "e3b0c44298fc1c149afbf4c8996fb924";
+
+    #[test]
+    fn test_content_addressed_name() {
+        assert_eq!(
+            content_addressed_name(HASH, "report.pdf").unwrap(),
+            format!("{}.pdf", HASH)
+        );
+        assert_eq!(
+            content_addressed_name(HASH, "uploads/2024\\photo.jpeg").unwrap(),
+            format!("{}.jpeg", HASH)
+        );
+
+        // Without an extension
+        for name in ["Makefile", ".env", "trailing.", ""] {
+            assert_eq!(content_addressed_name(HASH, name).unwrap(), HASH);
+        }
+
+        // Compound extensions keep only the last part
+        assert_eq!(
+            content_addressed_name(HASH, "backup.tar.gz").unwrap(),
+            format!("{}.gz", HASH)
+        );
+
+        // Trailing dots and spaces don't hide the extension
+        assert_eq!(
+            content_addressed_name(HASH, "setup.exe. ").unwrap(),
+            format!("{}.exe", HASH)
+        );
+
+        // The hash is lowercased
+        assert_eq!(
+            content_addressed_name("ABCDEF01", "a.txt").unwrap(),
+            "abcdef01.txt"
+        );
+    }
+
+    #[test]
+    fn test_content_addressed_name_rejects_bad_input() {
+        for hash in ["", "xyz", "abc 123", "../abc", "abc/def", "ab.cd"] {
+            assert!(
+                matches!(
+                    content_addressed_name(hash, "a.txt"),
+                    Err(PathError::ValidationFailed {
+                        rule: "content_hash",
+                        ..
+                    })
+                ),
+                "hash should be rejected: {:?}",
+                hash
+            );
+        }
+
+        // An extension that isn't safe in a file name
+        assert!(matches!(
+            content_addressed_name(HASH, "evil.ph<p"),
+            Err(PathError::InvalidCharacters { .. })
+        ));
+    }
+
+    #[test]
+    fn test_sanitize_filename() {
+        assert_eq!(sanitize_filename("report.pdf", '_'), "report.pdf");
+        assert_eq!(sanitize_filename("a<b>c", '_'), "a_b_c");
+        assert_eq!(sanitize_filename("a/b\\c.txt", '-'), "a-b-c.txt");
+        assert_eq!(sanitize_filename("a:b", '_'), "a_b");
+        assert_eq!(
+            sanitize_filename("line\nbreak\0.txt", '_'),
+            "line_break_.txt"
+        );
+        assert_eq!(
+            sanitize_filename("invoice\u{202E}fdp.exe", '_'),
+            "invoice_fdp.exe"
+        );
+        assert_eq!(sanitize_filename("notes... ", '_'), "notes");
+        assert_eq!(sanitize_filename("v1..2", '_'), "v1..2");
+        assert_eq!(sanitize_filename("..hidden", '_'), "..hidden");
+        assert_eq!(sanitize_filename("  padded.txt\u{3000}", '_'), "padded.txt");
+    }
+
+    #[test]
+    fn test_sanitize_filename_reserved() {
+        assert_eq!(sanitize_filename("CON", '_'), "CON_file");
+        assert_eq!(sanitize_filename("lpt1.txt", '_'), "lpt1_file.txt");
+        assert_eq!(sanitize_filename("nul. .", '_'), "nul_file");
+        assert_eq!(sanitize_filename("CONSOLE", '_'), "CONSOLE");
+    }
+
+    #[test]
+    fn test_sanitize_filename_all_invalid() {
+        assert_eq!(sanitize_filename("<>|?*\"", '_'), "______");
+        assert_eq!(sanitize_filename("", '_'), "unnamed");
+        assert_eq!(sanitize_filename(". . .", '_'), "unnamed");
+        assert_eq!(sanitize_filename("   ", '_'), "unnamed");
+
+        // An unusable replacement falls back to an underscore
+        assert_eq!(sanitize_filename("a/b", '/'), "a_b");
+        assert_eq!(sanitize_filename("a?b", '.'), "a_b");
+        assert_eq!(sanitize_filename("<a", ' '), "_a");
+    }
+
+    #[test]
+    fn test_sanitize_filename_always_valid() {
+        let long = "é".repeat(200);
+        for name in [
+            "../../etc/passwd",
+            "..",
+            "...",
+            "a/../b",
+            "CON.txt.",
+            "file.txt:$DATA",
+            "C:",
+            ". \u{3000}",
+            "\u{FEFF}",
+            "\t",
+            long.as_str(),
+        ] {
+            let sanitized = sanitize_filename(name, '_');
+            assert!(
+                validate_path(&sanitized).is_ok(),
+                "{:?} sanitized to invalid {:?}",
+                name,
+                sanitized
+            );
+            assert!(!sanitized.contains(['/', '\\']));
+        }
+
+        assert_eq!(sanitize_filename(&long, '_').len(), 254);
+    }
+}
@@ -0,0 +1,312 @@
+//! Filesystem-aware helpers
+//!
+//! Unlike the rest of the crate, which reasons about path strings, these
+//! functions touch the disk.
+
+use crate::error::{PathError, Result};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// How many names to try before giving up on creating a temp directory
+const TEMP_DIR_ATTEMPTS: u32 = 64;
+
+/// Create a new, uniquely named directory under `base`
+///
+/// The name is the sanitized `prefix` followed by a random suffix. Characters
+/// other than ASCII letters, digits, `-` and `_` in the prefix are replaced
+/// with `_`, so it can't introduce separators, `..` or reserved characters.
+/// Each candidate is created with [`std::fs::create_dir`], which fails rather
+/// than reuses an existing entry, so two callers can never be handed the same
+/// directory. On a collision a new name is tried.
+///
+/// Unlike `tempfile`, the directory is not removed automatically.
+///
+/// # Errors
+/// Any error from creating the directory, such as `base` not existing. If every
+/// attempt collides, the last `AlreadyExists` error is returned.
+///
+/// # Examples
+/// ```
+/// use path_utils::create_temp_dir_under;
+///
+/// let base = tempfile::TempDir::new()?;
+/// let dir = create_temp_dir_under(base.path(), "upload")?;
+/// assert!(dir.is_dir());
+/// assert!(dir.file_name().unwrap().to_str().unwrap().starts_with("upload-"));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn create_temp_dir_under(base: &Path, prefix: &str) -> io::Result<PathBuf> {
+    let prefix = sanitize_temp_prefix(prefix);
+    let random = RandomState::new();
+
+    let mut last_error = None;
+    for attempt in 0..TEMP_DIR_ATTEMPTS {
+        let candidate = base.join(temp_dir_name(&prefix, &random, attempt));
+        match std::fs::create_dir(&candidate) {
+            Ok(()) => return Ok(candidate),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => last_error = Some(e),
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| io::Error::from(io::ErrorKind::AlreadyExists)))
+}
+
+/// Resolve `candidate` on disk and check that it stays inside `root`
+///
+/// [`safe_repository_join`](crate::safe_repository_join) canonicalizes its
+/// workdir but checks the joined path only lexically, so a symlink inside the
+/// tree can still lead outside it. This closes that gap for paths that already
+/// exist: `root` and `candidate` (joined onto `root` if relative) are both
+/// canonicalized, resolving every symlink, and the result must lie within the
+/// canonical root.
+///
+/// If `candidate` doesn't fully exist yet, its longest existing ancestor is
+/// canonicalized and the missing components are appended. Those components
+/// can't be checked for symlinks, so a `..` among them is rejected, as is a
+/// dangling symlink, which a later write would follow wherever it points.
+///
+/// # Errors
+/// - [`PathError::PathTraversal`] if `candidate` resolves outside `root`
+/// - [`PathError::IoError`] if `root` can't be canonicalized or the disk can't
+///   be read
+///
+/// # Examples
+/// ```
+/// use path_utils::ensure_within_root;
+///
+/// let root = tempfile::TempDir::new()?;
+/// std::fs::create_dir(root.path().join("docs"))?;
+///
+/// let resolved = ensure_within_root(root.path(), "docs/new.txt".as_ref())?;
+/// assert!(resolved.ends_with("docs/new.txt"));
+/// assert!(ensure_within_root(root.path(), "../outside".as_ref()).is_err());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn ensure_within_root(root: &Path, candidate: &Path) -> Result<PathBuf> {
+    let root = root.canonicalize()?;
+    let escaped = || PathError::PathTraversal {
+        path: candidate.to_string_lossy().into_owned(),
+    };
+
+    let joined = root.join(candidate);
+    let mut existing = joined.as_path();
+    let mut missing = Vec::new();
+    let resolved = loop {
+        match existing.canonicalize() {
+            Ok(canonical) => break canonical,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                // Present but unresolvable means a dangling symlink
+                if existing.symlink_metadata().is_ok() {
+                    return Err(escaped());
+                }
+                let (Some(name), Some(parent)) = (existing.file_name(), existing.parent()) else {
+                    return Err(escaped());
+                };
+                missing.push(name);
+                existing = parent;
+            }
+            Err(e) => return Err(e.into()),
+        }
+    };
+
+    let resolved = missing
+        .iter()
+        .rev()
+        .fold(resolved, |path, name| path.join(name));
+    if resolved.starts_with(&root) {
+        Ok(resolved)
+    } else {
+        Err(escaped())
+    }
+}
+
+/// Reduce a temp directory prefix to characters that are safe everywhere
+fn sanitize_temp_prefix(prefix: &str) -> String {
+    prefix
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Generate the candidate name for one attempt
+fn temp_dir_name(prefix: &str, random: &RandomState, attempt: u32) -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+
+    let mut hasher = random.build_hasher();
+    hasher.write_u32(std::process::id());
+    hasher.write_u128(nanos);
+    hasher.write_u32(attempt);
+    let suffix = hasher.finish();
+
+    if prefix.is_empty() {
+        format!("{:016x}", suffix)
+    } else {
+        format!("{}-{:016x}", prefix, suffix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_create_temp_dir_under_is_unique() {
+        let base = TempDir::new().unwrap();
+
+        let dirs: Vec<PathBuf> = (0..20)
+            .map(|_| create_temp_dir_under(base.path(), "job").unwrap())
+            .collect();
+
+        let unique: HashSet<&PathBuf> = dirs.iter().collect();
+        assert_eq!(unique.len(), dirs.len());
+        for dir in &dirs {
+            assert!(dir.is_dir());
+            assert_eq!(dir.parent(), Some(base.path()));
+        }
+    }
+
+    #[test]
+    fn test_create_temp_dir_under_sanitizes_prefix() {
+        let base = TempDir::new().unwrap();
+
+        let dir = create_temp_dir_under(base.path(), "../../etc/x y").unwrap();
+        assert_eq!(dir.parent(), Some(base.path()));
+        let name = dir.file_name().unwrap().to_str().unwrap();
+        assert!(name.starts_with("______etc_x_y-"), "{}", name);
+
+        let dir = create_temp_dir_under(base.path(), "").unwrap();
+        assert_eq!(dir.file_name().unwrap().len(), 16);
+    }
+
+    #[test]
+    fn test_create_temp_dir_under_missing_base() {
+        let base = TempDir::new().unwrap();
+        let missing = base.path().join("missing");
+        assert_eq!(
+            create_temp_dir_under(&missing, "x").unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn test_ensure_within_root() {
+        let root = TempDir::new().unwrap();
+        let canonical_root = root.path().canonicalize().unwrap();
+        std::fs::create_dir(root.path().join("docs")).unwrap();
+        std::fs::write(root.path().join("docs/a.txt"), "a").unwrap();
+
+        // Existing paths, relative or absolute
+        assert_eq!(
+            ensure_within_root(root.path(), Path::new("docs/a.txt")).unwrap(),
+            canonical_root.join("docs/a.txt")
+        );
+        assert_eq!(
+            ensure_within_root(root.path(), &root.path().join("docs")).unwrap(),
+            canonical_root.join("docs")
+        );
+        assert_eq!(
+            ensure_within_root(root.path(), Path::new("")).unwrap(),
+            canonical_root
+        );
+
+        // Paths that don't exist yet
+        assert_eq!(
+            ensure_within_root(root.path(), Path::new("docs/new/b.txt")).unwrap(),
+            canonical_root.join("docs/new/b.txt")
+        );
+    }
+
+    #[test]
+    fn test_ensure_within_root_rejects_escapes() {
+        let parent = TempDir::new().unwrap();
+        let root = parent.path().join("root");
+        std::fs::create_dir(&root).unwrap();
+        std::fs::write(parent.path().join("secret.txt"), "s").unwrap();
+
+        for candidate in ["../secret.txt", "../elsewhere/new.txt", "missing/../../x"] {
+            assert!(
+                matches!(
+                    ensure_within_root(&root, Path::new(candidate)),
+                    Err(PathError::PathTraversal { .. })
+                ),
+                "{:?} should be rejected",
+                candidate
+            );
+        }
+        assert!(matches!(
+            ensure_within_root(&root, &parent.path().join("secret.txt")),
+            Err(PathError::PathTraversal { .. })
+        ));
+
+        // The root itself must exist
+        assert!(matches!(
+            ensure_within_root(&parent.path().join("missing"), Path::new("x")),
+            Err(PathError::IoError { .. })
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_ensure_within_root_rejects_symlink_escapes() {
+        use std::os::unix::fs::symlink;
+
+        let parent = TempDir::new().unwrap();
+        let root = parent.path().join("root");
+        let outside = parent.path().join("outside");
+        std::fs::create_dir(&root).unwrap();
+        std::fs::create_dir(&outside).unwrap();
+        std::fs::write(outside.join("secret.txt"), "s").unwrap();
+
+        // A symlink inside the root pointing outside it
+        symlink(&outside, root.join("link")).unwrap();
+        for candidate in ["link", "link/secret.txt", "link/new.txt"] {
+            assert!(
+                matches!(
+                    ensure_within_root(&root, Path::new(candidate)),
+                    Err(PathError::PathTraversal { .. })
+                ),
+                "{:?} should be rejected",
+                candidate
+            );
+        }
+
+        // A dangling symlink would be followed by the next write
+        symlink(outside.join("not-yet"), root.join("dangling")).unwrap();
+        assert!(matches!(
+            ensure_within_root(&root, Path::new("dangling")),
+            Err(PathError::PathTraversal { .. })
+        ));
+
+        // A symlink that stays inside the root is fine
+        std::fs::create_dir(root.join("real")).unwrap();
+        symlink(root.join("real"), root.join("alias")).unwrap();
+        assert_eq!(
+            ensure_within_root(&root, Path::new("alias/x.txt")).unwrap(),
+            root.canonicalize().unwrap().join("real/x.txt")
+        );
+    }
+
+    #[test]
+    fn test_temp_dir_names_differ_between_attempts() {
+        let random = RandomState::new();
+        let first = temp_dir_name("p", &random, 0);
+        let second = temp_dir_name("p", &random, 1);
+        assert_ne!(first, second);
+        assert!(first.starts_with("p-"));
+    }
+}
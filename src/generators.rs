@@ -149,7 +149,6 @@ impl PathGenerators {
     }
 
     /// Generate Windows drive letter paths (for platform testing)
-    #[cfg(windows)]
     pub fn drive_letter_path() -> impl Strategy<Value = String> {
         prop_oneof![
             Just("C:\\Windows\\System32".to_string()),
@@ -257,7 +256,6 @@ impl ScenarioGenerators {
 mod tests {
     use super::*;
 
-
     proptest! {
         #[test]
         fn path_generators_produce_valid_output(
@@ -303,4 +301,4 @@ mod tests {
             // The actual path validation is tested elsewhere
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,353 @@
+//! Git-specific path rules
+//!
+//! Checks modelled on the ones Git itself applies before writing a path into
+//! the working tree, for tools that create or move files inside repositories.
+
+use crate::error::{PathError, Result};
+use crate::reserved::ReservedNames;
+use alloc::format;
+use alloc::string::{String, ToString};
+
+/// Characters Git for Windows refuses in checked-out paths, besides control characters
+const GIT_WINDOWS_FORBIDDEN_CHARS: [char; 7] = ['<', '>', ':', '"', '|', '?', '*'];
+
+/// Code points that HFS+ ignores when comparing names
+///
+/// Git refuses names that become `.git` once these are removed (`is_hfs_dotgit`).
+const HFS_IGNORABLE_CHARS: [char; 16] = [
+    '\u{200C}', '\u{200D}', '\u{200E}', '\u{200F}', '\u{202A}', '\u{202B}', '\u{202C}', '\u{202D}',
+    '\u{202E}', '\u{206A}', '\u{206B}', '\u{206C}', '\u{206D}', '\u{206E}', '\u{206F}', '\u{FEFF}',
+];
+
+/// Validate that a path from a Git tree can be checked out safely on every platform
+///
+/// Applies the rules Git enforces with `core.protectNTFS` and `core.protectHFS`
+/// both enabled, plus the path restrictions of Git for Windows, so a path that
+/// passes can be checked out on Linux, macOS and Windows alike:
+///
+/// - **Shape** (`verify_path`): the path must be relative, with no empty
+///   components and no trailing separator. `\` counts as a separator, since it
+///   is one on Windows.
+/// - **Dot components** (`verify_dotfile`): no `.` or `..` components.
+/// - **The `.git` directory**: no component that refers to it, including the
+///   NTFS spellings with trailing dots or spaces (`.git.`, `.git . `), the 8.3
+///   short name `git~1`, alternate data streams (`.git::$INDEX_ALLOCATION`)
+///   (`is_ntfs_dotgit`), and the HFS+ spellings with ignorable code points such
+///   as `.g\u{200C}it` (`is_hfs_dotgit`). Matching is case-insensitive.
+/// - **Windows names** (`is_valid_win32_path`): no control characters, none of
+///   `< > : " | ? *`, no trailing dot or space in a component, and no device
+///   names (`CON`, `NUL`, `COM1`, ..., plus `CONIN$` and `CONOUT$`).
+///
+/// Case collisions (`README` next to `readme`) also break checkouts on
+/// case-insensitive filesystems, but they involve more than one path and are
+/// not checked here.
+///
+/// # Examples
+/// ```
+/// use path_utils::validate_git_checkout_safe;
+///
+/// assert!(validate_git_checkout_safe("src/main.rs").is_ok());
+/// assert!(validate_git_checkout_safe(".github/workflows/ci.yml").is_ok());
+///
+/// assert!(validate_git_checkout_safe(".git/config").is_err());
+/// assert!(validate_git_checkout_safe("GIT~1/hooks/post-checkout").is_err());
+/// assert!(validate_git_checkout_safe("docs/aux.md").is_err());
+/// ```
+pub fn validate_git_checkout_safe(path: &str) -> Result<()> {
+    if path.is_empty() {
+        return Err(PathError::EmptyPath);
+    }
+
+    let mut reserved = ReservedNames::with_defaults();
+    reserved.add("CONIN$").add("CONOUT$");
+
+    for component in path.split(['/', '\\']) {
+        match component {
+            "" => {
+                return Err(PathError::validation_failed(
+                    "git_empty_component",
+                    format!(
+                        "{} has an empty component, or a leading or trailing separator",
+                        path
+                    ),
+                ))
+            }
+            "." | ".." => {
+                return Err(PathError::PathTraversal {
+                    path: path.to_string(),
+                })
+            }
+            _ => {}
+        }
+
+        if is_dotgit_confusable(component) {
+            return Err(PathError::validation_failed(
+                "git_dotgit",
+                format!("{} refers to the .git directory in {}", component, path),
+            ));
+        }
+
+        if component
+            .chars()
+            .any(|c| c.is_ascii_control() || GIT_WINDOWS_FORBIDDEN_CHARS.contains(&c))
+            || component.ends_with(['.', ' '])
+        {
+            return Err(PathError::InvalidCharacters {
+                path: path.to_string(),
+            });
+        }
+
+        if reserved.matches(component) {
+            return Err(PathError::ReservedFilename {
+                filename: component.to_string(),
+                path: path.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Check whether a path component is `.git` or a name some filesystem treats as `.git`
+///
+/// Implements the same checks Git uses to keep a tree from writing into the
+/// repository's own `.git` directory:
+/// - `.git` in any case (`.GIT`, `.Git`), since Windows and macOS are case-insensitive
+/// - Trailing dots and spaces (`.git.`, `.git `), which NTFS strips
+/// - The NTFS 8.3 short name `git~1`, in any case and with the same trailing characters
+/// - An alternate data stream suffix (`.git::$INDEX_ALLOCATION`), which NTFS
+///   resolves to the directory itself
+/// - Code points that HFS+ ignores inserted anywhere (`.g\u{200C}it`)
+///
+/// # Examples
+/// ```
+/// use path_utils::is_dotgit_confusable;
+///
+/// assert!(is_dotgit_confusable(".Git"));
+/// assert!(is_dotgit_confusable("git~1"));
+/// assert!(is_dotgit_confusable(".git "));
+/// assert!(!is_dotgit_confusable(".gitignore"));
+/// ```
+pub fn is_dotgit_confusable(component: &str) -> bool {
+    is_ntfs_dotgit(component) || is_hfs_dotgit(component)
+}
+
+/// Check whether NTFS would resolve a component to the `.git` directory
+///
+/// Matches `.git` and its 8.3 short name `git~1`, case-insensitively, followed
+/// by nothing but dots and spaces, optionally ending in an alternate data
+/// stream suffix starting with `:`.
+fn is_ntfs_dotgit(component: &str) -> bool {
+    let bytes = component.as_bytes();
+    let rest = if bytes.len() >= 4 && bytes[..4].eq_ignore_ascii_case(b".git") {
+        &bytes[4..]
+    } else if bytes.len() >= 5 && bytes[..5].eq_ignore_ascii_case(b"git~1") {
+        &bytes[5..]
+    } else {
+        return false;
+    };
+
+    for &byte in rest {
+        match byte {
+            b':' => return true,
+            b'.' | b' ' => {}
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// Check whether HFS+ would resolve a component to the `.git` directory
+fn is_hfs_dotgit(component: &str) -> bool {
+    let stripped: String = component
+        .chars()
+        .filter(|c| !HFS_IGNORABLE_CHARS.contains(c))
+        .collect();
+    stripped.eq_ignore_ascii_case(".git")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_safe_paths() {
+        for path in [
+            "README.md",
+            "src/main.rs",
+            ".gitignore",
+            ".gitmodules",
+            ".github/workflows/ci.yml",
+            "git~2",
+            "my.git/config",
+            ".git-blame-ignore-revs",
+            "docs/console.md",
+            "unicode/café.txt",
+        ] {
+            assert!(
+                validate_git_checkout_safe(path).is_ok(),
+                "should be safe: {:?}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_dotgit_variants() {
+        // Variants from Git's own tests for protectNTFS and protectHFS
+        for path in [
+            ".git",
+            ".git/config",
+            "sub/.git/hooks/pre-commit",
+            ".GIT/config",
+            ".Git",
+            ".git.",
+            ".git ",
+            ".git. .",
+            ".git...",
+            "git~1/config",
+            "GIT~1",
+            "Git~1.",
+            ".git::$INDEX_ALLOCATION/config",
+            "git~1::$INDEX_ALLOCATION",
+            ".g\u{200C}it/config",
+            "\u{FEFF}.git",
+            ".GI\u{206F}T",
+        ] {
+            assert!(
+                matches!(
+                    validate_git_checkout_safe(path),
+                    Err(PathError::ValidationFailed {
+                        rule: "git_dotgit",
+                        ..
+                    })
+                ),
+                "should be rejected as .git: {:?}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_dotgit_confusable() {
+        for component in [
+            ".git",
+            ".GIT",
+            ".Git",
+            ".gIT",
+            ".git ",
+            ".git.",
+            ".git. . ",
+            "git~1",
+            "GIT~1",
+            "git~1 .",
+            ".git::$INDEX_ALLOCATION",
+            ".git:stream",
+            "\u{200E}.git",
+            ".gi\u{FEFF}t",
+        ] {
+            assert!(is_dotgit_confusable(component), "{:?}", component);
+        }
+
+        for component in [
+            "",
+            "git",
+            ".gi",
+            ".gitattributes",
+            ".git-credentials",
+            ".git~1",
+            "git~10",
+            "git~2",
+            "x.git",
+            ".git.x",
+            "\u{200E}.gitx",
+        ] {
+            assert!(!is_dotgit_confusable(component), "{:?}", component);
+        }
+    }
+
+    #[test]
+    fn test_reject_dotgit_option() {
+        use crate::options::ValidationOptions;
+        use crate::validate::validate_path_with;
+
+        let options = ValidationOptions::new().reject_dotgit(true);
+        assert!(validate_path_with("src/.gitignore", &options).is_ok());
+        for path in ["repo/.git/config", "GIT~1\\hooks", "a/.Git./b"] {
+            assert!(
+                matches!(
+                    validate_path_with(path, &options),
+                    Err(PathError::ReservedFilename { .. })
+                ),
+                "{:?}",
+                path
+            );
+        }
+
+        // Off by default
+        assert!(validate_path_with("repo/.git/config", &ValidationOptions::new()).is_ok());
+    }
+
+    #[test]
+    fn test_path_shape() {
+        assert_eq!(validate_git_checkout_safe(""), Err(PathError::EmptyPath));
+        for path in ["/etc/passwd", "a//b", "dir/", "\\\\server\\share"] {
+            assert!(
+                matches!(
+                    validate_git_checkout_safe(path),
+                    Err(PathError::ValidationFailed {
+                        rule: "git_empty_component",
+                        ..
+                    })
+                ),
+                "bad shape should be rejected: {:?}",
+                path
+            );
+        }
+        for path in ["..", "../x", "a/./b", "a\\..\\b"] {
+            assert!(matches!(
+                validate_git_checkout_safe(path),
+                Err(PathError::PathTraversal { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_windows_rules() {
+        for path in [
+            "a:b",
+            "what?",
+            "x|y",
+            "tab\there",
+            "trailing.",
+            "trailing ",
+            "dir./x",
+        ] {
+            assert!(
+                matches!(
+                    validate_git_checkout_safe(path),
+                    Err(PathError::InvalidCharacters { .. })
+                ),
+                "should be rejected: {:?}",
+                path
+            );
+        }
+        for path in [
+            "CON",
+            "aux.c",
+            "lib/nul.txt",
+            "COM1",
+            "conin$",
+            "CONOUT$.log",
+        ] {
+            assert!(
+                matches!(
+                    validate_git_checkout_safe(path),
+                    Err(PathError::ReservedFilename { .. })
+                ),
+                "should be reserved: {:?}",
+                path
+            );
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! `.gitignore`-style path filtering
+//!
+//! Builds on the glob matcher with the rules git layers on top of it:
+//! negation, directory-only patterns and anchoring.
+
+use crate::glob::matches_glob_components;
+use crate::normalize::normalized_components;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// A list of `.gitignore` patterns, applied in order
+///
+/// Each line is a [`matches_glob`](crate::matches_glob) pattern, with git's
+/// additional rules:
+///
+/// - Blank lines and lines starting with `#` are skipped, and trailing spaces
+///   are ignored
+/// - A leading `!` negates the pattern, re-including paths an earlier pattern
+///   ignored
+/// - A trailing `/` makes the pattern match directories only
+/// - A pattern containing a `/` other than a trailing one is anchored to the
+///   root, so `/build` and `docs/build` match only from the top; any other
+///   pattern matches at every depth, so `build` matches `a/b/build` too
+/// - A trailing `/**` matches everything inside a directory, but unlike
+///   [`matches_glob`](crate::matches_glob) not the directory itself
+/// - A leading `\` escapes a literal `#` or `!`
+///
+/// The last matching pattern decides. As in git, once a directory is ignored
+/// everything below it is ignored as well, and a later negation can't
+/// re-include a path inside it. Separators may be `/` or `\`, as everywhere in
+/// this crate, so backslash escapes are supported only at the start of a line.
+///
+/// # Examples
+/// ```
+/// use path_utils::GitignoreMatcher;
+///
+/// let matcher = GitignoreMatcher::from_lines(&["*.log", "!keep.log", "/target/"]);
+/// assert!(matcher.is_ignored("logs/debug.log"));
+/// assert!(!matcher.is_ignored("logs/keep.log"));
+/// assert!(matcher.is_ignored("target/debug/app"));
+/// assert!(!matcher.is_ignored("src/target"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GitignoreMatcher {
+    patterns: Vec<GitignorePattern>,
+}
+
+/// A single parsed `.gitignore` line
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct GitignorePattern {
+    /// Glob components, starting with `**` for unanchored patterns
+    components: Vec<String>,
+    negated: bool,
+    directory_only: bool,
+}
+
+impl GitignoreMatcher {
+    /// Parse patterns from the lines of a `.gitignore` file
+    pub fn from_lines(lines: &[&str]) -> Self {
+        Self {
+            patterns: lines
+                .iter()
+                .filter_map(|line| GitignorePattern::parse(line))
+                .collect(),
+        }
+    }
+
+    /// Check whether a path is ignored
+    ///
+    /// The path is split into components like
+    /// [`normalized_components`](crate::normalized_components). Only a trailing
+    /// separator marks the path itself as a directory, so pass `build/` rather
+    /// than `build` to test a directory against directory-only patterns. The
+    /// directories above the path are always known to be directories.
+    pub fn is_ignored(&self, path: &str) -> bool {
+        let components: Vec<&str> = normalized_components(path).collect();
+        let is_directory = path.ends_with(['/', '\\']);
+
+        for end in 1..components.len() {
+            if self.last_match(&components[..end], true) {
+                return true;
+            }
+        }
+        !components.is_empty() && self.last_match(&components, is_directory)
+    }
+
+    /// Whether the last pattern matching `components` ignores it
+    fn last_match(&self, components: &[&str], is_directory: bool) -> bool {
+        self.patterns
+            .iter()
+            .rev()
+            .find(|pattern| {
+                (is_directory || !pattern.directory_only)
+                    && matches_glob_components(&pattern.components, components)
+            })
+            .is_some_and(|pattern| !pattern.negated)
+    }
+}
+
+impl GitignorePattern {
+    /// Parse one line, returning `None` for blank lines and comments
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim_end_matches(' ');
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+        let (directory_only, line) = match line.strip_suffix(['/', '\\']) {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let anchored = line.contains(['/', '\\']);
+
+        let mut components: Vec<String> = Vec::new();
+        if !anchored {
+            components.push("**".to_string());
+        }
+        let start = components.len();
+        components.extend(normalized_components(line).map(ToString::to_string));
+        if components.len() == start {
+            // Nothing but separators
+            return None;
+        }
+        if components.last().is_some_and(|last| last == "**") {
+            // `a/**` must not match `a` itself, so require one more component
+            components.insert(components.len() - 1, "*".to_string());
+        }
+
+        Some(Self {
+            components,
+            negated,
+            directory_only,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unanchored_patterns() {
+        let matcher = GitignoreMatcher::from_lines(&["*.log", "node_modules"]);
+        assert!(matcher.is_ignored("debug.log"));
+        assert!(matcher.is_ignored("a/b/debug.log"));
+        assert!(matcher.is_ignored("node_modules"));
+        assert!(matcher.is_ignored("web/node_modules/react/index.js"));
+        assert!(matcher.is_ignored("a\\b\\c.log"));
+        assert!(!matcher.is_ignored("src/main.rs"));
+        assert!(!matcher.is_ignored("log"));
+    }
+
+    #[test]
+    fn test_negation() {
+        let matcher = GitignoreMatcher::from_lines(&["*.log", "!important.log"]);
+        assert!(matcher.is_ignored("debug.log"));
+        assert!(!matcher.is_ignored("important.log"));
+        assert!(!matcher.is_ignored("logs/important.log"));
+
+        // Last match wins, so the order matters
+        let matcher = GitignoreMatcher::from_lines(&["!important.log", "*.log"]);
+        assert!(matcher.is_ignored("important.log"));
+
+        // A file inside an ignored directory can't be re-included
+        let matcher = GitignoreMatcher::from_lines(&["build/", "!build/keep.txt"]);
+        assert!(matcher.is_ignored("build/keep.txt"));
+
+        // But ignoring the contents rather than the directory allows it
+        let matcher = GitignoreMatcher::from_lines(&["build/*", "!build/keep.txt"]);
+        assert!(!matcher.is_ignored("build/keep.txt"));
+        assert!(matcher.is_ignored("build/other.txt"));
+    }
+
+    #[test]
+    fn test_anchoring() {
+        let matcher = GitignoreMatcher::from_lines(&["/target", "docs/build"]);
+        assert!(matcher.is_ignored("target"));
+        assert!(matcher.is_ignored("target/debug/app"));
+        assert!(!matcher.is_ignored("crates/a/target"));
+        assert!(matcher.is_ignored("docs/build/index.html"));
+        assert!(!matcher.is_ignored("site/docs/build"));
+
+        // A leading slash on the path itself doesn't matter
+        assert!(matcher.is_ignored("/target/x"));
+
+        // `**` lifts the anchor for part of the pattern
+        let matcher = GitignoreMatcher::from_lines(&["src/**/generated"]);
+        assert!(matcher.is_ignored("src/generated/a.rs"));
+        assert!(matcher.is_ignored("src/a/b/generated/a.rs"));
+        assert!(!matcher.is_ignored("lib/generated/a.rs"));
+    }
+
+    #[test]
+    fn test_trailing_double_star() {
+        let matcher = GitignoreMatcher::from_lines(&["target/**"]);
+        assert!(matcher.is_ignored("target/debug"));
+        assert!(matcher.is_ignored("target/debug/app"));
+        assert!(!matcher.is_ignored("target"));
+        assert!(!matcher.is_ignored("target/"));
+
+        // Since `logs` itself is not ignored, a negation can re-include a file in it
+        let matcher = GitignoreMatcher::from_lines(&["logs/**", "!logs/keep.log"]);
+        assert!(matcher.is_ignored("logs/debug.log"));
+        assert!(!matcher.is_ignored("logs/keep.log"));
+    }
+
+    #[test]
+    fn test_directory_only() {
+        let matcher = GitignoreMatcher::from_lines(&["cache/"]);
+        assert!(matcher.is_ignored("cache/"));
+        assert!(matcher.is_ignored("cache/data.bin"));
+        assert!(matcher.is_ignored("app/cache/data.bin"));
+
+        // A file called `cache` is not a directory
+        assert!(!matcher.is_ignored("cache"));
+        assert!(!matcher.is_ignored("app/cache"));
+    }
+
+    #[test]
+    fn test_comments_and_blank_lines() {
+        let matcher = GitignoreMatcher::from_lines(&[
+            "# build output",
+            "",
+            "   ",
+            "out  ",
+            "\\#notes",
+            "\\!bang",
+            "/",
+        ]);
+        assert!(matcher.is_ignored("out/a"));
+        assert!(matcher.is_ignored("#notes"));
+        assert!(matcher.is_ignored("!bang"));
+        assert!(!matcher.is_ignored("# build output"));
+        assert!(!matcher.is_ignored("src/a.rs"));
+
+        let empty = GitignoreMatcher::from_lines(&[]);
+        assert!(!empty.is_ignored("anything"));
+        assert!(!GitignoreMatcher::from_lines(&["*"]).is_ignored(""));
+    }
+}
@@ -0,0 +1,165 @@
+//! Glob matching
+//!
+//! A small, dependency-free matcher for the common shell wildcards, for
+//! filtering normalized paths.
+
+use crate::normalize::normalized_components;
+use alloc::vec::Vec;
+
+/// Check whether a path matches a glob pattern
+///
+/// Both the pattern and the path are split into components the way
+/// [`normalize_path_str`](crate::normalize_path_str) does, so separators may be
+/// `/` or `\` and empty components are ignored. The supported wildcards are:
+///
+/// - `*` matches any run of characters within a single component
+/// - `?` matches exactly one character other than a separator
+/// - `**`, as a whole component, matches zero or more components, so `a/**`
+///   matches `a` itself as well as everything below it
+///
+/// Every other character matches itself, case-sensitively. There are no
+/// character classes and no escapes. Like normalization, matching is purely
+/// lexical: `.` and `..` are ordinary components.
+///
+/// # Examples
+/// ```
+/// use path_utils::matches_glob;
+///
+/// assert!(matches_glob("src/**/*.rs", "src/a/b/main.rs"));
+/// assert!(matches_glob("*.txt", "notes.txt"));
+/// assert!(!matches_glob("*.txt", "docs/notes.txt"));
+/// ```
+pub fn matches_glob(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = normalized_components(pattern).collect();
+    let path: Vec<&str> = normalized_components(path).collect();
+
+    matches_glob_components(&pattern, &path)
+}
+
+/// Match already-split pattern components against already-split path components
+pub(crate) fn matches_glob_components<P: AsRef<str>>(pattern: &[P], path: &[&str]) -> bool {
+    wildcard_match(
+        pattern,
+        path,
+        |segment| segment.as_ref() == "**",
+        |segment, component| matches_component(segment.as_ref(), component),
+    )
+}
+
+/// Match a single pattern component against a single path component
+fn matches_component(pattern: &str, component: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let component: Vec<char> = component.chars().collect();
+
+    wildcard_match(
+        &pattern,
+        &component,
+        |&c| c == '*',
+        |&p, &c| p == '?' || p == c,
+    )
+}
+
+/// Match a sequence against a pattern containing "match anything" wildcards
+///
+/// `is_star` identifies a wildcard that matches any run of items, including an
+/// empty one, and `matches_one` decides whether any other pattern item matches
+/// a single item. Only the most recent wildcard needs revisiting on a mismatch,
+/// so this runs in `O(pattern * text)` time without recursion.
+fn wildcard_match<P, T>(
+    pattern: &[P],
+    text: &[T],
+    is_star: impl Fn(&P) -> bool,
+    matches_one: impl Fn(&P, &T) -> bool,
+) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // Pattern index of the last wildcard, and the text index it resumes from
+    let mut backtrack = None;
+
+    while t < text.len() {
+        if p < pattern.len() && is_star(&pattern[p]) {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if p < pattern.len() && matches_one(&pattern[p], &text[t]) {
+            p += 1;
+            t += 1;
+        } else if let Some((star, resume)) = backtrack {
+            // Let the wildcard absorb one more item and try again
+            p = star + 1;
+            t = resume + 1;
+            backtrack = Some((star, resume + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(is_star)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literal() {
+        assert!(matches_glob("src/main.rs", "src/main.rs"));
+        assert!(matches_glob("src/main.rs", "/src\\main.rs"));
+        assert!(!matches_glob("src/main.rs", "src/lib.rs"));
+        assert!(!matches_glob("src/main.rs", "src/main.rs/extra"));
+        assert!(!matches_glob("Src/main.rs", "src/main.rs"));
+    }
+
+    #[test]
+    fn test_star() {
+        assert!(matches_glob("*.rs", "main.rs"));
+        assert!(matches_glob("*.rs", ".rs"));
+        assert!(matches_glob("src/*", "src/main.rs"));
+        assert!(matches_glob("a*b*c", "aXXbYYbZc"));
+        assert!(matches_glob("*", "anything"));
+
+        // Never crosses a separator
+        assert!(!matches_glob("*.rs", "src/main.rs"));
+        assert!(!matches_glob("src/*", "src/bin/main.rs"));
+        assert!(!matches_glob("a*b", "a/b"));
+    }
+
+    #[test]
+    fn test_question_mark() {
+        assert!(matches_glob("file?.txt", "file1.txt"));
+        assert!(matches_glob("??", "é!"));
+        assert!(!matches_glob("file?.txt", "file.txt"));
+        assert!(!matches_glob("file?.txt", "file10.txt"));
+        assert!(!matches_glob("a?b", "a/b"));
+    }
+
+    #[test]
+    fn test_double_star() {
+        assert!(matches_glob("src/**/*.rs", "src/a/b/main.rs"));
+        assert!(matches_glob("src/**/*.rs", "src/main.rs"));
+        assert!(matches_glob("**/*.rs", "main.rs"));
+        assert!(matches_glob("**/test/**", "a/test/b/c"));
+        assert!(matches_glob("**", "a/b/c"));
+        assert!(matches_glob("a/**/b/**/c", "a/x/b/y/z/c"));
+        assert!(!matches_glob("src/**/*.rs", "docs/a/main.rs"));
+        assert!(!matches_glob("src/**/*.rs", "src/a/main.py"));
+
+        // `**` inside a component is an ordinary `*`
+        assert!(matches_glob("a**b", "aXb"));
+        assert!(!matches_glob("a**b", "a/X/b"));
+    }
+
+    #[test]
+    fn test_trailing_double_star() {
+        assert!(matches_glob("target/**", "target/debug/build"));
+        assert!(matches_glob("target/**", "target/x"));
+        assert!(matches_glob("target/**", "target"));
+        assert!(!matches_glob("target/**", "targets/x"));
+    }
+
+    #[test]
+    fn test_empty() {
+        assert!(matches_glob("", ""));
+        assert!(matches_glob("**", ""));
+        assert!(!matches_glob("", "a"));
+        assert!(!matches_glob("*", ""));
+    }
+}
@@ -0,0 +1,697 @@
+//! Interoperability helpers
+//!
+//! Conversions between paths and the formats other tools use to refer to them.
+
+use crate::classify::{classify, PathKind};
+use crate::error::{PathError, Result};
+use crate::normalize::{
+    normalize_path_str, normalize_path_str_preserving_root, normalize_with_absoluteness,
+    sanitize_directory_file_path, sanitize_directory_file_path_with,
+};
+use crate::options::ValidationOptions;
+use crate::validate::{validate_path, validate_path_with};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// Python's hard keywords, which can never be used as a module name
+const PYTHON_KEYWORDS: [&str; 35] = [
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// Split a `path:annotation` string, such as grep-style `src/main.rs:42`
+///
+/// The split happens on the *last* colon, so grep-style `file:line:column`
+/// keeps `file:line` as the path. A colon directly after a leading drive
+/// letter (`C:`) is never treated as the separator. An empty annotation
+/// (`file.txt:`) is treated as absent. The path portion is checked with
+/// [`validate_path`] and returned unchanged, except that colons are allowed in
+/// it, since here they separate annotations rather than name an NTFS stream.
+///
+/// # Examples
+/// ```
+/// use path_utils::parse_annotated_path;
+///
+/// assert_eq!(
+///     parse_annotated_path("src/main.rs:42").unwrap(),
+///     ("src/main.rs".to_string(), Some("42".to_string()))
+/// );
+/// assert_eq!(
+///     parse_annotated_path("src/main.rs").unwrap(),
+///     ("src/main.rs".to_string(), None)
+/// );
+/// assert!(parse_annotated_path("../secret:1").is_err());
+/// ```
+pub fn parse_annotated_path(s: &str) -> Result<(String, Option<String>)> {
+    let (path, annotation) = match s.rfind(':') {
+        Some(1) if s.as_bytes()[0].is_ascii_alphabetic() => (s, None),
+        Some(index) => (&s[..index], Some(&s[index + 1..])),
+        None => (s, None),
+    };
+
+    validate_path_with(path, &ValidationOptions::new().reject_ads(false))?;
+
+    let annotation = annotation
+        .filter(|annotation| !annotation.is_empty())
+        .map(str::to_string);
+    Ok((path.to_string(), annotation))
+}
+
+/// Split a null-delimited path list, as written by `find -print0`, and sanitize each entry
+///
+/// Null bytes are the only delimiter that can't appear in a path, unlike
+/// newlines, so this is the reliable way to process untrusted path lists.
+/// Each segment is decoded as UTF-8 (lossily, replacing invalid sequences) and
+/// run through [`sanitize_directory_file_path`](crate::sanitize_directory_file_path).
+/// Returns each decoded input paired with its result, in order. A final
+/// terminating null byte does not produce an empty entry.
+///
+/// # Examples
+/// ```
+/// use path_utils::sanitize_null_delimited;
+///
+/// let results = sanitize_null_delimited(b"/src/a.rs\0../etc/passwd\0");
+/// assert_eq!(results.len(), 2);
+/// assert_eq!(results[0].1.as_deref(), Ok("src/a.rs"));
+/// assert!(results[1].1.is_err());
+/// ```
+pub fn sanitize_null_delimited(data: &[u8]) -> Vec<(String, Result<String>)> {
+    sanitize_null_delimited_with(data, &ValidationOptions::default())
+}
+
+/// Split and sanitize a null-delimited path list against a configurable policy
+///
+/// Performs the same processing as [`sanitize_null_delimited`], applying the
+/// rules from `options`. With
+/// [`reject_invalid_utf8`](ValidationOptions::reject_invalid_utf8) enabled,
+/// segments that aren't valid UTF-8 fail with
+/// [`PathError::InvalidCharacters`] instead of being decoded lossily.
+pub fn sanitize_null_delimited_with(
+    data: &[u8],
+    options: &ValidationOptions,
+) -> Vec<(String, Result<String>)> {
+    let data = data.strip_suffix(b"\0").unwrap_or(data);
+    if data.is_empty() {
+        return Vec::new();
+    }
+
+    data.split(|&byte| byte == 0)
+        .map(|segment| {
+            let path = String::from_utf8_lossy(segment).into_owned();
+            let result = if options.reject_invalid_utf8 && core::str::from_utf8(segment).is_err() {
+                Err(PathError::InvalidCharacters { path: path.clone() })
+            } else {
+                sanitize_directory_file_path_with(&path, options)
+            };
+            (path, result)
+        })
+        .collect()
+}
+
+/// Percent-encode a path for use as the path of a URL
+///
+/// Every byte other than an ASCII letter, digit, `-`, `.`, `_`, `~` or `/` is
+/// written as `%XX` with uppercase hex digits. This covers spaces, `%`, `#`, `?`
+/// and all non-ASCII text (encoded as UTF-8), while `/` stays a segment
+/// separator. The path is not normalized or validated; sanitize it first.
+///
+/// # Examples
+/// ```
+/// use path_utils::to_url_path;
+///
+/// assert_eq!(to_url_path("a b/c#d.txt"), "a%20b/c%23d.txt");
+/// assert_eq!(to_url_path("café/menu.pdf"), "caf%C3%A9/menu.pdf");
+/// ```
+pub fn to_url_path(path: &str) -> String {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+    let mut encoded = String::with_capacity(path.len());
+    for &byte in path.as_bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~' | b'/') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push('%');
+            encoded.push(HEX[usize::from(byte >> 4)] as char);
+            encoded.push(HEX[usize::from(byte & 0xF)] as char);
+        }
+    }
+    encoded
+}
+
+/// Decode a percent-encoded URL path and sanitize the result
+///
+/// Decoding happens *before* any checks, so an encoded traversal such as
+/// `%2e%2e%2f` is seen as the `../` it stands for. The decoded path is then run
+/// through [`sanitize_directory_file_path`], which strips a leading `/`,
+/// normalizes separators (including an encoded `%5C`) and applies the default
+/// validation rules. `+` is not treated as a space, since that convention only
+/// applies to query strings.
+///
+/// # Errors
+/// - [`PathError::InvalidCharacters`] if a `%` is not followed by two hex
+///   digits, or if the decoded bytes are not valid UTF-8
+/// - Any error from [`sanitize_directory_file_path`] for the decoded path
+///
+/// # Examples
+/// ```
+/// use path_utils::from_url_path;
+///
+/// assert_eq!(from_url_path("/a%20b/c%23d.txt").unwrap(), "a b/c#d.txt");
+/// assert!(from_url_path("%2e%2e/etc/passwd").is_err());
+/// ```
+pub fn from_url_path(encoded: &str) -> Result<String> {
+    let decoded = percent_decode(encoded)?;
+    sanitize_directory_file_path(&decoded)
+}
+
+/// Convert an absolute path into a `file://` URL
+///
+/// The path is normalized with
+/// [`normalize_path_str_preserving_root`](crate::normalize_path_str_preserving_root)
+/// and percent-encoded as by [`to_url_path`]. A Windows drive path gets the
+/// conventional extra slash, so `C:\Users\me` becomes `file:///C:/Users/me`.
+///
+/// # Errors
+/// - [`PathError::ConstructionFailed`] if the path is relative (including
+///   drive-relative paths like `C:foo`), since a file URL is always absolute
+/// - [`PathError::UncPath`] for a UNC path, which would need a URL host
+/// - Any error from [`validate_path`]
+///
+/// # Examples
+/// ```
+/// use path_utils::to_file_url;
+///
+/// assert_eq!(to_file_url("/home/me/a b.txt").unwrap(), "file:///home/me/a%20b.txt");
+/// assert_eq!(to_file_url("C:\\Users\\me").unwrap(), "file:///C:/Users/me");
+/// assert!(to_file_url("src/main.rs").is_err());
+/// ```
+pub fn to_file_url(path: &str) -> Result<String> {
+    if classify(path) == PathKind::Unc {
+        return Err(PathError::UncPath {
+            path: path.to_string(),
+        });
+    }
+    if !normalize_with_absoluteness(path).1 {
+        return Err(PathError::ConstructionFailed {
+            message: format!("A file URL needs an absolute path, got {}", path),
+        });
+    }
+    validate_path(path)?;
+
+    let normalized = normalize_path_str_preserving_root(path);
+    if normalized.starts_with('/') {
+        Ok(format!("file://{}", to_url_path(&normalized)))
+    } else {
+        let (drive, rest) = normalized.split_at(2);
+        Ok(format!("file:///{}{}", drive, to_url_path(rest)))
+    }
+}
+
+/// Convert a `file://` URL back into a validated, normalized path
+///
+/// The inverse of [`to_file_url`]: the URL's path is percent-decoded, the extra
+/// slash before a drive letter is dropped (`file:///C:/x` gives `C:/x`), and
+/// the result is checked with [`validate_path`] and normalized with its root
+/// preserved. The scheme is matched case-insensitively, and the host must be
+/// empty or `localhost`.
+///
+/// # Errors
+/// - [`PathError::ConstructionFailed`] if `url` is not a `file://` URL or names
+///   a remote host
+/// - [`PathError::InvalidCharacters`] for a malformed escape, decoded bytes
+///   that aren't UTF-8, or a query or fragment
+/// - [`PathError::UncPath`] if the decoded path starts with two separators,
+///   as in `file:////server/share`, since it would name a network share
+/// - Any error from [`validate_path`] for the decoded path
+///
+/// # Examples
+/// ```
+/// use path_utils::from_file_url;
+///
+/// assert_eq!(from_file_url("file:///home/me/a%20b.txt").unwrap(), "/home/me/a b.txt");
+/// assert_eq!(from_file_url("file:///C:/Users/me").unwrap(), "C:/Users/me");
+/// assert!(from_file_url("file:///srv/%2e%2e/etc").is_err());
+/// ```
+pub fn from_file_url(url: &str) -> Result<String> {
+    let not_a_file_url = || PathError::ConstructionFailed {
+        message: format!("Not a local file URL: {}", url),
+    };
+
+    let rest = match url.get(..7) {
+        Some(scheme) if scheme.eq_ignore_ascii_case("file://") => &url[7..],
+        _ => return Err(not_a_file_url()),
+    };
+    let rest = match rest.get(..10) {
+        Some(host) if host.eq_ignore_ascii_case("localhost/") => &rest[9..],
+        _ => rest,
+    };
+    if !rest.starts_with('/') {
+        return Err(not_a_file_url());
+    }
+    if rest.contains(['?', '#']) {
+        return Err(PathError::InvalidCharacters {
+            path: url.to_string(),
+        });
+    }
+
+    let decoded = percent_decode(rest)?;
+    let bytes = decoded.as_bytes();
+    let path = if bytes.len() >= 3 && bytes[1].is_ascii_alphabetic() && bytes[2] == b':' {
+        &decoded[1..]
+    } else {
+        &decoded
+    };
+    if classify(path) == PathKind::Unc {
+        return Err(PathError::UncPath {
+            path: path.to_string(),
+        });
+    }
+
+    validate_path(path)?;
+    Ok(normalize_path_str_preserving_root(path))
+}
+
+/// Decode `%XX` escapes, requiring the result to be UTF-8
+fn percent_decode(encoded: &str) -> Result<String> {
+    let invalid = || PathError::InvalidCharacters {
+        path: encoded.to_string(),
+    };
+
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let high = bytes.get(index + 1).and_then(|&b| hex_value(b));
+            let low = bytes.get(index + 2).and_then(|&b| hex_value(b));
+            match (high, low) {
+                (Some(high), Some(low)) => decoded.push(high << 4 | low),
+                _ => return Err(invalid()),
+            }
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+
+    String::from_utf8(decoded).map_err(|_| invalid())
+}
+
+/// The value of a single ASCII hex digit
+fn hex_value(byte: u8) -> Option<u8> {
+    (byte as char).to_digit(16).map(|digit| digit as u8)
+}
+
+/// Convert a source file path into a dotted Python module name
+///
+/// The path is normalized, a trailing `.py` is stripped, and the components are
+/// joined with `.`. A package's `__init__.py` maps to the package itself, so
+/// `pkg/__init__.py` becomes `pkg`. Paths without the extension are accepted
+/// as package directories.
+///
+/// Each component must be a valid Python identifier: a letter or underscore
+/// followed by letters, digits or underscores, and not a keyword. Non-ASCII
+/// letters are accepted, approximating Python's Unicode identifier rules.
+///
+/// # Errors
+/// - [`PathError::EmptyPath`] if nothing remains after normalization
+/// - [`PathError::ValidationFailed`] naming the first component that is not a
+///   valid identifier
+///
+/// # Examples
+/// ```
+/// use path_utils::to_python_module;
+///
+/// assert_eq!(to_python_module("pkg/sub/mod.py").unwrap(), "pkg.sub.mod");
+/// assert_eq!(to_python_module("pkg\\__init__.py").unwrap(), "pkg");
+/// assert!(to_python_module("my-pkg/mod.py").is_err());
+/// ```
+pub fn to_python_module(path: &str) -> Result<String> {
+    let normalized = normalize_path_str(path);
+    let stem = normalized.strip_suffix(".py").unwrap_or(&normalized);
+
+    let mut components: Vec<&str> = stem.split('/').filter(|s| !s.is_empty()).collect();
+    if components.last() == Some(&"__init__") {
+        components.pop();
+    }
+
+    if components.is_empty() {
+        return Err(PathError::EmptyPath);
+    }
+
+    if let Some(invalid) = components
+        .iter()
+        .find(|component| !is_python_identifier(component))
+    {
+        return Err(PathError::validation_failed(
+            "python_identifier",
+            format!("{} is not a valid Python identifier in {}", invalid, path),
+        ));
+    }
+
+    Ok(components.join("."))
+}
+
+/// Check whether `name` is usable as a Python module name
+fn is_python_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    let starts_well = chars.next().is_some_and(|c| c == '_' || c.is_alphabetic());
+
+    starts_well
+        && chars.all(|c| c == '_' || c.is_alphanumeric())
+        && !PYTHON_KEYWORDS.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_annotated_path() {
+        assert_eq!(
+            parse_annotated_path("src/main.rs:42").unwrap(),
+            ("src/main.rs".to_string(), Some("42".to_string()))
+        );
+        assert_eq!(
+            parse_annotated_path("bin/run.sh:rwx").unwrap(),
+            ("bin/run.sh".to_string(), Some("rwx".to_string()))
+        );
+
+        // Only the last colon separates the annotation
+        assert_eq!(
+            parse_annotated_path("C:/src/main.rs:42:7").unwrap(),
+            ("C:/src/main.rs:42".to_string(), Some("7".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_annotated_path_without_annotation() {
+        assert_eq!(
+            parse_annotated_path("src/main.rs").unwrap(),
+            ("src/main.rs".to_string(), None)
+        );
+        assert_eq!(
+            parse_annotated_path("src/main.rs:").unwrap(),
+            ("src/main.rs".to_string(), None)
+        );
+
+        // A drive letter colon is not an annotation
+        assert_eq!(
+            parse_annotated_path("C:\\src\\main.rs").unwrap(),
+            ("C:\\src\\main.rs".to_string(), None)
+        );
+        assert_eq!(
+            parse_annotated_path("C:/src/main.rs:42").unwrap(),
+            ("C:/src/main.rs".to_string(), Some("42".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_annotated_path_validates_path() {
+        assert!(matches!(
+            parse_annotated_path("../etc/passwd:1"),
+            Err(PathError::PathTraversal { .. })
+        ));
+        assert!(matches!(
+            parse_annotated_path(":42"),
+            Err(PathError::EmptyPath)
+        ));
+        assert!(matches!(
+            parse_annotated_path("CON:1"),
+            Err(PathError::ReservedFilename { .. })
+        ));
+    }
+
+    #[test]
+    fn test_to_python_module() {
+        assert_eq!(to_python_module("pkg/sub/mod.py").unwrap(), "pkg.sub.mod");
+        assert_eq!(
+            to_python_module("/src//app\\views.py").unwrap(),
+            "src.app.views"
+        );
+        assert_eq!(to_python_module("pkg/__init__.py").unwrap(), "pkg");
+        assert_eq!(to_python_module("pkg/sub").unwrap(), "pkg.sub");
+        assert_eq!(
+            to_python_module("_private/__main__.py").unwrap(),
+            "_private.__main__"
+        );
+        assert_eq!(to_python_module("données/café.py").unwrap(), "données.café");
+    }
+
+    #[test]
+    fn test_to_python_module_invalid_components() {
+        // The error names the first invalid component
+        match to_python_module("pkg/my-module/x-y.py") {
+            Err(PathError::ValidationFailed { rule, message }) => {
+                assert_eq!(rule, "python_identifier");
+                assert!(message.starts_with("my-module "), "{}", message)
+            }
+            other => panic!("expected ValidationFailed, got {:?}", other),
+        }
+
+        for path in [
+            "2fast.py",
+            "pkg/class.py",
+            "a.b.py",
+            "pkg/with space.py",
+            "None/x.py",
+        ] {
+            assert!(
+                matches!(
+                    to_python_module(path),
+                    Err(PathError::ValidationFailed { .. })
+                ),
+                "should be rejected: {:?}",
+                path
+            );
+        }
+
+        assert!(matches!(to_python_module(""), Err(PathError::EmptyPath)));
+        assert!(matches!(
+            to_python_module("__init__.py"),
+            Err(PathError::EmptyPath)
+        ));
+    }
+
+    #[test]
+    fn test_sanitize_null_delimited() {
+        let data =
+            b"/src/main.rs\0notes/line one\nline two.txt\0../../etc/passwd\0docs\\guide.md\0";
+        let results = sanitize_null_delimited(data);
+
+        let inputs: Vec<&str> = results.iter().map(|(input, _)| input.as_str()).collect();
+        assert_eq!(
+            inputs,
+            [
+                "/src/main.rs",
+                "notes/line one\nline two.txt",
+                "../../etc/passwd",
+                "docs\\guide.md"
+            ]
+        );
+
+        assert_eq!(results[0].1, Ok("src/main.rs".to_string()));
+        // A newline is a legal path character and survives intact
+        assert_eq!(results[1].1, Ok("notes/line one\nline two.txt".to_string()));
+        assert!(matches!(results[2].1, Err(PathError::PathTraversal { .. })));
+        assert_eq!(results[3].1, Ok("docs/guide.md".to_string()));
+    }
+
+    #[test]
+    fn test_sanitize_null_delimited_edge_cases() {
+        assert!(sanitize_null_delimited(b"").is_empty());
+        assert!(sanitize_null_delimited(b"\0").is_empty());
+
+        // Without a trailing terminator, and with an empty entry in the middle
+        let results = sanitize_null_delimited(b"a\0\0b");
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[1], (String::new(), Err(PathError::EmptyPath)));
+        assert_eq!(results[2].1, Ok("b".to_string()));
+    }
+
+    #[test]
+    fn test_sanitize_null_delimited_invalid_utf8() {
+        let data = b"caf\xE9.txt\0ok.txt";
+
+        // Decoded lossily by default
+        let results = sanitize_null_delimited(data);
+        assert_eq!(results[0].0, "caf\u{FFFD}.txt");
+        assert_eq!(results[0].1, Ok("caf\u{FFFD}.txt".to_string()));
+
+        let options = ValidationOptions::new().reject_invalid_utf8(true);
+        let results = sanitize_null_delimited_with(data, &options);
+        assert!(matches!(
+            results[0].1,
+            Err(PathError::InvalidCharacters { .. })
+        ));
+        assert_eq!(results[1].1, Ok("ok.txt".to_string()));
+    }
+
+    #[test]
+    fn test_to_url_path() {
+        assert_eq!(to_url_path("src/main.rs"), "src/main.rs");
+        assert_eq!(to_url_path("a b/c#d.txt"), "a%20b/c%23d.txt");
+        assert_eq!(to_url_path("100%/why?.md"), "100%25/why%3F.md");
+        assert_eq!(
+            to_url_path("naïve/日本.txt"),
+            "na%C3%AFve/%E6%97%A5%E6%9C%AC.txt"
+        );
+        assert_eq!(to_url_path("a\\b+c~d"), "a%5Cb%2Bc~d");
+        assert_eq!(to_url_path(""), "");
+    }
+
+    #[test]
+    fn test_url_path_round_trip() {
+        for path in [
+            "a b/c#d.txt",
+            "src/main.rs",
+            "100%/done.md",
+            "naïve/日本.txt",
+        ] {
+            assert_eq!(from_url_path(&to_url_path(path)).unwrap(), path);
+        }
+
+        // Lowercase hex digits decode too, and `+` stays a plus
+        assert_eq!(from_url_path("caf%c3%a9+menu").unwrap(), "café+menu");
+    }
+
+    #[test]
+    fn test_from_url_path_rejects_encoded_traversal() {
+        for encoded in [
+            "%2e%2e/etc",
+            "%2E%2E%2Fetc%2Fpasswd",
+            "a/%2e%2e/%2e%2e/etc",
+            "..%5C..%5Cwindows",
+            "a%00b",
+        ] {
+            assert!(
+                from_url_path(encoded).is_err(),
+                "should be rejected: {:?}",
+                encoded
+            );
+        }
+        assert!(matches!(
+            from_url_path("%2e%2e/etc"),
+            Err(PathError::PathTraversal { .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_url_path_malformed() {
+        for encoded in ["a%2", "a%zz", "%", "caf%E9"] {
+            assert!(
+                matches!(
+                    from_url_path(encoded),
+                    Err(PathError::InvalidCharacters { .. })
+                ),
+                "should be rejected: {:?}",
+                encoded
+            );
+        }
+    }
+
+    #[test]
+    fn test_to_file_url() {
+        assert_eq!(
+            to_file_url("/home/me/a b.txt").unwrap(),
+            "file:///home/me/a%20b.txt"
+        );
+        assert_eq!(to_file_url("/usr//lib/").unwrap(), "file:///usr/lib");
+        assert_eq!(to_file_url("/").unwrap(), "file:///");
+
+        // Windows drive paths
+        assert_eq!(
+            to_file_url("C:\\Users\\me\\Café #1.txt").unwrap(),
+            "file:///C:/Users/me/Caf%C3%A9%20%231.txt"
+        );
+        assert_eq!(to_file_url("d:/").unwrap(), "file:///d:/");
+    }
+
+    #[test]
+    fn test_to_file_url_rejects_relative_paths() {
+        for path in ["src/main.rs", "main.rs", "C:foo", ""] {
+            assert!(
+                matches!(to_file_url(path), Err(PathError::ConstructionFailed { .. })),
+                "should be rejected: {:?}",
+                path
+            );
+        }
+        assert!(matches!(
+            to_file_url("//server/share/x"),
+            Err(PathError::UncPath { .. })
+        ));
+        assert!(matches!(
+            to_file_url("/srv/../etc"),
+            Err(PathError::PathTraversal { .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_file_url() {
+        assert_eq!(
+            from_file_url("file:///home/me/a%20b.txt").unwrap(),
+            "/home/me/a b.txt"
+        );
+        assert_eq!(
+            from_file_url("FILE://localhost/etc/hosts").unwrap(),
+            "/etc/hosts"
+        );
+        assert_eq!(from_file_url("file:///C:/Users/me").unwrap(), "C:/Users/me");
+
+        for path in ["/home/me/a b.txt", "C:/Users/me/Café #1.txt", "/"] {
+            assert_eq!(from_file_url(&to_file_url(path).unwrap()).unwrap(), path);
+        }
+    }
+
+    #[test]
+    fn test_from_file_url_rejects_bad_urls() {
+        for url in [
+            "http://example.com/a",
+            "file:/etc/passwd",
+            "file://server/share/x",
+            "relative/path",
+        ] {
+            assert!(
+                matches!(
+                    from_file_url(url),
+                    Err(PathError::ConstructionFailed { .. })
+                ),
+                "should be rejected: {:?}",
+                url
+            );
+        }
+
+        assert!(matches!(
+            from_file_url("file:///srv/%2e%2e/etc"),
+            Err(PathError::PathTraversal { .. })
+        ));
+        assert!(matches!(
+            from_file_url("file:///a?b=c"),
+            Err(PathError::InvalidCharacters { .. })
+        ));
+        assert!(matches!(
+            from_file_url("file:///a%zz"),
+            Err(PathError::InvalidCharacters { .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_file_url_rejects_unc_paths() {
+        for url in [
+            "file:////server/share",
+            "file://///server/share/x",
+            "file:///%2Fserver/share",
+            "file:///%5Cserver/share",
+        ] {
+            assert!(
+                matches!(from_file_url(url), Err(PathError::UncPath { .. })),
+                "should be rejected: {:?}",
+                url
+            );
+        }
+    }
+}
@@ -19,6 +19,8 @@
 //! ### Basic Path Normalization
 //!
 //! ```rust
+//! # #[cfg(feature = "std")]
+//! # {
 //! use path_utils::{normalize_path_str, join_and_normalize};
 //! use std::path::PathBuf;
 //!
@@ -28,11 +30,14 @@
 //! // Join paths safely
 //! let result = join_and_normalize("source/", "/main.rs");
 //! assert_eq!(result, PathBuf::from("source/main.rs"));
+//! # }
 //! ```
 //!
 //! ### Security-Focused Path Sanitization
 //!
 //! ```rust
+//! # #[cfg(feature = "std")]
+//! # {
 //! use path_utils::{sanitize_directory_file_path, safe_repository_join};
 //! use std::path::Path;
 //!
@@ -44,6 +49,7 @@
 //! let temp_dir = std::env::temp_dir();
 //! let safe_path = safe_repository_join(&temp_dir, "project", "/config.js").unwrap();
 //! // Result: {temp_dir}/project/config.js (not root filesystem!)
+//! # }
 //! ```
 //!
 //! ### Path Traversal Prevention
@@ -56,9 +62,51 @@
 //! assert!(sanitize_directory_file_path("").is_err());
 //! assert!(sanitize_directory_file_path("file\0null").is_err());
 //! ```
+//!
+//! ## `no_std`
+//!
+//! The string-based normalization and validation functions only need an
+//! allocator. With `default-features = false` the crate is `#![no_std]` and
+//! depends only on `alloc`; the `std` feature (on by default) adds the
+//! filesystem, `Path` and `OsStr` APIs. Functions such as [`validate_path`]
+//! take `AsRef<str>` in either configuration, and with `std` each has a `_p`
+//! counterpart, like `validate_path_p`, that accepts `AsRef<Path>`.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
+extern crate alloc;
+
+mod archive;
+mod batch;
+mod classify;
+mod compare;
+mod components;
 mod error;
+#[cfg(feature = "std")]
+mod expand;
+mod filename;
+#[cfg(feature = "std")]
+mod fs;
+mod git;
+mod gitignore;
+mod glob;
+mod interop;
+mod lint;
+mod literal;
 mod normalize;
+mod options;
+#[cfg(feature = "std")]
+mod os;
+mod profile;
+mod relative;
+mod report;
+mod reserved;
+#[cfg(feature = "std")]
+mod sanitizer;
+#[cfg(feature = "unicode")]
+mod unicode;
+#[cfg(feature = "camino")]
+mod utf8;
 mod validate;
 
 // Generators module for property testing (available in tests)
@@ -66,12 +114,74 @@ mod validate;
 pub mod generators;
 
 // Re-export main public API
-pub use error::{PathError, Result};
+pub use archive::{validate_zip_name, zip_name_encoding, ZipEncoding};
+pub use batch::{
+    common_ancestor, dedupe_paths, dedupe_paths_with, find_case_collisions,
+    longest_common_path_len, max_entries_per_directory, prune_to_depth,
+};
+pub use classify::{classify, guess_path_kind, is_hidden, PathKind, PathKindGuess};
+pub use compare::{
+    canonical_key, canonical_key_with, compare_paths, compare_paths_natural, paths_equal,
+    paths_equal_with, PathComparison,
+};
+pub use components::{parent_path, split_extension, with_extension};
+pub use error::{ErrorCategory, IoErrorKind, PathError, Result};
+#[cfg(feature = "std")]
+pub use expand::{expand_env, expand_env_with, expand_tilde, UnknownVariable};
+pub use filename::{content_addressed_name, sanitize_filename};
+#[cfg(feature = "std")]
+pub use fs::{create_temp_dir_under, ensure_within_root};
+pub use git::{is_dotgit_confusable, validate_git_checkout_safe};
+pub use gitignore::GitignoreMatcher;
+pub use glob::matches_glob;
+pub use interop::{
+    from_file_url, from_url_path, parse_annotated_path, sanitize_null_delimited,
+    sanitize_null_delimited_with, to_file_url, to_python_module, to_url_path,
+};
+pub use lint::{lint_path, PathLint, PathLintKind};
+pub use literal::is_safe_path_literal;
+#[cfg(feature = "unicode-normalization")]
+pub use normalize::normalize_unicode;
+pub use normalize::{
+    check_join_compatibility, normalize_into, normalize_path_cow, normalize_path_str,
+    normalize_path_str_preserving_root, normalize_path_str_with, normalize_with_absoluteness,
+    normalized_components, path_depth, relative_depth_escape, safe_join_both_untrusted,
+    sanitize_directory_file_path, sanitize_directory_file_path_lossy,
+    sanitize_directory_file_path_with, strip_drive_letter, to_forward_slashes,
+    trim_redundant_separators,
+};
+#[cfg(feature = "std")]
 pub use normalize::{
-    join_and_normalize, normalize_path_buf, normalize_path_str, safe_repository_join,
-    sanitize_directory_file_path,
+    join_all, join_and_normalize, normalize_path_buf, safe_repository_join,
+    safe_repository_join_lexical, safe_repository_join_no_symlinks, sanitize_directory_file_path_p,
+    to_native_separators, try_join_all,
+};
+pub use options::{NormalizeOptions, PathLimits, TargetPlatform, ValidationOptions};
+#[cfg(feature = "std")]
+pub use os::{is_safe_os_path, normalize_os_str};
+pub use profile::{validate_path_for_profile, FatProfile, TargetProfile};
+#[cfg(feature = "std")]
+pub use relative::relative_to_cwd;
+pub use relative::{contained_within, is_ancestor, make_relative, strip_prefix_normalized};
+pub use report::{analyze_path, ValidationReport};
+pub use reserved::{is_reserved_basename, ReservedNames};
+#[cfg(feature = "std")]
+pub use sanitizer::PathSanitizer;
+#[cfg(feature = "unicode")]
+pub use unicode::grapheme_count;
+#[cfg(feature = "camino")]
+pub use utf8::{normalize_utf8, safe_repository_join_utf8};
+#[cfg(feature = "rayon")]
+pub use validate::validate_paths_par;
+pub use validate::{
+    count_traversal_attempts, is_safe_path, validate_filename, validate_length, validate_limits,
+    validate_path, validate_path_all, validate_path_with, validate_paths,
+};
+#[cfg(feature = "std")]
+pub use validate::{
+    is_safe_path_p, validate_and_normalize, validate_path_all_p, validate_path_p,
+    validate_path_with_p,
 };
-pub use validate::{is_safe_path, validate_path};
 
 // Version information
-pub const VERSION: &str = env!("CARGO_PKG_VERSION");
\ No newline at end of file
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
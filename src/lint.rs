@@ -0,0 +1,253 @@
+//! Path style linting
+//!
+//! Functions that point out untidy but harmless path spellings, such as those
+//! found in hand-written configuration files.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// The kind of issue reported by [`lint_path`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathLintKind {
+    /// A `.` component, as in `./src` or `src/./lib.rs`
+    RedundantCurrentDir,
+    /// Two or more separators in a row, as in `src//lib.rs`
+    DoubleSlash,
+    /// A separator at the end of the path, as in `src/`
+    TrailingSlash,
+    /// A backslash used as a separator, as in `src\lib.rs`
+    Backslash,
+}
+
+/// A single issue found by [`lint_path`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathLint {
+    /// What kind of issue this is
+    pub kind: PathLintKind,
+    /// Byte range of the offending text in the linted path
+    pub span: Range<usize>,
+    /// Text that should replace `span`
+    pub replacement: String,
+}
+
+/// Find redundant or non-portable spellings in a path
+///
+/// Reports `.` components, repeated separators, trailing separators and
+/// backslash separators. Lints are returned in order of position and their
+/// spans never overlap, so applying every replacement yields the tidy form of
+/// the path: forward slashes only, no `.` components (unless the path is just
+/// `.`), and no trailing separator. A leading `/` is kept.
+///
+/// A separator run that is both doubled and contains a backslash is reported
+/// once, as [`PathLintKind::DoubleSlash`].
+///
+/// # Examples
+/// ```
+/// use path_utils::{lint_path, PathLintKind};
+///
+/// let lints = lint_path("./src//lib.rs");
+/// assert_eq!(lints.len(), 2);
+/// assert_eq!(lints[0].kind, PathLintKind::RedundantCurrentDir);
+/// assert_eq!(lints[0].span, 0..2);
+/// assert_eq!(lints[1].kind, PathLintKind::DoubleSlash);
+/// assert_eq!(lints[1].replacement, "/");
+///
+/// assert!(lint_path("src/lib.rs").is_empty());
+/// ```
+pub fn lint_path(path: &str) -> Vec<PathLint> {
+    let tokens = tokenize(path);
+
+    // Decide which components survive in the tidy form
+    let rooted = tokens.first().is_some_and(|token| token.separator);
+    let mut keep: Vec<bool> = tokens
+        .iter()
+        .map(|token| !token.separator && token.text != ".")
+        .collect();
+    if !rooted && !keep.contains(&true) {
+        // A path made only of `.` components tidies to a single `.`
+        if let Some(first) = tokens.iter().position(|token| !token.separator) {
+            keep[first] = true;
+        }
+    }
+
+    let mut lints = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = &tokens[i];
+
+        if token.separator {
+            let kept_after = keep[i + 1..].contains(&true);
+            if i == 0 || (keep[i - 1] && kept_after) {
+                // This run should be a single `/`
+                let kind = if token.text.len() > 1 {
+                    Some(PathLintKind::DoubleSlash)
+                } else if token.text == "\\" {
+                    Some(PathLintKind::Backslash)
+                } else {
+                    None
+                };
+                if let Some(kind) = kind {
+                    lints.push(PathLint {
+                        kind,
+                        span: token.span(),
+                        replacement: "/".to_string(),
+                    });
+                }
+            } else {
+                // Runs following a `.` are consumed below, so anything left
+                // comes after the last surviving component
+                lints.push(PathLint {
+                    kind: PathLintKind::TrailingSlash,
+                    span: token.span(),
+                    replacement: String::new(),
+                });
+            }
+        } else if !keep[i] {
+            // A redundant `.`, removed along with the separators after it
+            let mut span = token.span();
+            if let Some(next) = tokens.get(i + 1) {
+                span.end = next.span().end;
+                i += 1;
+            }
+            lints.push(PathLint {
+                kind: PathLintKind::RedundantCurrentDir,
+                span,
+                replacement: String::new(),
+            });
+        }
+
+        i += 1;
+    }
+
+    lints
+}
+
+/// A component or a run of separators within a path
+struct Token<'a> {
+    start: usize,
+    text: &'a str,
+    separator: bool,
+}
+
+impl Token<'_> {
+    fn span(&self) -> Range<usize> {
+        self.start..self.start + self.text.len()
+    }
+}
+
+/// Split a path into alternating components and separator runs
+fn tokenize(path: &str) -> Vec<Token<'_>> {
+    let mut tokens: Vec<Token<'_>> = Vec::new();
+    for (start, c) in path.char_indices() {
+        let separator = c == '/' || c == '\\';
+        match tokens.last_mut() {
+            Some(last) if last.separator == separator => {
+                last.text = &path[last.start..start + c.len_utf8()];
+            }
+            _ => tokens.push(Token {
+                start,
+                text: &path[start..start + c.len_utf8()],
+                separator,
+            }),
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Apply every suggested replacement, back to front
+    fn apply(path: &str, lints: &[PathLint]) -> String {
+        let mut fixed = path.to_string();
+        for lint in lints.iter().rev() {
+            fixed.replace_range(lint.span.clone(), &lint.replacement);
+        }
+        fixed
+    }
+
+    fn kinds(path: &str) -> Vec<PathLintKind> {
+        lint_path(path).into_iter().map(|lint| lint.kind).collect()
+    }
+
+    #[test]
+    fn test_clean_paths_have_no_lints() {
+        for path in [
+            "",
+            ".",
+            "/",
+            "a",
+            "src/lib.rs",
+            "/etc/hosts",
+            "../a",
+            "a.b/c",
+        ] {
+            assert!(
+                lint_path(path).is_empty(),
+                "unexpected lints for {:?}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_individual_lints() {
+        use PathLintKind::*;
+
+        assert_eq!(kinds("./a"), [RedundantCurrentDir]);
+        assert_eq!(kinds("a/./b"), [RedundantCurrentDir]);
+        assert_eq!(kinds("a//b"), [DoubleSlash]);
+        assert_eq!(kinds("a/"), [TrailingSlash]);
+        assert_eq!(kinds("a\\b"), [Backslash]);
+        assert_eq!(kinds("//a"), [DoubleSlash]);
+        assert_eq!(kinds("./"), [TrailingSlash]);
+    }
+
+    #[test]
+    fn test_several_lints() {
+        use PathLintKind::*;
+
+        let path = "./src//utils\\./mod.rs/";
+        let lints = lint_path(path);
+        assert_eq!(
+            lints.iter().map(|lint| lint.kind).collect::<Vec<_>>(),
+            [
+                RedundantCurrentDir,
+                DoubleSlash,
+                Backslash,
+                RedundantCurrentDir,
+                TrailingSlash
+            ]
+        );
+        assert_eq!(lints[0].span, 0..2);
+        assert_eq!(lints[1].span, 5..7);
+        assert_eq!(lints[2].span, 12..13);
+        assert_eq!(lints[3].span, 13..15);
+        assert_eq!(lints[4].span, 21..22);
+        assert_eq!(&path[lints[2].span.clone()], "\\");
+
+        assert_eq!(apply(path, &lints), "src/utils/mod.rs");
+    }
+
+    #[test]
+    fn test_fixes_produce_tidy_form() {
+        for (path, tidy) in [
+            ("./a", "a"),
+            ("a/.", "a"),
+            ("a/./", "a"),
+            ("/./a", "/a"),
+            ("/.", "/"),
+            ("//", "/"),
+            ("./.", "."),
+            ("./", "."),
+            (".\\a\\\\b\\", "a/b"),
+            ("a/././b", "a/b"),
+            ("a\\/b", "a/b"),
+            ("C:\\dir", "C:/dir"),
+        ] {
+            assert_eq!(apply(path, &lint_path(path)), tidy, "fixing {:?}", path);
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Compile-time checks for path literals
+//!
+//! A `const fn` subset of the default validation rules, so hardcoded paths can
+//! be checked by the compiler instead of at run time.
+
+use crate::reserved::is_reserved_basename_in;
+use crate::validate::FORBIDDEN_CHARS;
+
+/// Check a path with the subset of the default rules that can run in a `const` context
+///
+/// Rejects the empty path, `..` components, NUL and other control characters
+/// (except newline and tab, as in [`validate_path`](crate::validate_path)),
+/// the forbidden characters `< > | ? * "`, and components that name a Windows
+/// device (see [`is_reserved_basename`](crate::is_reserved_basename)). Every
+/// path this rejects is also rejected by
+/// [`validate_path`](crate::validate_path), but the reverse does not hold:
+/// length limits and the Unicode checks are not applied. This is the check
+/// behind [`safe_path!`](crate::safe_path).
+///
+/// # Examples
+/// ```
+/// use path_utils::is_safe_path_literal;
+///
+/// const OK: bool = is_safe_path_literal("src/main.rs");
+/// assert!(OK);
+/// assert!(!is_safe_path_literal("../etc/passwd"));
+/// ```
+pub const fn is_safe_path_literal(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    if bytes.is_empty() {
+        return false;
+    }
+
+    let mut component_start = 0;
+    let mut index = 0;
+    while index < bytes.len() {
+        let byte = bytes[index];
+        if byte == b'/' || byte == b'\\' {
+            if is_unsafe_component(bytes, component_start, index) {
+                return false;
+            }
+            component_start = index + 1;
+        }
+        if (byte < 0x20 && byte != b'\n' && byte != b'\t') || byte == 0x7F {
+            return false;
+        }
+        // C1 control characters, U+0080 to U+009F, encoded as 0xC2 0x80..=0x9F
+        if byte == 0xC2 && index + 1 < bytes.len() && matches!(bytes[index + 1], 0x80..=0x9F) {
+            return false;
+        }
+
+        let mut forbidden = 0;
+        while forbidden < FORBIDDEN_CHARS.len() {
+            if byte as u32 == FORBIDDEN_CHARS[forbidden] as u32 {
+                return false;
+            }
+            forbidden += 1;
+        }
+        index += 1;
+    }
+    !is_unsafe_component(bytes, component_start, bytes.len())
+}
+
+/// Check whether `bytes[start..end]` is traversal or names a Windows device
+const fn is_unsafe_component(bytes: &[u8], start: usize, end: usize) -> bool {
+    is_traversal_component(bytes, start, end) || is_reserved_basename_in(bytes, start, end)
+}
+
+/// Check whether `bytes[start..end]` is `..`, possibly followed by dots and spaces
+///
+/// The same rule [`validate_path`](crate::validate_path) applies by default,
+/// since Windows strips the trailing dots and spaces.
+const fn is_traversal_component(bytes: &[u8], start: usize, end: usize) -> bool {
+    if end - start < 2 || bytes[start] != b'.' || bytes[start + 1] != b'.' {
+        return false;
+    }
+
+    let mut index = start + 2;
+    while index < end {
+        if bytes[index] != b'.' && bytes[index] != b' ' {
+            return false;
+        }
+        index += 1;
+    }
+    true
+}
+
+/// Validate a path literal at compile time
+///
+/// Expands to the literal itself, as a `&'static str`, after checking it with
+/// [`is_safe_path_literal`] in a `const` context, so an unsafe literal fails the
+/// build rather than a test. Only the rules that function applies are checked.
+///
+/// # Examples
+/// ```
+/// use path_utils::safe_path;
+///
+/// let config: &'static str = safe_path!("config/settings.toml");
+/// assert_eq!(config, "config/settings.toml");
+/// ```
+///
+/// Unsafe literals don't compile:
+///
+/// ```compile_fail
+/// let secret = path_utils::safe_path!("../etc/passwd");
+/// ```
+///
+/// ```compile_fail
+/// let pipe = path_utils::safe_path!("logs/a|b.txt");
+/// ```
+///
+/// ```compile_fail
+/// let device = path_utils::safe_path!("out/CON.txt");
+/// ```
+#[macro_export]
+macro_rules! safe_path {
+    ($path:literal) => {{
+        const PATH: &str = $path;
+        const _: () = assert!(
+            $crate::is_safe_path_literal(PATH),
+            "path literal failed validation"
+        );
+        PATH
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::validate_path;
+
+    #[test]
+    fn test_is_safe_path_literal() {
+        for path in [
+            "src/main.rs",
+            "a/.hidden",
+            "C:/x",
+            "line\nbreak",
+            "café.txt",
+            "CONSOLE/com10.txt",
+            "v1..2/a..b",
+        ] {
+            assert!(is_safe_path_literal(path), "{:?} should pass", path);
+        }
+
+        for path in [
+            "",
+            "../etc/passwd",
+            "a/..",
+            "a\\...\\b",
+            ".. /x",
+            "a\0b",
+            "a\u{1}b",
+            "a\u{7F}b",
+            "a\u{85}b",
+            "a<b",
+            "what?",
+            "quote\"d",
+            "CON",
+            "dir/nul.txt",
+            "lpt1\\x",
+            "a/Aux. /b",
+        ] {
+            assert!(!is_safe_path_literal(path), "{:?} should fail", path);
+        }
+    }
+
+    #[test]
+    fn test_is_safe_path_literal_agrees_with_validate_path() {
+        // Anything the const check rejects, full validation rejects too
+        for path in [
+            "", "..", "a/...", "a\0", "a\u{9F}", "a*", "a>b", "x/../y", "com1/x",
+        ] {
+            assert!(!is_safe_path_literal(path));
+            assert!(validate_path(path).is_err(), "{:?}", path);
+        }
+    }
+
+    #[test]
+    fn test_safe_path_macro() {
+        const README: &str = safe_path!("docs/README.md");
+        assert_eq!(README, "docs/README.md");
+        assert_eq!(safe_path!("src/lib.rs"), "src/lib.rs");
+    }
+}
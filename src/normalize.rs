@@ -2,7 +2,18 @@
 //!
 //! This module provides robust path manipulation functions with security as a primary concern.
 
+use crate::classify::{classify, PathKind};
 use crate::error::{PathError, Result};
+use crate::filename::{sanitize_filename, PLACEHOLDER_NAME};
+use crate::options::{NormalizeOptions, ValidationOptions};
+use crate::relative::{split_root, PathRoot};
+use crate::reserved::is_dots_and_whitespace;
+use crate::validate::{check_contents, has_traversal, validate_path};
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
 use std::path::{Path, PathBuf};
 
 /// Normalize a path string for cross-platform compatibility and consistency
@@ -12,6 +23,8 @@ use std::path::{Path, PathBuf};
 /// - Removes double slashes
 /// - Removes empty path components
 /// - Ensures consistent forward-slash separators
+/// - With the `unicode-normalization` feature, composes the text into NFC
+///   (see [`normalize_unicode`](crate::normalize_unicode))
 ///
 /// This is the canonical normalization function for all string-based path operations.
 ///
@@ -24,11 +37,394 @@ use std::path::{Path, PathBuf};
 /// assert_eq!(normalize_path_str("a//b//c"), "a/b/c");
 /// ```
 pub fn normalize_path_str(path: &str) -> String {
+    normalize_path_cow(path).into_owned()
+}
+
+/// Normalize a path string, borrowing the input when it is already normalized
+///
+/// Produces exactly the same result as [`normalize_path_str`], but only allocates
+/// when a transformation is actually needed. Input without backslashes, double
+/// slashes, or leading/trailing slashes is returned as `Cow::Borrowed`, which
+/// makes this the better choice for hot paths over mostly-clean input.
+///
+/// # Examples
+/// ```
+/// use path_utils::normalize_path_cow;
+/// use std::borrow::Cow;
+///
+/// assert!(matches!(normalize_path_cow("src/main.rs"), Cow::Borrowed("src/main.rs")));
+/// assert_eq!(normalize_path_cow("src\\main.rs"), "src/main.rs");
+/// ```
+pub fn normalize_path_cow(path: &str) -> Cow<'_, str> {
+    #[cfg(feature = "unicode-normalization")]
+    if !unicode_normalization::is_nfc(path) {
+        return Cow::Owned(normalize_unicode(&normalize_separators(path)));
+    }
+
+    if is_normalized(path) {
+        return Cow::Borrowed(path);
+    }
+
+    Cow::Owned(normalize_separators(path))
+}
+
+/// Normalize a path string into a caller-supplied buffer
+///
+/// Clears `buf` and writes the same result [`normalize_path_str`] would return.
+/// The buffer keeps its capacity, so reusing one `String` across many calls
+/// avoids allocating for each path once it has grown large enough.
+///
+/// # Examples
+/// ```
+/// use path_utils::normalize_into;
+///
+/// let mut buf = String::new();
+/// for (path, expected) in [("a//b", "a/b"), ("\\x\\y\\", "x/y")] {
+///     normalize_into(path, &mut buf);
+///     assert_eq!(buf, expected);
+/// }
+/// ```
+pub fn normalize_into(path: &str, buf: &mut String) {
+    buf.clear();
+
+    #[cfg(feature = "unicode-normalization")]
+    if !unicode_normalization::is_nfc(path) {
+        use unicode_normalization::UnicodeNormalization;
+
+        buf.extend(normalize_separators(path).nfc());
+        return;
+    }
+
+    buf.reserve(path.len());
+    push_normalized_separators(buf, path);
+}
+
+/// Join the normalized components of a path with forward slashes
+///
+/// A single scan over the bytes into one pre-sized buffer: each run of text
+/// between separators is copied in as a whole, preceded by a `/` unless it is
+/// the first, so repeated, leading and trailing separators never reach the
+/// output. Both separators are ASCII, so every slice falls on a char boundary.
+fn normalize_separators(path: &str) -> String {
+    let mut normalized = String::with_capacity(path.len());
+    push_normalized_separators(&mut normalized, path);
+    normalized
+}
+
+/// Append the separator-normalized form of `path` to an empty buffer
+fn push_normalized_separators(normalized: &mut String, path: &str) {
+    let mut start = 0;
+    for (index, &byte) in path.as_bytes().iter().enumerate() {
+        if byte == b'/' || byte == b'\\' {
+            push_component(normalized, &path[start..index]);
+            start = index + 1;
+        }
+    }
+    push_component(normalized, &path[start..]);
+}
+
+/// Append a component to a normalized path, skipping empty ones
+fn push_component(normalized: &mut String, component: &str) {
+    if component.is_empty() {
+        return;
+    }
+    if !normalized.is_empty() {
+        normalized.push('/');
+    }
+    normalized.push_str(component);
+}
+
+/// Compose a path into Unicode Normalization Form C (requires the `unicode-normalization` feature)
+///
+/// The same name can be spelled with different code points: `é` as the single
+/// U+00E9, or as `e` followed by the combining accent U+0301. Both render
+/// identically, but filesystems such as ext4 treat them as different names.
+/// NFC picks the composed spelling, so visually identical names compare equal.
+/// Separators are left alone; only the text within components changes.
+///
+/// With the feature enabled, [`normalize_path_str`] and [`normalize_path_cow`]
+/// apply this as well. [`normalized_components`] cannot, since it borrows
+/// from the input.
+///
+/// # Examples
+/// ```
+/// use path_utils::normalize_unicode;
+///
+/// assert_eq!(normalize_unicode("cafe\u{301}.txt"), "caf\u{e9}.txt");
+/// ```
+#[cfg(feature = "unicode-normalization")]
+pub fn normalize_unicode(path: &str) -> String {
+    use unicode_normalization::UnicodeNormalization;
+
+    path.nfc().collect()
+}
+
+/// Iterate over the components of a path as [`normalize_path_str`] would produce them
+///
+/// Yields the same sequence as `normalize_path_str(path).split('/')` (nothing at
+/// all for a path with no components), without allocating. Converting
+/// backslashes only ever changes separators, never the text of a component, so
+/// splitting on both `/` and `\` yields slices of the input directly: mixed
+/// separators never need an owned fallback.
+///
+/// # Examples
+/// ```
+/// use path_utils::normalized_components;
+///
+/// let components: Vec<&str> = normalized_components("/src\\bin//main.rs").collect();
+/// assert_eq!(components, ["src", "bin", "main.rs"]);
+/// assert_eq!(normalized_components("//").count(), 0);
+/// ```
+pub fn normalized_components(path: &str) -> impl Iterator<Item = &str> {
+    path.split(['/', '\\']).filter(|s| !s.is_empty())
+}
+
+/// Check in a single scan whether `path` is already in normalized form
+fn is_normalized(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    if bytes.first() == Some(&b'/') || bytes.last() == Some(&b'/') {
+        return false;
+    }
+
+    let mut previous = 0u8;
+    for &byte in bytes {
+        if byte == b'\\' || (byte == b'/' && previous == b'/') {
+            return false;
+        }
+        previous = byte;
+    }
+    true
+}
+
+/// Normalize a path string with configurable behavior
+///
+/// Performs the same normalization as [`normalize_path_str`], then applies any
+/// additional transformations enabled in `options`.
+///
+/// # Examples
+/// ```
+/// use path_utils::{normalize_path_str_with, NormalizeOptions};
+///
+/// let options = NormalizeOptions::new().lowercase_ascii(true);
+/// assert_eq!(normalize_path_str_with("FOO\\BAR.TXT", &options), "foo/bar.txt");
+/// assert_eq!(normalize_path_str_with("FOO\\BAR.TXT", &NormalizeOptions::new()), "FOO/BAR.TXT");
+///
+/// let options = NormalizeOptions::new().preserve_trailing_slash(true);
+/// assert_eq!(normalize_path_str_with("a//b//", &options), "a/b/");
+/// assert_eq!(normalize_path_str_with("a//b", &options), "a/b");
+///
+/// let options = NormalizeOptions::new().strip_current_dir(true);
+/// assert_eq!(normalize_path_str_with("./a/./b", &options), "a/b");
+/// ```
+pub fn normalize_path_str_with(path: &str, options: &NormalizeOptions) -> String {
+    let normalized = normalize_path_cow(path);
+    let mut normalized = if options.lowercase_ascii {
+        normalized.to_ascii_lowercase()
+    } else {
+        normalized.into_owned()
+    };
+
+    if options.strip_current_dir && normalized.split('/').any(|component| component == ".") {
+        normalized = normalized
+            .split('/')
+            .filter(|component| *component != ".")
+            .collect::<Vec<_>>()
+            .join("/");
+    }
+
+    if options.preserve_trailing_slash && !normalized.is_empty() && path.ends_with(['/', '\\']) {
+        normalized.push('/');
+    }
+    normalized
+}
+
+/// Normalize a path string and report whether it was absolute, in one pass
+///
+/// The returned string is exactly what [`normalize_path_str`] produces, so it is
+/// relativized the same way the sanitizer does it: leading separators are
+/// dropped. The flag records whether the original was absolute, meaning it
+/// started with `/` or `\`, or with a drive prefix followed by a separator
+/// (`C:\`). A drive prefix stays in the string as its first component, and a
+/// drive-relative path such as `C:foo` is not considered absolute.
+///
+/// # Examples
+/// ```
+/// use path_utils::normalize_with_absoluteness;
+///
+/// assert_eq!(normalize_with_absoluteness("/usr//lib/"), ("usr/lib".to_string(), true));
+/// assert_eq!(normalize_with_absoluteness("src\\main.rs"), ("src/main.rs".to_string(), false));
+/// assert_eq!(normalize_with_absoluteness("C:\\Windows"), ("C:/Windows".to_string(), true));
+/// ```
+pub fn normalize_with_absoluteness(path: &str) -> (String, bool) {
+    (normalize_path_str(path), is_absolute_str(path))
+}
+
+/// Whether a path starts at the root or with a drive prefix and a separator
+fn is_absolute_str(path: &str) -> bool {
+    path.starts_with(['/', '\\']) || (has_drive_letter(path) && path[2..].starts_with(['/', '\\']))
+}
+
+/// Normalize a path string, keeping it absolute if it was absolute
+///
+/// Separators are normalized and empty components removed exactly as in
+/// [`normalize_path_str`], but a path that started at the root keeps a single
+/// leading `/`, and a drive-absolute path keeps its drive prefix followed by
+/// `/`. Relative and drive-relative paths (`C:foo`) are normalized as usual.
+/// Use this for general-purpose normalization; the sanitizing functions
+/// deliberately drop the root instead.
+///
+/// # Examples
+/// ```
+/// use path_utils::normalize_path_str_preserving_root;
+///
+/// assert_eq!(normalize_path_str_preserving_root("/a//b"), "/a/b");
+/// assert_eq!(normalize_path_str_preserving_root("a//b"), "a/b");
+/// assert_eq!(normalize_path_str_preserving_root("C:\\Windows\\"), "C:/Windows");
+/// ```
+pub fn normalize_path_str_preserving_root(path: &str) -> String {
+    let (mut normalized, absolute) = normalize_with_absoluteness(path);
+    if !absolute {
+        return normalized;
+    }
+
+    if has_drive_letter(path) {
+        // The drive is already the first component; only a bare `C:` needs its root back
+        if normalized.len() == 2 {
+            normalized.push('/');
+        }
+        normalized
+    } else {
+        format!("/{}", normalized)
+    }
+}
+
+/// Count the components of a path after normalization
+///
+/// Empty components from leading, trailing or repeated separators don't count,
+/// so `"/a//b/"` has depth 2. Like [`normalize_path_str`], this is purely
+/// lexical: `.` and `..` are counted as ordinary components.
+///
+/// # Examples
+/// ```
+/// use path_utils::path_depth;
+///
+/// assert_eq!(path_depth("a/b/c.txt"), 3);
+/// assert_eq!(path_depth("/a//b/"), 2);
+/// assert_eq!(path_depth(""), 0);
+/// ```
+pub fn path_depth(path: &str) -> usize {
+    normalized_components(path).count()
+}
+
+/// Measure how far a path's `..` components reach above its starting point
+///
+/// Walks the components keeping a running depth: a name goes one level down,
+/// `..` one level up and `.` stays put. The result is the lowest depth reached,
+/// so `0` means the path never leaves its starting directory and `-3` means it
+/// climbs three levels above it, even if later names descend again. A leading
+/// root is ignored, so the result describes the path as if it were relative.
+///
+/// This separates a harmless `a/../b` from a deliberate `../../../../etc`
+/// when logging rejected paths.
+///
+/// # Examples
+/// ```
+/// use path_utils::relative_depth_escape;
+///
+/// assert_eq!(relative_depth_escape("a/b/../c"), 0);
+/// assert_eq!(relative_depth_escape("a/../../x"), -1);
+/// assert_eq!(relative_depth_escape("../../../../etc/passwd"), -4);
+/// ```
+pub fn relative_depth_escape(path: &str) -> i32 {
+    let mut depth = 0i32;
+    let mut lowest = 0;
+    for component in normalized_components(path) {
+        match component {
+            "." => {}
+            ".." => {
+                depth = depth.saturating_sub(1);
+                lowest = lowest.min(depth);
+            }
+            _ => depth = depth.saturating_add(1),
+        }
+    }
+    lowest
+}
+
+/// Convert a forward-slash path to the host platform's separator
+///
+/// Replaces every `/` with [`std::path::MAIN_SEPARATOR`], for handing a
+/// normalized path to a native API that expects backslashes on Windows. On
+/// Unix the separator is already `/`, so the path is returned unchanged. No
+/// other normalization is performed.
+///
+/// # Examples
+/// ```
+/// use path_utils::to_native_separators;
+///
+/// let native = to_native_separators("src/main.rs");
+/// if cfg!(windows) {
+///     assert_eq!(native, "src\\main.rs");
+/// } else {
+///     assert_eq!(native, "src/main.rs");
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub fn to_native_separators(path: &str) -> String {
+    if std::path::MAIN_SEPARATOR == '/' {
+        path.to_string()
+    } else {
+        path.replace('/', std::path::MAIN_SEPARATOR_STR)
+    }
+}
+
+/// Convert every backslash in a path to a forward slash
+///
+/// The inverse of [`to_native_separators`](crate::to_native_separators), and
+/// the same on every platform. Unlike [`normalize_path_str`], empty components
+/// and leading or trailing separators are kept, so the result has the same
+/// shape as the input.
+///
+/// # Examples
+/// ```
+/// use path_utils::to_forward_slashes;
+///
+/// assert_eq!(to_forward_slashes("C:\\Users\\me\\"), "C:/Users/me/");
+/// assert_eq!(to_forward_slashes("a//b"), "a//b");
+/// ```
+pub fn to_forward_slashes(path: &str) -> String {
     path.replace('\\', "/")
-        .split('/')
-        .filter(|s| !s.is_empty())
-        .collect::<Vec<_>>()
-        .join("/")
+}
+
+/// Collapse separators without otherwise normalizing a path
+///
+/// Backslashes become forward slashes and each run of separators becomes a
+/// single `/`. Unlike [`normalize_path_str`], nothing else changes: a leading
+/// or trailing separator is kept as one `/`, and `.` and `..` components are
+/// left for later processing, such as the traversal checks. A UNC prefix
+/// (`\\server`) collapses to a single `/` like any other run, so classify the
+/// path first if that distinction matters. To drop the trailing slash, trim it
+/// from the result.
+///
+/// # Examples
+/// ```
+/// use path_utils::trim_redundant_separators;
+///
+/// assert_eq!(trim_redundant_separators("//a/..//b\\c/"), "/a/../b/c/");
+/// assert_eq!(trim_redundant_separators("./x\\\\y"), "./x/y");
+/// ```
+pub fn trim_redundant_separators(path: &str) -> String {
+    let mut trimmed = String::with_capacity(path.len());
+    for c in path.chars() {
+        if c == '/' || c == '\\' {
+            if !trimmed.ends_with('/') {
+                trimmed.push('/');
+            }
+        } else {
+            trimmed.push(c);
+        }
+    }
+    trimmed
 }
 
 /// Normalize a PathBuf to a consistent format
@@ -48,6 +444,7 @@ pub fn normalize_path_str(path: &str) -> String {
 /// assert_eq!(normalize_path_buf("a//b"), PathBuf::from("a/b"));
 /// assert_eq!(normalize_path_buf("a\\b"), PathBuf::from("a/b"));
 /// ```
+#[cfg(feature = "std")]
 pub fn normalize_path_buf<P: AsRef<Path>>(path: P) -> PathBuf {
     let path_str = path.as_ref().to_string_lossy();
     let normalized_str = normalize_path_str(&path_str);
@@ -69,6 +466,7 @@ pub fn normalize_path_buf<P: AsRef<Path>>(path: P) -> PathBuf {
 /// let result = join_and_normalize(&base, &file);
 /// assert_eq!(result, PathBuf::from("source/main.rs"));
 /// ```
+#[cfg(feature = "std")]
 pub fn join_and_normalize<P1: AsRef<Path>, P2: AsRef<Path>>(base: P1, path: P2) -> PathBuf {
     let base_str = base.as_ref().to_string_lossy();
     let path_str = path.as_ref().to_string_lossy();
@@ -86,6 +484,69 @@ pub fn join_and_normalize<P1: AsRef<Path>, P2: AsRef<Path>>(base: P1, path: P2)
     }
 }
 
+/// Join any number of path segments and normalize the result
+///
+/// Equivalent to folding the segments with [`join_and_normalize`]: stray
+/// separators between segments are dropped, empty segments are skipped, and
+/// an empty iterator yields an empty path. Like [`normalize_path_str`], the
+/// result is relative. No validation is done; use [`try_join_all`] for
+/// untrusted segments.
+///
+/// # Examples
+/// ```
+/// use path_utils::join_all;
+/// use std::path::PathBuf;
+///
+/// assert_eq!(join_all(["a/", "/b", "c"]), PathBuf::from("a/b/c"));
+/// assert_eq!(join_all(Vec::<&str>::new()), PathBuf::new());
+/// ```
+#[cfg(feature = "std")]
+pub fn join_all<I, S>(segments: I) -> PathBuf
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut joined = String::new();
+    for segment in segments {
+        for component in normalized_components(segment.as_ref()) {
+            if !joined.is_empty() {
+                joined.push('/');
+            }
+            joined.push_str(component);
+        }
+    }
+    PathBuf::from(joined)
+}
+
+/// Join untrusted path segments, sanitizing each one
+///
+/// Each segment is run through [`sanitize_directory_file_path`] before
+/// joining, so the first segment that is empty, traverses, or breaks a
+/// validation rule fails the whole join with that error. An empty iterator
+/// yields an empty path.
+///
+/// # Examples
+/// ```
+/// use path_utils::try_join_all;
+/// use std::path::PathBuf;
+///
+/// assert_eq!(try_join_all(["uploads/", "/2024", "a.png"])?, PathBuf::from("uploads/2024/a.png"));
+/// assert!(try_join_all(["uploads", "../etc"]).is_err());
+/// # Ok::<(), path_utils::PathError>(())
+/// ```
+#[cfg(feature = "std")]
+pub fn try_join_all<I, S>(segments: I) -> Result<PathBuf>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let sanitized = segments
+        .into_iter()
+        .map(|segment| sanitize_directory_file_path(segment.as_ref()))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(PathBuf::from(sanitized.join("/")))
+}
+
 /// Sanitize a directory file path extracted from patch content
 ///
 /// This function is specifically designed for directory content parsing where
@@ -94,6 +555,7 @@ pub fn join_and_normalize<P1: AsRef<Path>, P2: AsRef<Path>>(base: P1, path: P2)
 ///
 /// # Security
 /// - Prevents path traversal attacks by validating path components
+/// - Rejects UNC paths (`\\server\share` or `//server/share`)
 /// - Ensures paths are relative to repository root
 /// - Cross-platform path normalization
 ///
@@ -110,16 +572,72 @@ pub fn join_and_normalize<P1: AsRef<Path>, P2: AsRef<Path>>(base: P1, path: P2)
 /// assert_eq!(result, "lib/generator.js");
 /// ```
 pub fn sanitize_directory_file_path(path: &str) -> Result<String> {
+    sanitize_directory_file_path_with(path, &ValidationOptions::default())
+}
+
+/// Sanitize a directory file path given as any path-like value
+///
+/// The [`sanitize_directory_file_path`] counterpart for callers holding a
+/// `Path` or `PathBuf`. The path is converted with `to_string_lossy`, like the
+/// other `AsRef<Path>` functions, so bytes that aren't valid UTF-8 become
+/// U+FFFD and the result may no longer name the original file. Use
+/// [`is_safe_os_path`](crate::is_safe_os_path) to check such paths exactly.
+///
+/// # Examples
+/// ```
+/// use path_utils::sanitize_directory_file_path_p;
+/// use std::path::Path;
+///
+/// let result = sanitize_directory_file_path_p(Path::new("/lib/generator.js")).unwrap();
+/// assert_eq!(result, "lib/generator.js");
+/// ```
+#[cfg(feature = "std")]
+pub fn sanitize_directory_file_path_p<P: AsRef<Path>>(path: P) -> Result<String> {
+    sanitize_directory_file_path(&path.as_ref().to_string_lossy())
+}
+
+/// Sanitize a directory file path against a configurable validation policy
+///
+/// Performs the same conversion as [`sanitize_directory_file_path`], applying
+/// the character, length and reserved-name rules from `options`. Traversal
+/// rejection always applies; drive-letter and UNC rejection apply unless the
+/// [`target_platform`](ValidationOptions::target_platform) is Unix. With a
+/// [`strip_absolute_prefix`](ValidationOptions::strip_absolute_prefix), a known
+/// mount point is removed from absolute paths instead of just the leading `/`.
+///
+/// # Examples
+/// ```
+/// use path_utils::{sanitize_directory_file_path_with, ValidationOptions};
+///
+/// let options = ValidationOptions::new().allow_reserved_names(true);
+/// assert_eq!(sanitize_directory_file_path_with("/logs/CON", &options).unwrap(), "logs/CON");
+/// assert!(sanitize_directory_file_path_with("../logs/CON", &options).is_err());
+///
+/// let options = ValidationOptions::new().strip_absolute_prefix(Some("/app"));
+/// assert_eq!(sanitize_directory_file_path_with("/app/src/x", &options).unwrap(), "src/x");
+/// ```
+pub fn sanitize_directory_file_path_with(
+    path: &str,
+    options: &ValidationOptions,
+) -> Result<String> {
     // Handle empty paths
     if path.trim().is_empty() {
         return Err(PathError::EmptyPath);
     }
 
+    // Security: A UNC path names a share on another machine, which stripping
+    // the leading separators would disguise as an innocent relative path
+    if options.windows_rules() && classify(path) == PathKind::Unc {
+        return Err(PathError::UncPath {
+            path: path.to_string(),
+        });
+    }
+
     // Normalize the path first (handles backslashes, double slashes)
     let normalized = normalize_path_str(path);
 
     // Security: Prevent path traversal attacks
-    if normalized.contains("..") {
+    if has_traversal(&normalized, options) {
         return Err(PathError::PathTraversal {
             path: path.to_string(),
         });
@@ -133,54 +651,128 @@ pub fn sanitize_directory_file_path(path: &str) -> Result<String> {
         normalized
     };
 
-    // Windows drive letters are also considered absolute
-    if cfg!(windows) && normalized.len() > 1 && normalized.chars().nth(1) == Some(':') {
+    // A known mount point comes off absolute paths before anything else
+    let normalized = match &options.strip_absolute_prefix {
+        Some(prefix) if is_absolute_str(path) => {
+            strip_absolute_prefix(path, normalized, prefix, options)?
+        }
+        _ => normalized,
+    };
+
+    // Windows drive letters are also considered absolute. This is checked on every
+    // host, since the result must be safe to write wherever the repository lives
+    if options.windows_rules() && has_drive_letter(&normalized) {
         return Err(PathError::DriveLetterPath {
             path: path.to_string(),
         });
     }
 
-    // Security: Reject null bytes and control characters
-    if normalized.contains('\0')
-        || normalized
-            .chars()
-            .any(|c| c.is_control() && c != '\n' && c != '\t')
-    {
-        return Err(PathError::InvalidCharacters {
-            path: path.to_string(),
-        });
-    }
+    // Security: Apply the same character and filename rules as validation,
+    // reporting the original input in any error
+    check_contents(&normalized, path, options)?;
 
-    // Security: Reject paths that would be problematic on Windows
-    // This ensures cross-platform compatibility
-    for invalid_char in ['<', '>', '|', '?', '*', '"'] {
-        if normalized.contains(invalid_char) {
-            return Err(PathError::InvalidCharacters {
-                path: path.to_string(),
-            });
+    Ok(normalized)
+}
+
+/// Remove `prefix` from the normalized form of the absolute path `path`
+///
+/// Falls back to `normalized` unchanged, or an error, when the prefix doesn't match.
+fn strip_absolute_prefix(
+    path: &str,
+    normalized: String,
+    prefix: &str,
+    options: &ValidationOptions,
+) -> Result<String> {
+    let mut components = normalized.split('/');
+    if normalized_components(prefix).all(|expected| components.next() == Some(expected)) {
+        let rest = components.collect::<Vec<_>>().join("/");
+        if rest.is_empty() {
+            return Err(PathError::EmptyPath);
         }
+        Ok(rest)
+    } else if options.reject_unmatched_absolute {
+        Err(PathError::validation_failed(
+            "absolute_prefix",
+            format!("{} is not under {}", path, prefix),
+        ))
+    } else {
+        Ok(normalized)
     }
+}
 
-    // Security: Reject reserved Windows filenames (case-insensitive)
-    let reserved_names = [
-        "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
-        "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
-    ];
-
-    // Check each path component
-    for component in normalized.split('/') {
-        let component_upper = component.to_uppercase();
-        // Check base name without extension
-        let base_name = component_upper.split('.').next().unwrap_or("");
-        if reserved_names.contains(&base_name) {
-            return Err(PathError::ReservedFilename {
-                filename: component.to_string(),
-                path: path.to_string(),
-            });
+/// Repair a directory file path into a usable relative path, never failing
+///
+/// A best-effort alternative to [`sanitize_directory_file_path`] for bulk
+/// imports and display, where one bad entry shouldn't stop the batch. Instead
+/// of rejecting problems it removes or replaces them:
+///
+/// - Leading separators and a leading drive prefix are dropped
+/// - Empty, `.`, `..` and dots-and-whitespace components are skipped entirely
+/// - Each remaining component is repaired with
+///   [`sanitize_filename`](crate::sanitize_filename), replacing forbidden
+///   characters with `_`, trimming trailing dots and renaming reserved names
+/// - If nothing is left, the result is `unnamed`
+///
+/// Skipping `..` means the result can name a different file than the input
+/// intended, so this is **not** a security boundary: use
+/// [`sanitize_directory_file_path`] where rejecting bad input matters. The
+/// result does always pass it.
+///
+/// # Examples
+/// ```
+/// use path_utils::sanitize_directory_file_path_lossy;
+///
+/// assert_eq!(sanitize_directory_file_path_lossy("../../etc/passwd"), "etc/passwd");
+/// assert_eq!(sanitize_directory_file_path_lossy("/docs/file<>.txt"), "docs/file__.txt");
+/// assert_eq!(sanitize_directory_file_path_lossy(".."), "unnamed");
+/// ```
+pub fn sanitize_directory_file_path_lossy(path: &str) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    for component in normalized_components(path) {
+        let component = if parts.is_empty() && has_drive_letter(component) {
+            &component[2..]
+        } else {
+            component
+        };
+        if component.is_empty() || component == "." || is_dots_and_whitespace(component) {
+            continue;
         }
+        parts.push(sanitize_filename(component, '_'));
     }
 
-    Ok(normalized)
+    if parts.is_empty() {
+        return PLACEHOLDER_NAME.to_string();
+    }
+    parts.join("/")
+}
+
+/// Remove a leading Windows drive prefix, keeping the rest as a relative path
+///
+/// If `path` starts with a drive prefix such as `C:`, returns the remainder
+/// normalized like [`normalize_path_str`], so the separator after the prefix is
+/// dropped too. Returns `None` for any other input. This is a deliberate
+/// opt-in for import tools that want the tail of a path that
+/// [`sanitize_directory_file_path`] would reject; the result is not validated.
+///
+/// # Examples
+/// ```
+/// use path_utils::strip_drive_letter;
+///
+/// assert_eq!(strip_drive_letter("C:\\Users\\x"), Some("Users/x".to_string()));
+/// assert_eq!(strip_drive_letter("Users/x"), None);
+/// ```
+pub fn strip_drive_letter(path: &str) -> Option<String> {
+    if has_drive_letter(path) {
+        Some(normalize_path_str(&path[2..]))
+    } else {
+        None
+    }
+}
+
+/// Check whether a path starts with a Windows drive prefix such as `C:`
+fn has_drive_letter(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':'
 }
 
 /// Safe repository path joining for directory content
@@ -212,6 +804,7 @@ pub fn sanitize_directory_file_path(path: &str) -> Result<String> {
 /// let result = safe_repository_join(workdir, target, file).unwrap();
 /// assert!(result.to_string_lossy().ends_with("testing/framework/args.js"));
 /// ```
+#[cfg(feature = "std")]
 pub fn safe_repository_join<P1: AsRef<Path>, P2: AsRef<Path>>(
     workdir: P1,
     target_path: P2,
@@ -221,30 +814,193 @@ pub fn safe_repository_join<P1: AsRef<Path>, P2: AsRef<Path>>(
     let sanitized_file_path = sanitize_directory_file_path(file_path)?;
 
     // Canonicalize workdir early to handle symlinks
-    let workdir_canonical = workdir
-        .as_ref()
+    let workdir_canonical = canonicalize_workdir(workdir.as_ref())?;
+
+    join_within_root(
+        &workdir_canonical,
+        target_path.as_ref(),
+        &sanitized_file_path,
+    )
+}
+
+/// Safe repository path joining without touching the filesystem
+///
+/// Performs the same sanitization and containment checks as
+/// [`safe_repository_join`], but purely lexically: the workdir is not
+/// canonicalized and doesn't need to exist, which suits planning writes before
+/// any directories are created. `.` and `..` components in the target are
+/// resolved on a component stack, and any input that would climb above the
+/// workdir is rejected, so the result is within `workdir` by construction.
+///
+/// Because nothing is resolved on disk, symlinks are **not** followed. A
+/// symlink inside the workdir can still point elsewhere once the tree exists;
+/// use [`safe_repository_join`] when that matters.
+///
+/// # Examples
+/// ```
+/// use path_utils::safe_repository_join_lexical;
+/// use std::path::Path;
+///
+/// let workdir = Path::new("/planned/repo");
+/// let result = safe_repository_join_lexical(workdir, "src/./lib", "/mod.rs").unwrap();
+/// assert_eq!(result, workdir.join("src/lib/mod.rs"));
+///
+/// assert!(safe_repository_join_lexical(workdir, "../elsewhere", "mod.rs").is_err());
+/// ```
+#[cfg(feature = "std")]
+pub fn safe_repository_join_lexical<P1: AsRef<Path>, P2: AsRef<Path>>(
+    workdir: P1,
+    target_path: P2,
+    file_path: &str,
+) -> Result<PathBuf> {
+    let workdir = workdir.as_ref();
+
+    // Sanitize the file path from directory content
+    let sanitized_file_path = sanitize_directory_file_path(file_path)?;
+
+    // Resolve . and .. in the target and file against a stack rooted at workdir
+    let target_normalized = normalize_path_str(&target_path.as_ref().to_string_lossy());
+    let components = target_normalized
+        .split('/')
+        .chain(sanitized_file_path.split('/'))
+        .filter(|component| !component.is_empty());
+    let resolved = resolve_dot_segments(components).ok_or_else(|| PathError::PathTraversal {
+        path: format!("{}/{}", target_normalized, sanitized_file_path),
+    })?;
+
+    let final_path = resolved
+        .iter()
+        .fold(workdir.to_path_buf(), |path, component| {
+            path.join(component)
+        });
+
+    // A component such as a drive prefix could still replace the base on join
+    if !final_path.starts_with(workdir) {
+        return Err(PathError::ConstructionFailed {
+            message: format!(
+                "Path construction failed - result not within workdir. Final: {:?}, Workdir: {:?}",
+                final_path, workdir
+            ),
+        });
+    }
+
+    Ok(final_path)
+}
+
+/// Safe repository path joining that refuses to pass through symlinks
+///
+/// Performs the same sanitization and containment checks as
+/// [`safe_repository_join`], then walks the result from the workdir down with
+/// [`symlink_metadata`](std::fs::symlink_metadata), rejecting the path if any
+/// component that already exists is a symlink, including the final one. Even
+/// with `..` blocked, an archive can plant a symlink to `/etc` and then write
+/// through it; this gives extractors a guarantee that every write lands in a
+/// real directory under the workdir. The walk stops at the first component
+/// that doesn't exist yet. The workdir itself may be a symlink.
+///
+/// The check is a snapshot: a symlink created after this returns is not
+/// caught, so extract into a directory nothing else writes to.
+///
+/// # Errors
+/// - [`PathError::SymlinkInPath`] naming the first symlink found
+/// - Any error [`safe_repository_join`] returns
+///
+/// # Examples
+/// ```
+/// use path_utils::safe_repository_join_no_symlinks;
+///
+/// let temp_dir = tempfile::TempDir::new()?;
+/// let result = safe_repository_join_no_symlinks(temp_dir.path(), "src", "/lib.rs")?;
+/// assert!(result.ends_with("src/lib.rs"));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[cfg(feature = "std")]
+pub fn safe_repository_join_no_symlinks<P1: AsRef<Path>, P2: AsRef<Path>>(
+    workdir: P1,
+    target_path: P2,
+    file_path: &str,
+) -> Result<PathBuf> {
+    let sanitized_file_path = sanitize_directory_file_path(file_path)?;
+    let workdir_canonical = canonicalize_workdir(workdir.as_ref())?;
+    let final_path = join_within_root(
+        &workdir_canonical,
+        target_path.as_ref(),
+        &sanitized_file_path,
+    )?;
+
+    let mut current = workdir_canonical.clone();
+    for component in final_path
+        .strip_prefix(&workdir_canonical)
+        .into_iter()
+        .flat_map(Path::components)
+    {
+        current.push(component);
+        match std::fs::symlink_metadata(&current) {
+            Ok(metadata) if metadata.file_type().is_symlink() => {
+                return Err(PathError::SymlinkInPath {
+                    path: current.to_string_lossy().into_owned(),
+                });
+            }
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => break,
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Ok(final_path)
+}
+
+/// Resolve `.` and `..` components on a stack
+///
+/// Returns `None` if a `..` would climb above the starting point.
+pub(crate) fn resolve_dot_segments<'a>(
+    components: impl IntoIterator<Item = &'a str>,
+) -> Option<Vec<&'a str>> {
+    let mut stack = Vec::new();
+    for component in components {
+        match component {
+            "." => {}
+            ".." => {
+                stack.pop()?;
+            }
+            _ => stack.push(component),
+        }
+    }
+    Some(stack)
+}
+
+/// Canonicalize a repository working directory
+#[cfg(feature = "std")]
+pub(crate) fn canonicalize_workdir(workdir: &Path) -> Result<PathBuf> {
+    workdir
         .canonicalize()
-        .map_err(|e| PathError::IoError {
-            message: format!("Cannot canonicalize workdir: {}", e),
-        })?;
+        .map_err(|e| PathError::io_error(format!("Cannot canonicalize workdir: {}", e), e.kind()))
+}
 
+/// Join a target directory and an already-sanitized file path onto a canonical root
+///
+/// Verifies that the result stays within `root_canonical`.
+#[cfg(feature = "std")]
+pub(crate) fn join_within_root(
+    root_canonical: &Path,
+    target_path: &Path,
+    sanitized_file_path: &str,
+) -> Result<PathBuf> {
     // Use standard library path operations for absolute paths to preserve leading slash
-    let target_normalized = normalize_path_buf(target_path.as_ref());
+    let target_normalized = normalize_path_buf(target_path);
     let file_normalized = PathBuf::from(sanitized_file_path);
 
     // Join canonical_workdir -> target -> file preserving absolute path
-    let final_path = workdir_canonical
-        .join(target_normalized)
-        .join(file_normalized);
+    let final_path = root_canonical.join(target_normalized).join(file_normalized);
 
     // Basic validation: ensure the constructed path has no .. components
     let relative_to_workdir =
         final_path
-            .strip_prefix(&workdir_canonical)
+            .strip_prefix(root_canonical)
             .map_err(|_| PathError::ConstructionFailed {
                 message: format!(
                 "Path construction failed - result not within workdir. Final: {:?}, Workdir: {:?}",
-                final_path, workdir_canonical
+                final_path, root_canonical
             ),
             })?;
 
@@ -259,9 +1015,88 @@ pub fn safe_repository_join<P1: AsRef<Path>, P2: AsRef<Path>>(
     Ok(final_path)
 }
 
+/// Join two untrusted path strings, validating both sides
+///
+/// Unlike [`safe_repository_join`], which trusts its workdir, this treats the
+/// base as attacker-influenced too. The base is checked with
+/// [`validate_path`] and the relative path is run through
+/// [`sanitize_directory_file_path`], so neither side may smuggle in `..` components.
+/// A leading `/` on the base is kept so an absolute base stays absolute.
+///
+/// # Examples
+/// ```
+/// use path_utils::safe_join_both_untrusted;
+///
+/// assert_eq!(
+///     safe_join_both_untrusted("/srv/uploads", "/user//avatar.png").unwrap(),
+///     "/srv/uploads/user/avatar.png"
+/// );
+///
+/// // A malicious base is rejected just like a malicious relative path
+/// assert!(safe_join_both_untrusted("uploads/../../etc", "passwd").is_err());
+/// assert!(safe_join_both_untrusted("uploads", "../etc/passwd").is_err());
+/// ```
+pub fn safe_join_both_untrusted(base: &str, relative: &str) -> Result<String> {
+    // Security: the base gets the same scrutiny as the relative path
+    validate_path(base)?;
+    let relative = sanitize_directory_file_path(relative)?;
+
+    let base_normalized = normalize_path_str(base);
+    let root = if base.starts_with(['/', '\\']) {
+        "/"
+    } else {
+        ""
+    };
+
+    if base_normalized.is_empty() {
+        Ok(format!("{}{}", root, relative))
+    } else {
+        Ok(format!("{}{}/{}", root, base_normalized, relative))
+    }
+}
+
+/// Check that joining `child` onto `base` means what it appears to mean
+///
+/// Joining a rooted child silently discards the base: `Path::new("a").join("/b")`
+/// is `/b`, and on Windows `C:\` or `\` behave the same. This returns an error
+/// whenever `child` has a root (a leading separator or a drive letter), saying
+/// whether the base was absolute or relative, so callers can pick the right
+/// tool before joining: [`sanitize_directory_file_path`] to treat the child as
+/// relative, or [`make_relative`](crate::make_relative) to express it relative to
+/// the base. Any base with a relative child is compatible.
+///
+/// # Examples
+/// ```
+/// use path_utils::check_join_compatibility;
+///
+/// assert!(check_join_compatibility("/srv/www", "index.html").is_ok());
+/// assert!(check_join_compatibility("www", "index.html").is_ok());
+/// assert!(check_join_compatibility("www", "/index.html").is_err());
+/// assert!(check_join_compatibility("/srv/www", "/index.html").is_err());
+/// ```
+pub fn check_join_compatibility(base: &str, child: &str) -> Result<()> {
+    if split_root(child).0 == PathRoot::None {
+        return Ok(());
+    }
+
+    let base_kind = if split_root(base).0 == PathRoot::None {
+        "relative"
+    } else {
+        "absolute"
+    };
+    Err(PathError::validation_failed(
+        "join_absolute_child",
+        format!(
+            "Cannot join absolute path {} onto {} base {}: the base would be discarded",
+            child, base_kind, base
+        ),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "std")]
     use tempfile::TempDir;
 
     #[test]
@@ -273,12 +1108,167 @@ mod tests {
         assert_eq!(normalize_path_str("a/./b"), "a/./b"); // Doesn't resolve . or ..
     }
 
+    #[test]
+    fn test_normalize_into() {
+        let mut buf = String::new();
+        for path in ["a//b", "\\a\\b\\", "", "/", "src/main.rs", "x//y/z/"] {
+            normalize_into(path, &mut buf);
+            assert_eq!(buf, normalize_path_str(path), "{:?}", path);
+        }
+    }
+
+    #[test]
+    fn test_normalize_into_reuses_buffer() {
+        // A dirty buffer gives the same result as a fresh one
+        let mut dirty = String::from("left over from a previous path/with/several/components");
+        let mut fresh = String::new();
+        normalize_into("a\\b", &mut dirty);
+        normalize_into("a\\b", &mut fresh);
+        assert_eq!(dirty, fresh);
+        assert_eq!(dirty, "a/b");
+
+        // Capacity is kept across calls
+        let capacity = dirty.capacity();
+        assert!(capacity >= 54);
+        normalize_into("", &mut dirty);
+        assert_eq!(dirty, "");
+        normalize_into("x/y", &mut dirty);
+        assert_eq!(dirty.capacity(), capacity);
+    }
+
+    #[test]
+    fn test_normalize_separators_matches_components() {
+        // The single-pass scanner must agree with joining the components
+        for path in [
+            "",
+            "/",
+            "\\\\",
+            "a",
+            "/a",
+            "a/",
+            "\\a\\",
+            "a//\\//b",
+            "//server\\share//x/",
+            "é/日本\\ü",
+            "C:\\Users\\me\\",
+        ] {
+            let joined = normalized_components(path).collect::<Vec<_>>().join("/");
+            assert_eq!(normalize_separators(path), joined, "{:?}", path);
+        }
+    }
+
+    #[test]
+    fn test_normalize_path_cow_borrows_clean_input() {
+        for clean in ["src/main.rs", "a", "a/b/c", "file.txt", ""] {
+            assert!(
+                matches!(normalize_path_cow(clean), Cow::Borrowed(s) if s == clean),
+                "expected borrowed result for {:?}",
+                clean
+            );
+        }
+
+        for dirty in ["a//b", "a\\b", "/a/b", "a/b/", "/"] {
+            let normalized = normalize_path_cow(dirty);
+            assert!(
+                matches!(normalized, Cow::Owned(_)),
+                "expected owned result for {:?}",
+                dirty
+            );
+            assert_eq!(normalized, normalize_path_str(dirty));
+        }
+    }
+
+    #[test]
+    fn test_normalize_path_str_with_lowercase_ascii() {
+        let lowercase = NormalizeOptions::new().lowercase_ascii(true);
+
+        assert_eq!(
+            normalize_path_str_with("FOO/BAR.TXT", &lowercase),
+            "foo/bar.txt"
+        );
+        assert_eq!(
+            normalize_path_str_with("Src\\Main.RS", &lowercase),
+            "src/main.rs"
+        );
+
+        // Non-ASCII characters are left untouched
+        assert_eq!(
+            normalize_path_str_with("DIR/ÉTÉ.TXT", &lowercase),
+            "dir/ÉtÉ.txt"
+        );
+        assert_eq!(normalize_path_str_with("ΑΒΓ/FILE", &lowercase), "ΑΒΓ/file");
+
+        // Default options leave case alone
+        assert_eq!(
+            normalize_path_str_with("FOO//BAR.TXT", &NormalizeOptions::default()),
+            "FOO/BAR.TXT"
+        );
+    }
+
+    #[test]
+    fn test_normalize_path_str_with_preserve_trailing_slash() {
+        let preserve = NormalizeOptions::new().preserve_trailing_slash(true);
+
+        assert_eq!(normalize_path_str_with("a//b//", &preserve), "a/b/");
+        assert_eq!(normalize_path_str_with("a/b", &preserve), "a/b");
+
+        // Mixed and backslash trailing separators collapse to one slash
+        assert_eq!(normalize_path_str_with("a\\b\\", &preserve), "a/b/");
+        assert_eq!(normalize_path_str_with("a/b/\\/", &preserve), "a/b/");
+        assert_eq!(normalize_path_str_with("/dir\\", &preserve), "dir/");
+
+        // Nothing to keep a slash after
+        assert_eq!(normalize_path_str_with("/", &preserve), "");
+        assert_eq!(normalize_path_str_with("", &preserve), "");
+
+        // Off by default, and combines with lowercasing
+        assert_eq!(
+            normalize_path_str_with("a//b//", &NormalizeOptions::new()),
+            "a/b"
+        );
+        let both = preserve.lowercase_ascii(true);
+        assert_eq!(normalize_path_str_with("DIR\\SUB\\", &both), "dir/sub/");
+    }
+
+    #[test]
+    fn test_normalize_path_str_with_strip_current_dir() {
+        let strip = NormalizeOptions::new().strip_current_dir(true);
+
+        // Leading, interior and trailing
+        assert_eq!(normalize_path_str_with("./a", &strip), "a");
+        assert_eq!(normalize_path_str_with("a/./b", &strip), "a/b");
+        assert_eq!(normalize_path_str_with("a/b/.", &strip), "a/b");
+        assert_eq!(normalize_path_str_with(".\\a\\.\\.\\b\\.", &strip), "a/b");
+        assert_eq!(normalize_path_str_with(".", &strip), "");
+        assert_eq!(normalize_path_str_with("././", &strip), "");
+
+        // `..` and other dot names are untouched
+        assert_eq!(normalize_path_str_with("./../a/./..", &strip), "../a/..");
+        assert_eq!(
+            normalize_path_str_with("a/.../.env/.", &strip),
+            "a/.../.env"
+        );
+
+        // Off by default
+        assert_eq!(
+            normalize_path_str_with("a/./b", &NormalizeOptions::new()),
+            "a/./b"
+        );
+
+        // Combines with the other options
+        let all = strip.preserve_trailing_slash(true).lowercase_ascii(true);
+        assert_eq!(normalize_path_str_with("./DIR/./", &all), "dir/");
+        assert_eq!(normalize_path_str_with("./.", &all), "");
+    }
+
+    #[cfg(feature = "std")]
     #[test]
     fn test_normalize_path_buf() {
         assert_eq!(normalize_path_buf("a//b"), PathBuf::from("a/b"));
         assert_eq!(normalize_path_buf("a\\b"), PathBuf::from("a/b"));
     }
 
+    #[cfg(feature = "std")]
     #[test]
     fn test_join_and_normalize() {
         assert_eq!(
@@ -299,6 +1289,46 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_join_all() {
+        assert_eq!(join_all(["a/", "/b", "c"]), PathBuf::from("a/b/c"));
+        assert_eq!(join_all(["a\\", "\\b//", "//c/"]), PathBuf::from("a/b/c"));
+        assert_eq!(join_all(["x/y//z"]), PathBuf::from("x/y/z"));
+        assert_eq!(join_all(["", "a", "/", "b"]), PathBuf::from("a/b"));
+        assert_eq!(join_all(Vec::<String>::new()), PathBuf::new());
+
+        // Matches chained join_and_normalize calls
+        assert_eq!(
+            join_all(["source/", "/main.rs"]),
+            join_and_normalize("source/", "/main.rs")
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_try_join_all() {
+        assert_eq!(
+            try_join_all(["a/", "/b", "c.txt"]).unwrap(),
+            PathBuf::from("a/b/c.txt")
+        );
+        assert_eq!(try_join_all(["single"]).unwrap(), PathBuf::from("single"));
+        assert_eq!(try_join_all(Vec::<&str>::new()).unwrap(), PathBuf::new());
+
+        assert!(matches!(
+            try_join_all(["a", "../../etc"]),
+            Err(PathError::PathTraversal { .. })
+        ));
+        assert!(matches!(
+            try_join_all(["a", "", "b"]),
+            Err(PathError::EmptyPath)
+        ));
+        assert!(matches!(
+            try_join_all(["a", "CON"]),
+            Err(PathError::ReservedFilename { .. })
+        ));
+    }
+
     #[test]
     fn test_sanitize_directory_file_path() {
         // Test absolute path conversion - this is the core bug fix
@@ -333,6 +1363,12 @@ mod tests {
         assert!(sanitize_directory_file_path("").is_err());
         assert!(sanitize_directory_file_path("   ").is_err());
 
+        // Surrounding whitespace usually means a parsing bug upstream
+        assert!(matches!(
+            sanitize_directory_file_path(" a/b.txt"),
+            Err(PathError::InvalidCharacters { .. })
+        ));
+
         // Test path traversal rejection
         assert!(sanitize_directory_file_path("../etc/passwd").is_err());
         assert!(sanitize_directory_file_path("lib/../../../etc/passwd").is_err());
@@ -363,17 +1399,323 @@ mod tests {
         assert!(sanitize_directory_file_path("con").is_err());
         assert!(sanitize_directory_file_path("Con.txt").is_err());
         assert!(sanitize_directory_file_path("lib/aux.js").is_err());
+
+        // Test reserved names hidden behind trailing dots and spaces
+        assert!(sanitize_directory_file_path("CON ").is_err());
+        assert!(sanitize_directory_file_path("CON.").is_err());
+        assert!(sanitize_directory_file_path("lib/nul...").is_err());
+        assert!(sanitize_directory_file_path("lpt1.txt.").is_err());
+
+        // Test components made only of dots and spaces
+        assert!(sanitize_directory_file_path("lib/ /main.rs").is_err());
     }
 
     #[test]
-    #[cfg(windows)]
-    fn test_sanitize_directory_file_path_windows() {
-        // Test Windows drive letter rejection
-        assert!(sanitize_directory_file_path("C:\\Windows\\System32").is_err());
-        assert!(sanitize_directory_file_path("D:/data/file.txt").is_err());
-        assert!(sanitize_directory_file_path("c:\\file.txt").is_err());
+    fn test_sanitize_directory_file_path_drive_letters() {
+        // Drive letters are rejected on every platform, not just Windows
+        for path in [
+            "C:\\Windows\\System32",
+            "D:/data/file.txt",
+            "c:\\file.txt",
+            "/C:/file.txt",
+            "z:",
+        ] {
+            assert!(
+                matches!(
+                    sanitize_directory_file_path(path),
+                    Err(PathError::DriveLetterPath { .. })
+                ),
+                "drive letter path should be rejected: {}",
+                path
+            );
+        }
+
+        // A colon elsewhere is not a drive letter, though by default it is
+        // rejected as an alternate data stream
+        let options = ValidationOptions::new().reject_ads(false);
+        assert!(sanitize_directory_file_path_with("ab:/file.txt", &options).is_ok());
+        assert!(sanitize_directory_file_path_with("1:/file.txt", &options).is_ok());
+        assert!(matches!(
+            sanitize_directory_file_path("ab:/file.txt"),
+            Err(PathError::InvalidCharacters { .. })
+        ));
+    }
+
+    #[test]
+    fn test_sanitize_directory_file_path_unc() {
+        for path in [
+            "\\\\server\\share\\file",
+            "//server/share/file",
+            "\\/server/share",
+            "\\\\?\\C:\\file.txt",
+        ] {
+            assert_eq!(
+                sanitize_directory_file_path(path),
+                Err(PathError::UncPath {
+                    path: path.to_string()
+                }),
+                "{:?} should be rejected",
+                path
+            );
+        }
+
+        // Double slashes anywhere else still collapse
+        assert_eq!(sanitize_directory_file_path("a//b").unwrap(), "a/b");
+        assert_eq!(sanitize_directory_file_path("/a//b").unwrap(), "a/b");
+        assert_eq!(sanitize_directory_file_path("a\\\\b//c").unwrap(), "a/b/c");
+    }
+
+    #[test]
+    fn test_sanitize_directory_file_path_with() {
+        let options = ValidationOptions::new()
+            .allow_reserved_names(true)
+            .extra_forbidden_chars(&['#']);
+
+        assert_eq!(
+            sanitize_directory_file_path_with("/lib/aux.js", &options).unwrap(),
+            "lib/aux.js"
+        );
+        assert!(sanitize_directory_file_path_with("lib/#1.js", &options).is_err());
+
+        // Traversal and drive letters are rejected regardless of these options
+        assert!(matches!(
+            sanitize_directory_file_path_with("../aux.js", &options),
+            Err(PathError::PathTraversal { .. })
+        ));
+        assert!(matches!(
+            sanitize_directory_file_path_with("C:\\aux.js", &options),
+            Err(PathError::DriveLetterPath { .. })
+        ));
+
+        // Errors report the original input, not the normalized form
+        assert_eq!(
+            sanitize_directory_file_path_with("\\lib\\#1.js", &options),
+            Err(PathError::InvalidCharacters {
+                path: "\\lib\\#1.js".to_string()
+            })
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_sanitize_directory_file_path_p() {
+        assert_eq!(
+            sanitize_directory_file_path_p(PathBuf::from("/src/main.rs")).unwrap(),
+            "src/main.rs"
+        );
+        assert_eq!(
+            sanitize_directory_file_path_p(Path::new("lib//a.rs")).unwrap(),
+            "lib/a.rs"
+        );
+        assert_eq!(
+            sanitize_directory_file_path_p(String::from("x\\y")).unwrap(),
+            "x/y"
+        );
+        assert!(matches!(
+            sanitize_directory_file_path_p(Path::new("../etc/passwd")),
+            Err(PathError::PathTraversal { .. })
+        ));
+
+        // The `&str` version still takes anything that derefs to `str`
+        let cow: Cow<str> = Cow::Owned("/a/b".to_string());
+        let boxed: Box<str> = "/c/d".into();
+        assert_eq!(sanitize_directory_file_path(&cow).unwrap(), "a/b");
+        assert_eq!(sanitize_directory_file_path(&boxed).unwrap(), "c/d");
+    }
+
+    #[cfg(all(unix, feature = "std"))]
+    #[test]
+    fn test_sanitize_directory_file_path_p_non_utf8_is_lossy() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = Path::new(OsStr::from_bytes(b"/dir/a\xFF.txt"));
+        assert_eq!(
+            sanitize_directory_file_path_p(path).unwrap(),
+            "dir/a\u{FFFD}.txt"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_directory_file_path_strip_absolute_prefix() {
+        let options = ValidationOptions::new().strip_absolute_prefix(Some("/app"));
+
+        // Matching prefix
+        for path in ["/app/src/x", "\\app\\src\\x", "/app//src/x/"] {
+            assert_eq!(
+                sanitize_directory_file_path_with(path, &options).unwrap(),
+                "src/x",
+                "{:?}",
+                path
+            );
+        }
+        assert!(matches!(
+            sanitize_directory_file_path_with("/app", &options),
+            Err(PathError::EmptyPath)
+        ));
+
+        // Non-matching absolute paths fall back to the blanket strip...
+        assert_eq!(
+            sanitize_directory_file_path_with("/srv/x", &options).unwrap(),
+            "srv/x"
+        );
+        assert_eq!(
+            sanitize_directory_file_path_with("/application/x", &options).unwrap(),
+            "application/x"
+        );
+
+        // ...or are rejected
+        let strict = options.clone().reject_unmatched_absolute(true);
+        let err = sanitize_directory_file_path_with("/srv/x", &strict).unwrap_err();
+        assert_eq!(err.rule(), Some("absolute_prefix"));
+        assert_eq!(
+            sanitize_directory_file_path_with("/app/src/x", &strict).unwrap(),
+            "src/x"
+        );
+
+        // Relative paths are never stripped, even under the strict policy
+        assert_eq!(
+            sanitize_directory_file_path_with("app/src/x", &strict).unwrap(),
+            "app/src/x"
+        );
+
+        // A drive prefix can be the mount point
+        let drive = ValidationOptions::new().strip_absolute_prefix(Some("C:\\app"));
+        assert_eq!(
+            sanitize_directory_file_path_with("C:\\app\\src\\x", &drive).unwrap(),
+            "src/x"
+        );
+
+        // The other checks still apply after stripping
+        assert!(sanitize_directory_file_path_with("/app/../etc", &options).is_err());
+        assert!(sanitize_directory_file_path_with("/app/CON", &options).is_err());
+    }
+
+    #[test]
+    fn test_sanitize_directory_file_path_default_blanket_strip() {
+        let options = ValidationOptions::new();
+        assert_eq!(
+            sanitize_directory_file_path_with("/app/src/x", &options).unwrap(),
+            "app/src/x"
+        );
+        // Without a prefix, the strict flag does nothing
+        let strict = options.reject_unmatched_absolute(true);
+        assert_eq!(
+            sanitize_directory_file_path_with("/app/src/x", &strict).unwrap(),
+            "app/src/x"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_directory_file_path_target_platform() {
+        use crate::options::TargetPlatform;
+
+        // On a Unix target, a drive letter is just an odd directory name and a
+        // UNC prefix just more slashes
+        let unix = ValidationOptions::new().target_platform(TargetPlatform::Unix);
+        assert_eq!(
+            sanitize_directory_file_path_with("C:/x", &unix).unwrap(),
+            "C:/x"
+        );
+        assert_eq!(
+            sanitize_directory_file_path_with("\\\\server\\share\\x", &unix).unwrap(),
+            "server/share/x"
+        );
+        assert!(sanitize_directory_file_path_with("../x", &unix).is_err());
+
+        let windows = ValidationOptions::new().target_platform(TargetPlatform::Windows);
+        assert!(matches!(
+            sanitize_directory_file_path_with("C:/x", &windows),
+            Err(PathError::DriveLetterPath { .. })
+        ));
+        assert!(matches!(
+            sanitize_directory_file_path_with("//server/share/x", &windows),
+            Err(PathError::UncPath { .. })
+        ));
+    }
+
+    #[test]
+    fn test_strip_drive_letter() {
+        assert_eq!(strip_drive_letter("C:\\a\\b"), Some("a/b".to_string()));
+        assert_eq!(strip_drive_letter("c:/a/b"), Some("a/b".to_string()));
+        assert_eq!(
+            strip_drive_letter("D:relative\\x"),
+            Some("relative/x".to_string())
+        );
+        assert_eq!(strip_drive_letter("z:"), Some(String::new()));
+
+        assert_eq!(strip_drive_letter("a/b"), None);
+        assert_eq!(strip_drive_letter("/c:/a"), None);
+        assert_eq!(strip_drive_letter("1:/a"), None);
+        assert_eq!(strip_drive_letter(""), None);
+
+        // The result is ready for sanitization
+        let stripped = strip_drive_letter("C:\\Users\\x\\file.txt").unwrap();
+        assert_eq!(
+            sanitize_directory_file_path(&stripped).unwrap(),
+            "Users/x/file.txt"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_directory_file_path_lossy() {
+        assert_eq!(
+            sanitize_directory_file_path_lossy("../../etc/passwd"),
+            "etc/passwd"
+        );
+        assert_eq!(
+            sanitize_directory_file_path_lossy("file<>.txt"),
+            "file__.txt"
+        );
+        assert_eq!(
+            sanitize_directory_file_path_lossy("/lib/generator.js"),
+            "lib/generator.js"
+        );
+        assert_eq!(sanitize_directory_file_path_lossy("a/./b/../c"), "a/b/c");
+        assert_eq!(
+            sanitize_directory_file_path_lossy("logs\\CON\\today.log. "),
+            "logs/CON_file/today.log"
+        );
+        assert_eq!(
+            sanitize_directory_file_path_lossy("C:\\Users\\me?.txt"),
+            "Users/me_.txt"
+        );
+        assert_eq!(sanitize_directory_file_path_lossy("/C:/x"), "x");
+        assert_eq!(sanitize_directory_file_path_lossy("a/. ./b"), "a/b");
+        assert_eq!(
+            sanitize_directory_file_path_lossy("a/..hidden"),
+            "a/..hidden"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_directory_file_path_lossy_placeholder() {
+        for path in ["", "/", "../..", "./.", ". . /..", "C:\\"] {
+            assert_eq!(sanitize_directory_file_path_lossy(path), "unnamed");
+        }
+    }
+
+    #[test]
+    fn test_sanitize_directory_file_path_lossy_output_is_valid() {
+        for path in [
+            "../../etc/passwd",
+            "a..b/c",
+            "\\\\server\\share\\NUL.txt",
+            "x\0y/z\u{202E}",
+            "d:relative/path",
+            "|/*/?",
+        ] {
+            let repaired = sanitize_directory_file_path_lossy(path);
+            assert_eq!(
+                sanitize_directory_file_path(&repaired).as_deref(),
+                Ok(repaired.as_str()),
+                "repair of {:?} is not clean",
+                path
+            );
+        }
     }
 
+    #[cfg(feature = "std")]
     #[test]
     fn test_safe_repository_join() {
         // Create a temporary directory for testing
@@ -401,6 +1743,7 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[cfg(feature = "std")]
     #[test]
     fn test_safe_repository_join_security() {
         let temp_dir = TempDir::new().unwrap();
@@ -420,6 +1763,192 @@ mod tests {
         assert!(safe_repository_join(temp_dir.path(), "test", "   ").is_err());
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_safe_repository_join_preserves_io_error_kind() {
+        let temp_dir = TempDir::new().unwrap();
+        let missing = temp_dir.path().join("not-created-yet");
+
+        let err = safe_repository_join(&missing, "test", "file.txt").unwrap_err();
+        assert!(matches!(err, PathError::IoError { .. }));
+        assert_eq!(err.kind(), Some(std::io::ErrorKind::NotFound));
+        assert!(err
+            .to_string()
+            .starts_with("I/O error: Cannot canonicalize workdir"));
+
+        // Errors converted with `?` keep their kind too
+        let io_error = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+        assert_eq!(
+            PathError::from(io_error).kind(),
+            Some(std::io::ErrorKind::PermissionDenied)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_safe_repository_join_no_symlinks() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_dir_canonical = temp_dir.path().canonicalize().unwrap();
+        std::fs::create_dir(temp_dir.path().join("src")).unwrap();
+
+        // Existing and missing directories both work
+        assert_eq!(
+            safe_repository_join_no_symlinks(temp_dir.path(), "src", "/lib.rs").unwrap(),
+            temp_dir_canonical.join("src/lib.rs")
+        );
+        assert_eq!(
+            safe_repository_join_no_symlinks(temp_dir.path(), "new/dir", "a.txt").unwrap(),
+            temp_dir_canonical.join("new/dir/a.txt")
+        );
+
+        // The usual checks still apply
+        assert!(matches!(
+            safe_repository_join_no_symlinks(temp_dir.path(), "src", "../../etc/passwd"),
+            Err(PathError::PathTraversal { .. })
+        ));
+    }
+
+    #[cfg(all(unix, feature = "std"))]
+    #[test]
+    fn test_safe_repository_join_no_symlinks_rejects_symlinks() {
+        use std::os::unix::fs::symlink;
+
+        let parent = TempDir::new().unwrap();
+        let workdir = parent.path().join("repo");
+        let outside = parent.path().join("outside");
+        std::fs::create_dir(&workdir).unwrap();
+        std::fs::create_dir(&outside).unwrap();
+        let workdir_canonical = workdir.canonicalize().unwrap();
+
+        // A symlinked intermediate directory
+        symlink(&outside, workdir.join("link")).unwrap();
+        assert_eq!(
+            safe_repository_join_no_symlinks(&workdir, "link/sub", "a.txt").unwrap_err(),
+            PathError::SymlinkInPath {
+                path: workdir_canonical
+                    .join("link")
+                    .to_string_lossy()
+                    .into_owned()
+            }
+        );
+        assert!(matches!(
+            safe_repository_join_no_symlinks(&workdir, "", "link/a.txt"),
+            Err(PathError::SymlinkInPath { .. })
+        ));
+
+        // A symlink as the file itself, even a dangling one
+        symlink(outside.join("missing"), workdir.join("file.txt")).unwrap();
+        assert!(matches!(
+            safe_repository_join_no_symlinks(&workdir, "", "file.txt"),
+            Err(PathError::SymlinkInPath { .. })
+        ));
+
+        // The plain join follows the link without complaint
+        assert!(safe_repository_join(&workdir, "link/sub", "a.txt").is_ok());
+
+        // A symlinked workdir is fine
+        let workdir_link = parent.path().join("repo-link");
+        symlink(&workdir, &workdir_link).unwrap();
+        assert_eq!(
+            safe_repository_join_no_symlinks(&workdir_link, "src", "a.txt").unwrap(),
+            workdir_canonical.join("src/a.txt")
+        );
+    }
+
+    #[test]
+    fn test_safe_join_both_untrusted() {
+        assert_eq!(
+            safe_join_both_untrusted("uploads", "avatar.png").unwrap(),
+            "uploads/avatar.png"
+        );
+        assert_eq!(
+            safe_join_both_untrusted("uploads//user/", "/avatar.png").unwrap(),
+            "uploads/user/avatar.png"
+        );
+        assert_eq!(
+            safe_join_both_untrusted("/srv\\uploads", "avatar.png").unwrap(),
+            "/srv/uploads/avatar.png"
+        );
+        assert_eq!(
+            safe_join_both_untrusted("/", "avatar.png").unwrap(),
+            "/avatar.png"
+        );
+
+        // Malicious base containing traversal
+        assert!(matches!(
+            safe_join_both_untrusted("uploads/../../etc", "passwd"),
+            Err(PathError::PathTraversal { .. })
+        ));
+        assert!(matches!(
+            safe_join_both_untrusted("..\\..\\windows", "system32"),
+            Err(PathError::PathTraversal { .. })
+        ));
+
+        // Malicious relative path
+        assert!(matches!(
+            safe_join_both_untrusted("uploads", "../etc/passwd"),
+            Err(PathError::PathTraversal { .. })
+        ));
+
+        // Other validation failures on either side
+        assert!(safe_join_both_untrusted("", "avatar.png").is_err());
+        assert!(safe_join_both_untrusted("uploads", "").is_err());
+        assert!(safe_join_both_untrusted("uploads/CON", "avatar.png").is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_safe_repository_join_lexical() {
+        // The workdir does not need to exist
+        let temp_dir = TempDir::new().unwrap();
+        let workdir = temp_dir.path().join("not/created/yet");
+
+        let result = safe_repository_join_lexical(&workdir, "testing/framework", "/args.js");
+        assert_eq!(result.unwrap(), workdir.join("testing/framework/args.js"));
+        assert!(!workdir.exists());
+
+        // Dot segments in the target are resolved when they stay inside
+        let result = safe_repository_join_lexical(&workdir, "a/./b/../c", "lib/gen.js");
+        assert_eq!(result.unwrap(), workdir.join("a/c/lib/gen.js"));
+
+        // An absolute target is treated as relative to the workdir
+        let result = safe_repository_join_lexical(&workdir, "/tools", "x.js");
+        assert_eq!(result.unwrap(), workdir.join("tools/x.js"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_safe_repository_join_lexical_security() {
+        let workdir = Path::new("/nonexistent/workdir");
+
+        assert!(matches!(
+            safe_repository_join_lexical(workdir, "..", "passwd"),
+            Err(PathError::PathTraversal { .. })
+        ));
+        assert!(matches!(
+            safe_repository_join_lexical(workdir, "a/../..", "passwd"),
+            Err(PathError::PathTraversal { .. })
+        ));
+        assert!(matches!(
+            safe_repository_join_lexical(workdir, "test", "../../etc/passwd"),
+            Err(PathError::PathTraversal { .. })
+        ));
+        assert!(safe_repository_join_lexical(workdir, "test", "file<script>").is_err());
+        assert!(safe_repository_join_lexical(workdir, "test", "").is_err());
+    }
+
+    #[test]
+    fn test_resolve_dot_segments() {
+        assert_eq!(
+            resolve_dot_segments(["a", ".", "b", "..", "c"]),
+            Some(vec!["a", "c"])
+        );
+        assert_eq!(resolve_dot_segments(["a", ".."]), Some(vec![]));
+        assert_eq!(resolve_dot_segments(["a", "..", ".."]), None);
+        assert_eq!(resolve_dot_segments([".."]), None);
+    }
+
+    #[cfg(feature = "std")]
     #[test]
     fn test_cli_bug_reproduction() {
         // This test reproduces the exact CLI bug scenario
@@ -452,4 +1981,235 @@ mod tests {
             "Should contain the filename"
         );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_check_join_compatibility() {
+        // Relative children are fine with any base
+        assert!(check_join_compatibility("/srv/www", "css/site.css").is_ok());
+        assert!(check_join_compatibility("C:\\www", "css\\site.css").is_ok());
+        assert!(check_join_compatibility("www", "css/site.css").is_ok());
+        assert!(check_join_compatibility("", "css/site.css").is_ok());
+
+        // Absolute children are rejected with any base
+        for (base, child, base_kind) in [
+            ("/srv/www", "/etc/passwd", "absolute"),
+            ("C:\\www", "D:\\data", "absolute"),
+            ("www", "/etc/passwd", "relative"),
+            ("www", "\\Windows", "relative"),
+            ("www", "C:file.txt", "relative"),
+        ] {
+            match check_join_compatibility(base, child) {
+                Err(PathError::ValidationFailed { rule, message }) => {
+                    assert_eq!(rule, "join_absolute_child");
+                    assert!(message.contains(base_kind), "{}", message)
+                }
+                other => panic!(
+                    "expected an error joining {:?} onto {:?}, got {:?}",
+                    child, base, other
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn test_normalized_components() {
+        for path in [
+            "a/b/c",
+            "a\\b/c",
+            "/a//b\\\\c/",
+            "C:\\Users\\me",
+            "./x/../y",
+            "single",
+            "",
+            "/",
+            "\\/\\",
+        ] {
+            let expected: Vec<String> = normalize_path_str(path)
+                .split('/')
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect();
+            let actual: Vec<&str> = normalized_components(path).collect();
+            assert_eq!(actual, expected, "components of {:?}", path);
+        }
+
+        // Components borrow from the input
+        let path = String::from("a\\b");
+        let first = normalized_components(&path).next().unwrap();
+        assert!(std::ptr::eq(first.as_ptr(), path.as_ptr()));
+    }
+
+    #[test]
+    fn test_path_depth() {
+        for path in ["", "/", "//", "\\", "/\\/"] {
+            assert_eq!(path_depth(path), 0, "depth of {:?}", path);
+        }
+
+        assert_eq!(path_depth("file.txt"), 1);
+        assert_eq!(path_depth("/dir/"), 1);
+        assert_eq!(path_depth("a\\b"), 2);
+
+        let deep = vec!["level"; 64].join("/");
+        assert_eq!(path_depth(&deep), 64);
+        assert_eq!(path_depth(&format!("//{}//", deep.replace('/', "\\"))), 64);
+
+        // Always agrees with the normalized form
+        for path in ["a/b/c", "./a/../b", "x//y\\z/"] {
+            assert_eq!(
+                path_depth(path),
+                normalize_path_str(path).split('/').count()
+            );
+        }
+    }
+
+    #[test]
+    fn test_relative_depth_escape() {
+        // Paths that stay within their starting directory
+        for path in [
+            "",
+            ".",
+            "a",
+            "a/b/../c",
+            "a/..",
+            "./a/./b",
+            "a/b/../../c",
+            "/a/..",
+        ] {
+            assert_eq!(relative_depth_escape(path), 0, "{:?}", path);
+        }
+
+        assert_eq!(relative_depth_escape(".."), -1);
+        assert_eq!(relative_depth_escape("a/../../x"), -1);
+        assert_eq!(relative_depth_escape("../a/b/c"), -1);
+        assert_eq!(relative_depth_escape("a\\..\\..\\..\\x"), -2);
+        assert_eq!(relative_depth_escape("/../../etc"), -2);
+
+        // The lowest point counts, even after descending again
+        assert_eq!(relative_depth_escape("../../../x/y/z/w"), -3);
+        assert_eq!(relative_depth_escape("a/../../b/c/../../../d"), -2);
+
+        let deep = vec![".."; 40].join("/");
+        assert_eq!(relative_depth_escape(&deep), -40);
+    }
+
+    #[test]
+    fn test_normalize_path_str_preserving_root() {
+        assert_eq!(normalize_path_str_preserving_root("/a//b"), "/a/b");
+        assert_eq!(normalize_path_str_preserving_root("a//b"), "a/b");
+        assert_eq!(normalize_path_str_preserving_root("//a"), "/a");
+        assert_eq!(normalize_path_str_preserving_root("\\a\\b\\"), "/a/b");
+        assert_eq!(normalize_path_str_preserving_root("/"), "/");
+        assert_eq!(normalize_path_str_preserving_root(""), "");
+
+        // Drive prefixes stay, and a bare drive root keeps its slash
+        assert_eq!(
+            normalize_path_str_preserving_root("C:\\Windows\\\\System32"),
+            "C:/Windows/System32"
+        );
+        assert_eq!(normalize_path_str_preserving_root("d:\\"), "d:/");
+        assert_eq!(
+            normalize_path_str_preserving_root("C:file.txt"),
+            "C:file.txt"
+        );
+    }
+
+    #[test]
+    fn test_to_forward_slashes() {
+        assert_eq!(to_forward_slashes("a\\b\\c.txt"), "a/b/c.txt");
+        assert_eq!(to_forward_slashes("\\\\server\\share"), "//server/share");
+        assert_eq!(to_forward_slashes("already/forward"), "already/forward");
+        assert_eq!(to_forward_slashes(""), "");
+    }
+
+    #[test]
+    fn test_trim_redundant_separators() {
+        assert_eq!(trim_redundant_separators("//a/..//b\\c/"), "/a/../b/c/");
+
+        // Leading and trailing separators are kept as one
+        assert_eq!(trim_redundant_separators("\\\\a"), "/a");
+        assert_eq!(trim_redundant_separators("a\\/\\"), "a/");
+        assert_eq!(trim_redundant_separators("///"), "/");
+        assert_eq!(trim_redundant_separators("a/b"), "a/b");
+        assert_eq!(trim_redundant_separators(""), "");
+
+        // Dot segments survive
+        assert_eq!(trim_redundant_separators("./a/.//./b/../"), "./a/././b/../");
+        assert_eq!(trim_redundant_separators("..\\..\\x"), "../../x");
+    }
+
+    #[test]
+    #[cfg(all(unix, feature = "std"))]
+    fn test_to_native_separators_unix() {
+        assert_eq!(to_native_separators("a/b/c.txt"), "a/b/c.txt");
+        assert_eq!(to_native_separators("/usr/lib/"), "/usr/lib/");
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn test_to_native_separators_windows() {
+        assert_eq!(to_native_separators("a/b/c.txt"), "a\\b\\c.txt");
+        assert_eq!(to_native_separators("C:/Users/"), "C:\\Users\\");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_native_separators_round_trip() {
+        for path in ["a/b/c.txt", "C:/Windows/System32", "/", "file"] {
+            assert_eq!(to_forward_slashes(&to_native_separators(path)), path);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-normalization")]
+    fn test_normalize_unicode() {
+        let composed = "docs/caf\u{e9}.txt";
+        let decomposed = "docs/cafe\u{301}.txt";
+        assert_ne!(composed, decomposed);
+
+        assert_eq!(normalize_unicode(decomposed), composed);
+        assert_eq!(normalize_unicode(composed), composed);
+        assert_eq!(normalize_path_str(decomposed), composed);
+        assert_eq!(
+            normalize_path_str("\\docs\\\\cafe\u{301}.txt"),
+            normalize_path_str(composed)
+        );
+
+        // Already-composed, normalized input is still borrowed
+        assert!(matches!(normalize_path_cow(composed), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_normalize_with_absoluteness() {
+        assert_eq!(
+            normalize_with_absoluteness("/a/b"),
+            ("a/b".to_string(), true)
+        );
+        assert_eq!(
+            normalize_with_absoluteness("\\\\server\\share"),
+            ("server/share".to_string(), true)
+        );
+        assert_eq!(
+            normalize_with_absoluteness("d:/data"),
+            ("d:/data".to_string(), true)
+        );
+        assert_eq!(normalize_with_absoluteness("/"), (String::new(), true));
+
+        assert_eq!(
+            normalize_with_absoluteness("a//b/"),
+            ("a/b".to_string(), false)
+        );
+        assert_eq!(
+            normalize_with_absoluteness("C:file.txt"),
+            ("C:file.txt".to_string(), false)
+        );
+        assert_eq!(normalize_with_absoluteness(""), (String::new(), false));
+
+        // The string always matches normalize_path_str
+        for path in ["a\\b", "//x//", "./a", "C:\\x\\", "a/../b"] {
+            assert_eq!(
+                normalize_with_absoluteness(path).0,
+                normalize_path_str(path)
+            );
+        }
+    }
+}
@@ -0,0 +1,450 @@
+//! Configuration types for path operations
+//!
+//! Options are built with consuming builder methods starting from `Default`,
+//! which always matches the behavior of the plain (option-less) functions.
+
+use crate::reserved::ReservedNames;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Options controlling [`normalize_path_str_with`](crate::normalize_path_str_with)
+///
+/// # Examples
+/// ```
+/// use path_utils::{normalize_path_str_with, NormalizeOptions};
+///
+/// let options = NormalizeOptions::new().lowercase_ascii(true);
+/// assert_eq!(normalize_path_str_with("FOO//BAR.TXT", &options), "foo/bar.txt");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NormalizeOptions {
+    pub(crate) lowercase_ascii: bool,
+    pub(crate) preserve_trailing_slash: bool,
+    pub(crate) strip_current_dir: bool,
+}
+
+impl NormalizeOptions {
+    /// Create options matching the behavior of [`normalize_path_str`](crate::normalize_path_str)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lowercase ASCII letters in each component (default: `false`)
+    ///
+    /// Useful for legacy Windows output that uppercases everything (`FOO/BAR.TXT`).
+    /// Only `A`-`Z` are affected; non-ASCII characters are left untouched to avoid
+    /// locale-dependent surprises, so this is not full Unicode case folding.
+    pub fn lowercase_ascii(mut self, enabled: bool) -> Self {
+        self.lowercase_ascii = enabled;
+        self
+    }
+
+    /// Keep one trailing `/` when the input ended in a separator (default: `false`)
+    ///
+    /// Many tools treat `foo/` as a directory and `foo` as possibly a file. With
+    /// this enabled, any run of trailing `/` or `\` collapses to a single `/`
+    /// instead of being removed. A path with no components, such as `/`, still
+    /// normalizes to the empty string.
+    pub fn preserve_trailing_slash(mut self, enabled: bool) -> Self {
+        self.preserve_trailing_slash = enabled;
+        self
+    }
+
+    /// Remove `.` components (default: `false`)
+    ///
+    /// `a/./b` becomes `a/b` and `./a` becomes `a`. Only components that are
+    /// exactly `.` are removed: `..` is left for the traversal checks to deal
+    /// with, and names such as `...` or `.env` are ordinary components.
+    pub fn strip_current_dir(mut self, enabled: bool) -> Self {
+        self.strip_current_dir = enabled;
+        self
+    }
+}
+
+/// Policy for [`validate_path_with`](crate::validate_path_with)
+///
+/// The default policy matches [`validate_path`](crate::validate_path) exactly.
+///
+/// # Examples
+/// ```
+/// use path_utils::{validate_path_with, ValidationOptions};
+///
+/// // A Linux-only deployment that doesn't care about Windows device names
+/// let options = ValidationOptions::new().allow_reserved_names(true);
+/// assert!(validate_path_with("logs/CON", &options).is_ok());
+///
+/// // Stricter rules on top of the defaults
+/// let options = ValidationOptions::new()
+///     .max_length(Some(16))
+///     .extra_forbidden_chars(&['#', '%']);
+/// assert!(validate_path_with("notes#1.txt", &options).is_err());
+/// assert!(validate_path_with("a/very/long/path/name.txt", &options).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationOptions {
+    pub(crate) allow_reserved_names: bool,
+    pub(crate) reserved_names: ReservedNames,
+    pub(crate) allow_control_chars: bool,
+    pub(crate) reject_bidi_controls: bool,
+    pub(crate) reject_ads: bool,
+    pub(crate) reject_dot_whitespace_components: bool,
+    pub(crate) reject_surrounding_whitespace: bool,
+    pub(crate) reject_short_names: bool,
+    pub(crate) require_relative: bool,
+    pub(crate) max_length: Option<usize>,
+    pub(crate) max_component_length: Option<usize>,
+    pub(crate) extra_forbidden_chars: Vec<char>,
+    pub(crate) extension_policy: ExtensionPolicy,
+    pub(crate) reject_dotgit: bool,
+    pub(crate) reject_invalid_utf8: bool,
+    pub(crate) target_platform: TargetPlatform,
+    pub(crate) strip_absolute_prefix: Option<String>,
+    pub(crate) reject_unmatched_absolute: bool,
+    #[cfg(feature = "unicode")]
+    pub(crate) max_component_graphemes: Option<usize>,
+}
+
+/// Size limits for [`validate_limits`](crate::validate_limits)
+///
+/// Every limit is off by default; enable the ones the target filesystem
+/// imposes. Lengths are UTF-8 bytes and depth counts components as
+/// [`path_depth`](crate::path_depth) does.
+///
+/// # Examples
+/// ```
+/// use path_utils::{validate_limits, PathLimits};
+///
+/// // Typical Linux limits, plus a nesting cap for extracted archives
+/// let limits = PathLimits::new()
+///     .max_total_bytes(Some(4096))
+///     .max_component_bytes(Some(255))
+///     .max_depth(Some(32));
+/// assert!(validate_limits("src/main.rs", &limits).is_ok());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PathLimits {
+    pub(crate) max_total_bytes: Option<usize>,
+    pub(crate) max_depth: Option<usize>,
+    pub(crate) max_component_bytes: Option<usize>,
+}
+
+impl PathLimits {
+    /// Create limits that accept every path
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject paths longer than `max` bytes in total (default: `None`)
+    pub fn max_total_bytes(mut self, max: Option<usize>) -> Self {
+        self.max_total_bytes = max;
+        self
+    }
+
+    /// Reject paths with more than `max` components (default: `None`)
+    pub fn max_depth(mut self, max: Option<usize>) -> Self {
+        self.max_depth = max;
+        self
+    }
+
+    /// Reject paths with any component longer than `max` bytes (default: `None`)
+    pub fn max_component_bytes(mut self, max: Option<usize>) -> Self {
+        self.max_component_bytes = max;
+        self
+    }
+}
+
+/// The platform whose filesystem rules a path must satisfy
+///
+/// The Windows rules are the device names (`CON`, `LPT1`, ...), the characters
+/// `< > | ? * "`, `:` outside a drive prefix (an NTFS alternate data stream),
+/// components made only of dots and whitespace, and, when sanitizing, drive
+/// letters and UNC prefixes. Unix adds no rules of its own, so a
+/// [`Unix`](TargetPlatform::Unix) target is the Windows-free subset. The rules
+/// are chosen by this setting alone, never by the host the code runs on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TargetPlatform {
+    /// Paths that will only be used on Unix-like systems
+    Unix,
+    /// Paths that will be written on Windows
+    Windows,
+    /// Paths that must work everywhere; the strictest choice
+    #[default]
+    All,
+}
+
+/// Which file extensions validation accepts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ExtensionPolicy {
+    /// Any extension, or none
+    Any,
+    /// Anything except these lowercase extensions
+    Deny(Vec<String>),
+    /// Only these lowercase extensions (`""` for files without one)
+    Allow(Vec<String>),
+}
+
+/// Lowercase extensions and strip any leading dot, so `".EXE"` and `"exe"` agree
+fn normalize_extensions(extensions: &[&str]) -> Vec<String> {
+    extensions
+        .iter()
+        .map(|extension| extension.trim_start_matches('.').to_ascii_lowercase())
+        .collect()
+}
+
+/// The longest file name most filesystems accept, in bytes
+pub(crate) const DEFAULT_MAX_COMPONENT_LENGTH: usize = 255;
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        Self {
+            allow_reserved_names: false,
+            reserved_names: ReservedNames::default(),
+            allow_control_chars: false,
+            reject_bidi_controls: true,
+            reject_ads: true,
+            reject_dot_whitespace_components: true,
+            reject_surrounding_whitespace: true,
+            reject_short_names: false,
+            require_relative: false,
+            max_length: None,
+            max_component_length: Some(DEFAULT_MAX_COMPONENT_LENGTH),
+            extra_forbidden_chars: Vec::new(),
+            extension_policy: ExtensionPolicy::Any,
+            reject_dotgit: false,
+            reject_invalid_utf8: false,
+            target_platform: TargetPlatform::All,
+            strip_absolute_prefix: None,
+            reject_unmatched_absolute: false,
+            #[cfg(feature = "unicode")]
+            max_component_graphemes: None,
+        }
+    }
+}
+
+impl ValidationOptions {
+    /// Create options matching the behavior of [`validate_path`](crate::validate_path)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept Windows reserved filenames such as `CON` or `LPT1` (default: `false`)
+    pub fn allow_reserved_names(mut self, allow: bool) -> Self {
+        self.allow_reserved_names = allow;
+        self
+    }
+
+    /// Replace the set of reserved file names (default: [`ReservedNames::with_defaults`])
+    ///
+    /// Ignored when [`allow_reserved_names`](Self::allow_reserved_names) is enabled.
+    pub fn reserved_names(mut self, names: ReservedNames) -> Self {
+        self.reserved_names = names;
+        self
+    }
+
+    /// Accept control characters (default: `false`)
+    ///
+    /// Null bytes are always rejected since no filesystem can store them.
+    pub fn allow_control_chars(mut self, allow: bool) -> Self {
+        self.allow_control_chars = allow;
+        self
+    }
+
+    /// Reject bidirectional controls and invisible format characters (default: `true`)
+    ///
+    /// Overrides such as U+202E can make a name like `exe.txt` display as
+    /// `txt.exe` (the "Trojan Source" trick), and zero-width characters make two
+    /// names look identical, so both are dangerous in any UI that shows paths.
+    /// Disable this for names that legitimately use a zero-width joiner, such as
+    /// some emoji sequences and Indic scripts.
+    pub fn reject_bidi_controls(mut self, reject: bool) -> Self {
+        self.reject_bidi_controls = reject;
+        self
+    }
+
+    /// Reject `:` anywhere but in a leading drive prefix (default: `true`)
+    ///
+    /// On NTFS, `file.txt:hidden` writes to an alternate data stream of
+    /// `file.txt`, which can smuggle data past checks that only look at file
+    /// names. A drive prefix such as `C:` or `C:\` is left to the drive-letter
+    /// rules, but a short name like `a:b` is treated as a stream.
+    pub fn reject_ads(mut self, reject: bool) -> Self {
+        self.reject_ads = reject;
+        self
+    }
+
+    /// Reject components made only of dots and whitespace, such as `...` or `   ` (default: `true`)
+    ///
+    /// Windows strips trailing dots and spaces, so these names are inaccessible
+    /// or resolve to the parent directory, and they are almost never intended.
+    /// The `.` current-directory component is not affected, and `..` is always
+    /// rejected as traversal.
+    pub fn reject_dot_whitespace_components(mut self, reject: bool) -> Self {
+        self.reject_dot_whitespace_components = reject;
+        self
+    }
+
+    /// Reject paths that start or end with whitespace, such as ` a/b` or `a/b ` (default: `true`)
+    ///
+    /// Whitespace around a stored path is almost always left over from parsing,
+    /// and makes two spellings of the same file compare unequal. The first
+    /// component may not start with whitespace and the last may not end with
+    /// it; whitespace inside the path, as in `a/ b/c` or `my file.txt`, is
+    /// allowed. Fails with [`PathError::InvalidCharacters`](crate::PathError::InvalidCharacters).
+    pub fn reject_surrounding_whitespace(mut self, reject: bool) -> Self {
+        self.reject_surrounding_whitespace = reject;
+        self
+    }
+
+    /// Reject absolute paths with [`PathError::AbsolutePath`](crate::PathError::AbsolutePath) (default: `false`)
+    ///
+    /// By default validation only looks for unsafe content, so `/etc/passwd`
+    /// passes. Enable this when "valid" should mean "safe to use relative to
+    /// a repository or other base directory": paths starting with `/` or `\`,
+    /// including UNC paths, are rejected, and so are paths starting with a
+    /// drive letter (`C:\x`, or the drive-relative `C:x`) unless the
+    /// [`target_platform`](Self::target_platform) is Unix.
+    pub fn require_relative(mut self, require: bool) -> Self {
+        self.require_relative = require;
+        self
+    }
+
+    /// Reject components shaped like Windows 8.3 short names, such as `PROGRA~1` (default: `false`)
+    ///
+    /// Windows gives long names an alias like `PROGRA~1` or `FOO~12.TXT`, so a
+    /// path using one can reach a file that a check on the long name (a deny
+    /// list, say) was meant to block. A component is rejected when its base
+    /// name is at most 8 characters and ends in `~` followed by digits, and its
+    /// extension, if any, is at most 3 characters. A tilde elsewhere, as in
+    /// `foo~bar.txt` or `backup~`, is fine. Only applies when the target
+    /// includes Windows.
+    ///
+    /// Such paths fail with a [`ValidationFailed`](crate::PathError::ValidationFailed)
+    /// error for the `short_name` rule, whose message names the alias.
+    pub fn reject_short_names(mut self, reject: bool) -> Self {
+        self.reject_short_names = reject;
+        self
+    }
+
+    /// Reject paths longer than `max` bytes (default: `None`, no limit)
+    pub fn max_length(mut self, max: Option<usize>) -> Self {
+        self.max_length = max;
+        self
+    }
+
+    /// Reject any single component longer than `max` bytes (default: `Some(255)`)
+    ///
+    /// ext4, NTFS, APFS and most other filesystems cap each name at 255 bytes
+    /// (or units), however short the full path is. `None` disables the check.
+    pub fn max_component_length(mut self, max: Option<usize>) -> Self {
+        self.max_component_length = max;
+        self
+    }
+
+    /// Reject these characters in addition to the built-in forbidden set
+    pub fn extra_forbidden_chars(mut self, chars: &[char]) -> Self {
+        self.extra_forbidden_chars = chars.to_vec();
+        self
+    }
+
+    /// Reject paths with a `.git` component or a confusable variant (default: `false`)
+    ///
+    /// Useful when writing into a Git working tree, where a `.git` component
+    /// could overwrite repository internals such as hooks. See
+    /// [`is_dotgit_confusable`](crate::is_dotgit_confusable) for the variants.
+    pub fn reject_dotgit(mut self, reject: bool) -> Self {
+        self.reject_dotgit = reject;
+        self
+    }
+
+    /// Apply the rules of a specific platform instead of all of them (default: [`TargetPlatform::All`])
+    ///
+    /// A Linux CI job checking paths bound for Windows can use
+    /// [`TargetPlatform::Windows`], and a Unix-only tool can use
+    /// [`TargetPlatform::Unix`] to accept names like `CON` or `a:b`. The
+    /// individual switches such as
+    /// [`allow_reserved_names`](Self::allow_reserved_names) still apply on top:
+    /// a rule runs only if both the target and its own setting call for it.
+    pub fn target_platform(mut self, platform: TargetPlatform) -> Self {
+        self.target_platform = platform;
+        self
+    }
+
+    /// Whether the Windows-specific rules apply for the target platform
+    pub(crate) fn windows_rules(&self) -> bool {
+        self.target_platform != TargetPlatform::Unix
+    }
+
+    /// Remove a known mount point from absolute paths when sanitizing (default: `None`)
+    ///
+    /// Some formats record absolute paths that are really relative to a known
+    /// root, such as `/app/src/x` for a project mounted at `/app`. With a
+    /// prefix set,
+    /// [`sanitize_directory_file_path_with`](crate::sanitize_directory_file_path_with)
+    /// removes it from an absolute path whose leading components match it, so
+    /// `/app/src/x` becomes `src/x` rather than `app/src/x`. Components are
+    /// compared whole and case-sensitively, after separator normalization, so
+    /// `/application/x` doesn't match `/app`. Relative paths are never
+    /// stripped. Absolute paths that don't match fall back to the usual
+    /// removal of the leading separator, unless
+    /// [`reject_unmatched_absolute`](Self::reject_unmatched_absolute) is set.
+    pub fn strip_absolute_prefix(mut self, prefix: Option<&str>) -> Self {
+        self.strip_absolute_prefix = prefix.map(String::from);
+        self
+    }
+
+    /// Reject absolute paths outside the [`strip_absolute_prefix`](Self::strip_absolute_prefix) (default: `false`)
+    ///
+    /// Such paths fail with a [`ValidationFailed`](crate::PathError::ValidationFailed)
+    /// error for the `absolute_prefix` rule. Has no effect without a prefix.
+    pub fn reject_unmatched_absolute(mut self, reject: bool) -> Self {
+        self.reject_unmatched_absolute = reject;
+        self
+    }
+
+    /// Reject byte input that isn't valid UTF-8 instead of decoding it lossily (default: `false`)
+    ///
+    /// Only applies to functions that take raw bytes, such as
+    /// [`sanitize_null_delimited_with`](crate::sanitize_null_delimited_with).
+    /// Lossy decoding replaces invalid sequences with U+FFFD, so the sanitized
+    /// path may no longer name the original file.
+    pub fn reject_invalid_utf8(mut self, reject: bool) -> Self {
+        self.reject_invalid_utf8 = reject;
+        self
+    }
+
+    /// Reject files whose extension is in `extensions` (default: any extension is accepted)
+    ///
+    /// Extensions are compared case-insensitively, with or without a leading
+    /// dot. Only the last extension of the final component counts, so
+    /// `archive.tar.gz` has the extension `gz`; to reject it, deny `gz` rather
+    /// than `tar.gz`. Trailing dots and spaces are ignored as Windows ignores
+    /// them, so `payload.exe.` is treated as an `exe`. Files without an
+    /// extension are accepted. Replaces any earlier
+    /// [`allow_extensions`](Self::allow_extensions) list.
+    pub fn deny_extensions(mut self, extensions: &[&str]) -> Self {
+        self.extension_policy = ExtensionPolicy::Deny(normalize_extensions(extensions));
+        self
+    }
+
+    /// Accept only files whose extension is in `extensions` (default: any extension is accepted)
+    ///
+    /// Extensions are matched as in [`deny_extensions`](Self::deny_extensions).
+    /// Files without an extension are rejected unless `""` is in the list.
+    /// Replaces any earlier [`deny_extensions`](Self::deny_extensions) list.
+    pub fn allow_extensions(mut self, extensions: &[&str]) -> Self {
+        self.extension_policy = ExtensionPolicy::Allow(normalize_extensions(extensions));
+        self
+    }
+
+    /// Reject components longer than `max` grapheme clusters (default: `None`, no limit)
+    ///
+    /// Counts what a user sees as characters, so `e` followed by a combining
+    /// accent is one. Suited to user-facing limits such as display width
+    /// budgets; use [`max_length`](Self::max_length) for filesystem limits,
+    /// which are counted in bytes. See [`grapheme_count`](crate::grapheme_count).
+    #[cfg(feature = "unicode")]
+    pub fn max_component_graphemes(mut self, max: Option<usize>) -> Self {
+        self.max_component_graphemes = max;
+        self
+    }
+}
@@ -0,0 +1,136 @@
+//! Path functions over `OsStr` for paths that may not be valid UTF-8
+//!
+//! The string-based API converts with `to_string_lossy`, which replaces invalid
+//! sequences with U+FFFD. These functions work on the platform's native code
+//! units instead (bytes on Unix, UTF-16 on Windows), so every unit survives.
+
+use crate::validate::is_safe_path;
+use std::ffi::{OsStr, OsString};
+
+/// Normalize an `OsStr` path without losing non-UTF-8 data
+///
+/// Performs the same transformation as [`normalize_path_str`](crate::normalize_path_str):
+/// backslashes become forward slashes, and empty components (from leading,
+/// trailing or repeated separators) are removed. Only separators are
+/// touched, so any invalid sequences are preserved exactly, and no Unicode
+/// normalization is applied even with the `unicode-normalization` feature.
+///
+/// # Examples
+/// ```
+/// use path_utils::normalize_os_str;
+/// use std::ffi::OsStr;
+///
+/// assert_eq!(normalize_os_str(OsStr::new("a//b\\c/")), OsStr::new("a/b/c"));
+/// ```
+pub fn normalize_os_str(path: &OsStr) -> OsString {
+    imp::normalize(path)
+}
+
+/// Check if an `OsStr` path is safe for use
+///
+/// Applies the same rules as [`is_safe_path`] to a lossy conversion of the
+/// path. The conversion keeps every valid character intact, so the character
+/// rules, including the non-ASCII checks for invisible format characters and
+/// C1 controls, see exactly what the original contains. Only invalid
+/// sequences change, each becoming U+FFFD, which is not a control or format
+/// character and so passes every character rule. One difference remains:
+/// U+FFFD takes three bytes, so a name close to the component length limit can
+/// fail after conversion where its raw bytes would fit.
+///
+/// # Examples
+/// ```
+/// use path_utils::is_safe_os_path;
+/// use std::ffi::OsStr;
+///
+/// assert!(is_safe_os_path(OsStr::new("safe/path/file.txt")));
+/// assert!(!is_safe_os_path(OsStr::new("../etc/passwd")));
+/// ```
+pub fn is_safe_os_path(path: &OsStr) -> bool {
+    is_safe_path(path.to_string_lossy())
+}
+
+/// Collapse separators in a sequence of code units, as `normalize_path_str` does for `str`
+fn normalize_units<T: Copy + PartialEq>(units: &[T], slash: T, backslash: T) -> Vec<T> {
+    let mut normalized = Vec::with_capacity(units.len());
+    let components = units
+        .split(|&unit| unit == slash || unit == backslash)
+        .filter(|component| !component.is_empty());
+
+    for component in components {
+        if !normalized.is_empty() {
+            normalized.push(slash);
+        }
+        normalized.extend_from_slice(component);
+    }
+    normalized
+}
+
+#[cfg(unix)]
+mod imp {
+    use super::normalize_units;
+    use std::ffi::{OsStr, OsString};
+    use std::os::unix::ffi::{OsStrExt, OsStringExt};
+
+    pub(super) fn normalize(path: &OsStr) -> OsString {
+        OsString::from_vec(normalize_units(path.as_bytes(), b'/', b'\\'))
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use super::normalize_units;
+    use std::ffi::{OsStr, OsString};
+    use std::os::windows::ffi::{OsStrExt, OsStringExt};
+
+    pub(super) fn normalize(path: &OsStr) -> OsString {
+        let wide: Vec<u16> = path.encode_wide().collect();
+        OsString::from_wide(&normalize_units(&wide, b'/' as u16, b'\\' as u16))
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod imp {
+    use crate::normalize::normalize_path_str;
+    use std::ffi::{OsStr, OsString};
+
+    // No access to the raw units here; platforms in this group (e.g. WASI
+    // without extensions) generally require UTF-8 paths anyway.
+    pub(super) fn normalize(path: &OsStr) -> OsString {
+        OsString::from(normalize_path_str(&path.to_string_lossy()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_os_str_matches_str_version() {
+        for path in ["a//b", "a\\b", "/a/b/", "a", "", "//", "a/./b"] {
+            assert_eq!(
+                normalize_os_str(OsStr::new(path)),
+                OsString::from(crate::normalize_path_str(path)),
+                "mismatch for {:?}",
+                path
+            );
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_non_utf8_bytes_survive() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = OsStr::from_bytes(b"a//\xFF/b");
+        assert_eq!(normalize_os_str(path).as_bytes(), b"a/\xFF/b");
+
+        assert!(is_safe_os_path(path));
+        assert!(!is_safe_os_path(OsStr::from_bytes(b"\xFF/../b")));
+        assert!(!is_safe_os_path(OsStr::from_bytes(b"\xFF/CON")));
+        assert!(!is_safe_os_path(OsStr::from_bytes(b"a\x01\xFF")));
+
+        // Non-ASCII rules still see the valid characters around invalid bytes
+        assert!(!is_safe_os_path(OsStr::from_bytes(b"\xFFa\xE2\x80\xAEb")));
+        assert!(!is_safe_os_path(OsStr::from_bytes(b"\xFF\xC2\x85")));
+    }
+}
@@ -0,0 +1,276 @@
+//! Filesystem target profiles
+//!
+//! A [`TargetProfile`] describes the naming rules of a specific filesystem, for
+//! checking that a path can be written there before attempting it.
+
+use crate::error::{PathError, Result};
+use crate::reserved::ReservedNames;
+use alloc::format;
+use alloc::string::ToString;
+
+/// Naming rules for a specific target filesystem
+pub trait TargetProfile {
+    /// Human-readable name of the target, used in error messages
+    fn name(&self) -> &str;
+
+    /// Check a single path component
+    ///
+    /// `path` is the full path being validated, for error reporting.
+    fn validate_component(&self, component: &str, path: &str) -> Result<()>;
+}
+
+/// Validate that a path can be written to the filesystem described by `profile`
+///
+/// Both `/` and `\` are accepted as separators and empty components are
+/// ignored. `..` components are always rejected; `.` components are skipped.
+/// Every other component is checked with [`TargetProfile::validate_component`].
+///
+/// # Examples
+/// ```
+/// use path_utils::{validate_path_for_profile, FatProfile};
+///
+/// let fat = FatProfile::new();
+/// assert!(validate_path_for_profile("DCIM/IMG_0001.JPG", &fat).is_ok());
+/// assert!(validate_path_for_profile("notes/todo:later.txt", &fat).is_err());
+/// ```
+pub fn validate_path_for_profile<T: TargetProfile + ?Sized>(path: &str, profile: &T) -> Result<()> {
+    if path.trim().is_empty() {
+        return Err(PathError::EmptyPath);
+    }
+
+    for component in path.split(['/', '\\']) {
+        match component {
+            "" | "." => {}
+            ".." => {
+                return Err(PathError::PathTraversal {
+                    path: path.to_string(),
+                })
+            }
+            _ => profile.validate_component(component, path)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Characters that are never allowed in FAT long file names
+const FAT_FORBIDDEN_CHARS: [char; 9] = ['"', '*', '/', ':', '<', '>', '?', '\\', '|'];
+
+/// Characters allowed in 8.3 short names besides ASCII letters and digits
+const FAT_SHORT_NAME_SPECIALS: &str = "!#$%&'()-@^_`{}~";
+
+/// The longest long file name, in UTF-16 code units
+const FAT_MAX_LONG_NAME: usize = 255;
+
+/// Naming rules for FAT32 (and VFAT) filesystems, as found on USB sticks and SD cards
+///
+/// By default names are checked against the long file name (VFAT) rules:
+/// - No control characters (0x00-0x1F) and none of `" * / : < > ? \ |`
+/// - At most 255 UTF-16 code units per name
+/// - No trailing dot or space, since Windows strips them
+/// - No Windows device names (`CON`, `NUL`, `COM1`, ...)
+///
+/// With [`require_short_names`](Self::require_short_names), names must also fit
+/// the classic 8.3 format, for devices that don't understand long names:
+/// - A base name of 1-8 characters and an optional extension of 1-3 characters
+/// - Only ASCII letters, digits and ``! # $ % & ' ( ) - @ ^ _ ` { } ~``
+///
+/// Short names are stored uppercased, so letters are accepted in either case,
+/// but the original case is not preserved on the device.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FatProfile {
+    short_names_only: bool,
+}
+
+impl FatProfile {
+    /// A profile using the long file name rules
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Additionally require every name to fit the 8.3 short name format (default: `false`)
+    pub fn require_short_names(mut self, required: bool) -> Self {
+        self.short_names_only = required;
+        self
+    }
+
+    fn validate_short_name(&self, component: &str, path: &str) -> Result<()> {
+        let (base, extension) = match component.split_once('.') {
+            Some((base, extension)) => (base, Some(extension)),
+            None => (component, None),
+        };
+
+        let valid_chars = |part: &str| {
+            part.chars()
+                .all(|c| c.is_ascii_alphanumeric() || FAT_SHORT_NAME_SPECIALS.contains(c))
+        };
+
+        if !valid_chars(base) || !extension.map_or(true, valid_chars) {
+            return Err(PathError::InvalidCharacters {
+                path: path.to_string(),
+            });
+        }
+
+        if base.is_empty()
+            || base.len() > 8
+            || extension.is_some_and(|extension| extension.is_empty() || extension.len() > 3)
+        {
+            return Err(PathError::validation_failed(
+                "fat_short_name",
+                format!("{} is not a valid 8.3 short name in {}", component, path),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl TargetProfile for FatProfile {
+    fn name(&self) -> &str {
+        "FAT32"
+    }
+
+    fn validate_component(&self, component: &str, path: &str) -> Result<()> {
+        if component
+            .chars()
+            .any(|c| c.is_ascii_control() && c != '\x7F' || FAT_FORBIDDEN_CHARS.contains(&c))
+        {
+            return Err(PathError::InvalidCharacters {
+                path: path.to_string(),
+            });
+        }
+
+        if component.ends_with(['.', ' ']) {
+            return Err(PathError::InvalidCharacters {
+                path: path.to_string(),
+            });
+        }
+
+        if ReservedNames::default().matches(component) {
+            return Err(PathError::ReservedFilename {
+                filename: component.to_string(),
+                path: path.to_string(),
+            });
+        }
+
+        let length = component.encode_utf16().count();
+        if length > FAT_MAX_LONG_NAME {
+            return Err(PathError::validation_failed(
+                "fat_name_length",
+                format!(
+                    "{} name is {} UTF-16 units long, exceeding the maximum of {}: {}",
+                    self.name(),
+                    length,
+                    FAT_MAX_LONG_NAME,
+                    path
+                ),
+            ));
+        }
+
+        if self.short_names_only {
+            self.validate_short_name(component, path)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fat_long_names() {
+        let fat = FatProfile::new();
+
+        assert!(validate_path_for_profile("DCIM/100CANON/IMG_0001.JPG", &fat).is_ok());
+        assert!(
+            validate_path_for_profile("Music\\Artist - Album (2020)\\01 Track.mp3", &fat).is_ok()
+        );
+        assert!(validate_path_for_profile("Über naïve 文件.txt", &fat).is_ok());
+        assert!(validate_path_for_profile("a+b,c;d=e[f].txt", &fat).is_ok());
+
+        // Characters FAT forbids
+        for name in [
+            "what?.txt",
+            "a*b",
+            "time 12:00.txt",
+            "quote\".txt",
+            "a<b>c",
+            "pipe|d",
+            "tab\there",
+        ] {
+            assert!(
+                matches!(
+                    validate_path_for_profile(name, &fat),
+                    Err(PathError::InvalidCharacters { .. })
+                ),
+                "should be rejected on FAT32: {:?}",
+                name
+            );
+        }
+
+        // Trailing dots and spaces
+        assert!(validate_path_for_profile("name.", &fat).is_err());
+        assert!(validate_path_for_profile("dir /file", &fat).is_err());
+
+        // Device names
+        assert!(matches!(
+            validate_path_for_profile("AUX.txt", &fat),
+            Err(PathError::ReservedFilename { .. })
+        ));
+
+        // Traversal and emptiness
+        assert!(matches!(
+            validate_path_for_profile("../x", &fat),
+            Err(PathError::PathTraversal { .. })
+        ));
+        assert!(matches!(
+            validate_path_for_profile("", &fat),
+            Err(PathError::EmptyPath)
+        ));
+    }
+
+    #[test]
+    fn test_fat_long_name_length() {
+        let fat = FatProfile::new();
+        assert!(validate_path_for_profile(&"a".repeat(255), &fat).is_ok());
+        assert_eq!(
+            validate_path_for_profile(&"a".repeat(256), &fat)
+                .unwrap_err()
+                .rule(),
+            Some("fat_name_length")
+        );
+
+        // Length is measured in UTF-16 units: 200 chars of 3-byte UTF-8 fit
+        assert!(validate_path_for_profile(&"文".repeat(200), &fat).is_ok());
+        // ...while characters outside the BMP take two units each
+        assert!(validate_path_for_profile(&"😀".repeat(128), &fat).is_err());
+    }
+
+    #[test]
+    fn test_fat_short_names() {
+        let fat = FatProfile::new().require_short_names(true);
+
+        assert!(validate_path_for_profile("DCIM/IMG_0001.JPG", &fat).is_ok());
+        assert!(validate_path_for_profile("readme.txt", &fat).is_ok());
+        assert!(validate_path_for_profile("MAKEFILE", &fat).is_ok());
+        assert!(validate_path_for_profile("PROGRA~1/A$B{C}.~1", &fat).is_ok());
+
+        // Too long
+        assert_eq!(
+            validate_path_for_profile("LONGFILENAME.TXT", &fat)
+                .unwrap_err()
+                .rule(),
+            Some("fat_short_name")
+        );
+        assert!(validate_path_for_profile("FILE.HTML", &fat).is_err());
+        // Characters that only long names allow
+        assert!(validate_path_for_profile("A+B.TXT", &fat).is_err());
+        assert!(validate_path_for_profile("MY FILE.TXT", &fat).is_err());
+        assert!(validate_path_for_profile("A.B.C", &fat).is_err());
+        assert!(validate_path_for_profile("CAFÉ.TXT", &fat).is_err());
+        // Empty base or extension
+        assert!(validate_path_for_profile(".PROFILE", &fat).is_err());
+    }
+}
@@ -0,0 +1,528 @@
+//! Relative path computation
+//!
+//! Functions for expressing one path relative to another.
+
+use crate::error::{PathError, Result};
+use crate::normalize::{normalized_components, resolve_dot_segments};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use std::path::{Component, Path};
+
+/// Compute the relative path from `base` to `target`, given their components
+///
+/// Both inputs must be free of `.` and `..` components and share the same root.
+/// Returns `.` when the paths are identical.
+pub(crate) fn relative_path_between(base: &[&str], target: &[&str]) -> String {
+    let common = base.iter().zip(target).take_while(|(a, b)| a == b).count();
+
+    let parts: Vec<&str> = core::iter::repeat("..")
+        .take(base.len() - common)
+        .chain(target[common..].iter().copied())
+        .collect();
+
+    if parts.is_empty() {
+        ".".to_string()
+    } else {
+        parts.join("/")
+    }
+}
+
+/// Where a path string is anchored
+///
+/// Ordered relative paths first, then rooted ones, then drives by letter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum PathRoot {
+    /// A relative path
+    None,
+    /// Rooted at `/` (or `\`)
+    Slash,
+    /// A Windows drive, stored as its uppercase letter
+    Drive(char),
+}
+
+/// Split a path string into its root and the remainder after it
+///
+/// A drive letter is recognized with or without a following separator, so
+/// `C:` and `C:\` both yield `PathRoot::Drive('C')`.
+pub(crate) fn split_root(path: &str) -> (PathRoot, &str) {
+    let bytes = path.as_bytes();
+    if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+        return (
+            PathRoot::Drive(bytes[0].to_ascii_uppercase() as char),
+            &path[2..],
+        );
+    }
+    match path.strip_prefix(['/', '\\']) {
+        Some(rest) => (PathRoot::Slash, rest),
+        None => (PathRoot::None, path),
+    }
+}
+
+/// Compute the path of `path` relative to the directory `base`
+///
+/// Both inputs are treated as strings: `/` and `\` are both separators, empty
+/// and `.` components are ignored, and `..` components are resolved lexically
+/// without touching the filesystem. The result uses forward slashes and is `.`
+/// when the two paths are the same.
+///
+/// # Errors
+/// - [`PathError::ConstructionFailed`] if the paths have different roots, such
+///   as different drive letters or one absolute and one relative path
+/// - [`PathError::PathTraversal`] if a `..` component climbs above the root
+///
+/// # Examples
+/// ```
+/// use path_utils::make_relative;
+///
+/// assert_eq!(make_relative("/repo/src", "/repo/src/a/b.rs")?, "a/b.rs");
+/// assert_eq!(make_relative("/repo/src", "/repo/tests/it.rs")?, "../tests/it.rs");
+/// assert_eq!(make_relative("C:\\repo", "c:/repo/docs")?, "docs");
+/// assert!(make_relative("C:\\repo", "D:\\repo").is_err());
+/// # Ok::<(), path_utils::PathError>(())
+/// ```
+pub fn make_relative(base: &str, path: &str) -> Result<String> {
+    let (base_root, base_rest) = split_root(base);
+    let (path_root, path_rest) = split_root(path);
+
+    if base_root != path_root {
+        return Err(PathError::ConstructionFailed {
+            message: format!(
+                "No relative path from {} to {}: different roots",
+                base, path
+            ),
+        });
+    }
+
+    let base_components = resolve_dot_segments(
+        base_rest.split(['/', '\\']).filter(|s| !s.is_empty()),
+    )
+    .ok_or_else(|| PathError::PathTraversal {
+        path: base.to_string(),
+    })?;
+    let path_components = resolve_dot_segments(
+        path_rest.split(['/', '\\']).filter(|s| !s.is_empty()),
+    )
+    .ok_or_else(|| PathError::PathTraversal {
+        path: path.to_string(),
+    })?;
+
+    Ok(relative_path_between(&base_components, &path_components))
+}
+
+/// Check whether `ancestor` lexically contains `descendant`
+///
+/// Both paths are compared the way [`make_relative`] reads them: `/` and `\`
+/// are separators, empty and `.` components are ignored, and `..` is resolved
+/// lexically, so `a/b/../../etc` is not inside `a`. Components are compared
+/// whole, so `a/b` contains `a/b/c` but not `a/bc`. A path is not its own
+/// ancestor; check equality separately for an inclusive test. Paths with
+/// different roots, or with a `..` that climbs above the root, are never
+/// related. Nothing is resolved on disk, so symlinks are not considered.
+///
+/// # Examples
+/// ```
+/// use path_utils::is_ancestor;
+///
+/// assert!(is_ancestor("a/b", "a/b/c"));
+/// assert!(!is_ancestor("a/b", "a/bc"));
+/// assert!(!is_ancestor("a/b", "a/b"));
+/// ```
+pub fn is_ancestor(ancestor: &str, descendant: &str) -> bool {
+    let (ancestor_root, ancestor_rest) = split_root(ancestor);
+    let (descendant_root, descendant_rest) = split_root(descendant);
+    if ancestor_root != descendant_root {
+        return false;
+    }
+
+    let ancestor = resolve_dot_segments(ancestor_rest.split(['/', '\\']).filter(|s| !s.is_empty()));
+    let descendant =
+        resolve_dot_segments(descendant_rest.split(['/', '\\']).filter(|s| !s.is_empty()));
+    match (ancestor, descendant) {
+        (Some(ancestor), Some(descendant)) => {
+            descendant.len() > ancestor.len() && descendant.starts_with(&ancestor)
+        }
+        _ => false,
+    }
+}
+
+/// Return the part of `path` below `root`, after resolving `.` and `..` lexically
+///
+/// `path` is read the same way as by [`is_ancestor`], including `root` itself,
+/// and its `.` and `..` components are resolved without touching the
+/// filesystem, which makes this suitable for sandboxing entries in memory
+/// (for example while planning an archive extraction). The remainder is
+/// returned with forward slashes; a path that resolves to `root` itself gives
+/// an empty string.
+///
+/// # Errors
+/// Returns [`PathError::PathTraversal`] if `path` resolves to somewhere outside
+/// `root`, has a different root (such as another drive), or if either path
+/// climbs above its own starting point.
+///
+/// # Examples
+/// ```
+/// use path_utils::contained_within;
+///
+/// assert_eq!(contained_within("repo", "repo/a/../b").unwrap(), "b");
+/// assert_eq!(contained_within("repo", "repo").unwrap(), "");
+/// assert!(contained_within("repo", "repo/../etc").is_err());
+/// ```
+pub fn contained_within(root: &str, path: &str) -> Result<String> {
+    let traversal = || PathError::PathTraversal {
+        path: path.to_string(),
+    };
+
+    let (root_anchor, root_rest) = split_root(root);
+    let (path_anchor, path_rest) = split_root(path);
+    if root_anchor != path_anchor {
+        return Err(traversal());
+    }
+
+    let root_components = resolve_dot_segments(
+        root_rest.split(['/', '\\']).filter(|s| !s.is_empty()),
+    )
+    .ok_or_else(|| PathError::PathTraversal {
+        path: root.to_string(),
+    })?;
+    let path_components =
+        resolve_dot_segments(path_rest.split(['/', '\\']).filter(|s| !s.is_empty()))
+            .ok_or_else(traversal)?;
+
+    match path_components.strip_prefix(root_components.as_slice()) {
+        Some(tail) => Ok(tail.join("/")),
+        None => Err(traversal()),
+    }
+}
+
+/// Remove a leading directory from a path, ignoring separator differences
+///
+/// Unlike `Path::strip_prefix`, both paths are split into components the way
+/// [`normalized_components`](crate::normalized_components) does, so `a\b/c`
+/// starts with `a//b`. Components are compared whole, so `a/b` is not a
+/// prefix of `a/bc`, and both paths must have the same root. Returns the
+/// remainder joined with `/`, an empty string when the paths are equal, or
+/// `None` when `prefix` doesn't lead `path`. `.` and `..` are compared as
+/// written; use [`contained_within`] to resolve them first.
+///
+/// # Examples
+/// ```
+/// use path_utils::strip_prefix_normalized;
+///
+/// assert_eq!(strip_prefix_normalized("a\\b/c", "a/b").as_deref(), Some("c"));
+/// assert_eq!(strip_prefix_normalized("a/bc", "a/b"), None);
+/// ```
+pub fn strip_prefix_normalized(path: &str, prefix: &str) -> Option<String> {
+    let (path_root, path_rest) = split_root(path);
+    let (prefix_root, prefix_rest) = split_root(prefix);
+    if path_root != prefix_root {
+        return None;
+    }
+
+    let mut components = normalized_components(path_rest);
+    for expected in normalized_components(prefix_rest) {
+        if components.next() != Some(expected) {
+            return None;
+        }
+    }
+    Some(components.collect::<Vec<_>>().join("/"))
+}
+
+/// Express a path relative to the current working directory, for display
+///
+/// Both the path and the working directory are canonicalized first, so the
+/// path must exist and symlinks are resolved. The result uses forward slashes,
+/// with `..` segments where the path lies outside the working directory. If the
+/// two share no common root (e.g. different drives on Windows), the canonical
+/// absolute path is returned instead.
+///
+/// # Examples
+/// ```no_run
+/// use path_utils::relative_to_cwd;
+/// use std::path::Path;
+///
+/// // With the working directory at /home/user/project
+/// assert_eq!(relative_to_cwd(Path::new("/home/user/project/src/main.rs"))?, "src/main.rs");
+/// assert_eq!(relative_to_cwd(Path::new("/home/user/other"))?, "../other");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg(feature = "std")]
+pub fn relative_to_cwd(path: &Path) -> io::Result<String> {
+    let cwd = std::env::current_dir()?.canonicalize()?;
+    let target = path.canonicalize()?;
+
+    let (cwd_root, cwd_components) = split_components(&cwd);
+    let (target_root, target_components) = split_components(&target);
+
+    if cwd_root != target_root {
+        return Ok(target.to_string_lossy().into_owned());
+    }
+
+    let base: Vec<&str> = cwd_components.iter().map(AsRef::as_ref).collect();
+    let target: Vec<&str> = target_components.iter().map(AsRef::as_ref).collect();
+    Ok(relative_path_between(&base, &target))
+}
+
+/// Split a canonical path into its root (prefix and root directory) and its
+/// normal components
+#[cfg(feature = "std")]
+fn split_components(path: &Path) -> (Vec<Component<'_>>, Vec<Cow<'_, str>>) {
+    let mut root = Vec::new();
+    let mut components = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => components.push(name.to_string_lossy()),
+            other => root.push(other),
+        }
+    }
+    (root, components)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_relative_path_between() {
+        assert_eq!(relative_path_between(&["a", "b"], &["a", "b"]), ".");
+        assert_eq!(relative_path_between(&["a"], &["a", "b", "c"]), "b/c");
+        assert_eq!(relative_path_between(&["a", "b"], &["a", "c"]), "../c");
+        assert_eq!(relative_path_between(&["a", "b"], &["x"]), "../../x");
+        assert_eq!(relative_path_between(&[], &["x", "y"]), "x/y");
+        assert_eq!(relative_path_between(&["a", "b"], &[]), "../..");
+    }
+
+    #[test]
+    fn test_split_root() {
+        assert_eq!(split_root("/a/b"), (PathRoot::Slash, "a/b"));
+        assert_eq!(split_root("\\a"), (PathRoot::Slash, "a"));
+        assert_eq!(split_root("c:\\a"), (PathRoot::Drive('C'), "\\a"));
+        assert_eq!(split_root("C:"), (PathRoot::Drive('C'), ""));
+        assert_eq!(split_root("a/b"), (PathRoot::None, "a/b"));
+        assert_eq!(split_root(""), (PathRoot::None, ""));
+    }
+
+    #[test]
+    fn test_make_relative() {
+        // Identical paths
+        assert_eq!(make_relative("/repo/src", "/repo/src").unwrap(), ".");
+        assert_eq!(make_relative("/repo/src/", "/repo//src").unwrap(), ".");
+
+        // Descendants and siblings
+        assert_eq!(
+            make_relative("/repo/src", "/repo/src/a/b.rs").unwrap(),
+            "a/b.rs"
+        );
+        assert_eq!(
+            make_relative("/repo/src", "/repo/tests/it.rs").unwrap(),
+            "../tests/it.rs"
+        );
+        assert_eq!(make_relative("/repo/src/a", "/repo").unwrap(), "../..");
+        assert_eq!(
+            make_relative("docs", "src/lib.rs").unwrap(),
+            "../src/lib.rs"
+        );
+
+        // Dot segments are resolved lexically
+        assert_eq!(make_relative("/repo/./src/..", "/repo/src").unwrap(), "src");
+        assert!(matches!(
+            make_relative("/repo", "/../etc"),
+            Err(PathError::PathTraversal { .. })
+        ));
+        assert!(matches!(
+            make_relative("..", "a"),
+            Err(PathError::PathTraversal { .. })
+        ));
+
+        // Windows drives, compared case-insensitively
+        assert_eq!(make_relative("C:\\repo", "c:/repo/docs").unwrap(), "docs");
+    }
+
+    #[test]
+    fn test_make_relative_different_roots() {
+        for (base, path) in [
+            ("C:\\repo", "D:\\repo"),
+            ("/repo", "repo"),
+            ("C:/repo", "/repo"),
+        ] {
+            assert!(
+                matches!(
+                    make_relative(base, path),
+                    Err(PathError::ConstructionFailed { .. })
+                ),
+                "expected different-root error for {:?} -> {:?}",
+                base,
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_ancestor() {
+        assert!(is_ancestor("a/b", "a/b/c"));
+        assert!(is_ancestor("a", "a/b/c/d.txt"));
+        assert!(is_ancestor("/srv", "/srv/www"));
+        assert!(is_ancestor("", "a"));
+        assert!(is_ancestor("/", "/etc"));
+
+        // The string-prefix trap
+        assert!(!is_ancestor("a/b", "a/bc"));
+        assert!(!is_ancestor("/srv/www", "/srv/www-old/index.html"));
+
+        // Equal paths, however they are written
+        assert!(!is_ancestor("a/b", "a/b"));
+        assert!(!is_ancestor("a/b/", "a//b"));
+
+        // Descendants can't be ancestors
+        assert!(!is_ancestor("a/b/c", "a/b"));
+    }
+
+    #[test]
+    fn test_is_ancestor_normalizes() {
+        // Mixed separators
+        assert!(is_ancestor("a\\b", "a/b/c"));
+        assert!(is_ancestor("C:\\repo", "c:/repo/src"));
+
+        // Dot segments are resolved before comparing
+        assert!(is_ancestor("a/./b", "a/b/c"));
+        assert!(!is_ancestor("a", "a/b/../../etc"));
+        assert!(is_ancestor("a", "a/x/../y"));
+        assert!(!is_ancestor("..", "../x"));
+
+        // Different roots are unrelated
+        assert!(!is_ancestor("/a", "a/b"));
+        assert!(!is_ancestor("a", "/a/b"));
+        assert!(!is_ancestor("C:/a", "D:/a/b"));
+    }
+
+    #[test]
+    fn test_contained_within() {
+        assert_eq!(contained_within("repo", "repo/a/../b").unwrap(), "b");
+        assert_eq!(
+            contained_within("repo", "repo/src/main.rs").unwrap(),
+            "src/main.rs"
+        );
+        assert_eq!(
+            contained_within("/srv/www", "\\srv\\www\\./index.html").unwrap(),
+            "index.html"
+        );
+        assert_eq!(contained_within("", "a/b").unwrap(), "a/b");
+
+        // Leaving and re-entering the root resolves lexically
+        assert_eq!(contained_within("repo", "repo/../repo/x").unwrap(), "x");
+
+        // The root itself
+        assert_eq!(contained_within("repo", "repo").unwrap(), "");
+        assert_eq!(contained_within("repo/", "repo/a/..").unwrap(), "");
+    }
+
+    #[test]
+    fn test_contained_within_escapes() {
+        for (root, path) in [
+            ("repo", "repo/../etc"),
+            ("repo", "repo/a/../../etc/passwd"),
+            ("repo", "repository/x"),
+            ("repo", "other"),
+            ("repo/sub", "repo"),
+            ("", "../x"),
+            ("/srv", "srv/x"),
+            ("C:/repo", "D:/repo/x"),
+            ("../repo", "../repo/x"),
+        ] {
+            assert!(
+                matches!(
+                    contained_within(root, path),
+                    Err(PathError::PathTraversal { .. })
+                ),
+                "{:?} should not be within {:?}",
+                path,
+                root
+            );
+        }
+    }
+
+    #[test]
+    fn test_strip_prefix_normalized() {
+        assert_eq!(
+            strip_prefix_normalized("a\\b/c", "a/b").as_deref(),
+            Some("c")
+        );
+        assert_eq!(
+            strip_prefix_normalized("src//bin\\tools/x.rs", "src/bin/").as_deref(),
+            Some("tools/x.rs")
+        );
+        assert_eq!(
+            strip_prefix_normalized("/srv/www/index.html", "\\srv").as_deref(),
+            Some("www/index.html")
+        );
+        assert_eq!(strip_prefix_normalized("a/b", "").as_deref(), Some("a/b"));
+
+        // Exact match leaves nothing
+        assert_eq!(strip_prefix_normalized("a/b", "a//b/").as_deref(), Some(""));
+    }
+
+    #[test]
+    fn test_strip_prefix_normalized_not_a_prefix() {
+        // The string-prefix trap
+        assert_eq!(strip_prefix_normalized("a/bc", "a/b"), None);
+        assert_eq!(strip_prefix_normalized("a/bc/d", "a/b"), None);
+
+        assert_eq!(strip_prefix_normalized("a/b", "a/b/c"), None);
+        assert_eq!(strip_prefix_normalized("x/y", "a"), None);
+        assert_eq!(strip_prefix_normalized("/a/b", "a"), None);
+        assert_eq!(strip_prefix_normalized("C:/a/b", "D:/a"), None);
+
+        // Dot segments are not resolved
+        assert_eq!(strip_prefix_normalized("a/./b/c", "a/b"), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_relative_to_cwd_subdirectory() {
+        // Unit tests run with the crate root as the working directory
+        let file = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("lib.rs");
+        assert_eq!(relative_to_cwd(&file).unwrap(), "src/lib.rs");
+
+        let cwd = std::env::current_dir().unwrap();
+        assert_eq!(relative_to_cwd(&cwd).unwrap(), ".");
+        assert_eq!(relative_to_cwd(Path::new("src")).unwrap(), "src");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_relative_to_cwd_unrelated_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_canonical = temp_dir.path().canonicalize().unwrap();
+
+        let relative = relative_to_cwd(temp_dir.path()).unwrap();
+
+        // Either a relative path that resolves back to the temp dir, or the
+        // absolute path when there is no common root
+        let resolved = std::env::current_dir()
+            .unwrap()
+            .join(&relative)
+            .canonicalize()
+            .unwrap();
+        assert_eq!(resolved, temp_canonical);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_relative_to_cwd_missing_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let missing = temp_dir.path().join("does-not-exist");
+        assert_eq!(
+            relative_to_cwd(&missing).unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! Non-failing path analysis
+//!
+//! Describes what validation would find in a path without rejecting it, for
+//! auditing tools that scan whole manifests and report on every entry.
+
+use crate::normalize::{normalized_components, path_depth};
+use crate::options::ValidationOptions;
+use crate::reserved::find_reserved_component;
+use crate::validate::{has_traversal, is_absolute_for, is_invalid_char};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// A summary of a path's properties, produced by [`analyze_path`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Whether the path is rooted, a UNC path, or starts with a drive letter
+    pub is_absolute: bool,
+    /// Whether validation would reject the path as traversal
+    pub has_traversal: bool,
+    /// The first component matching a reserved name, if any
+    pub reserved_component: Option<String>,
+    /// Each rejected character in the path, once, in order of first appearance
+    pub invalid_chars: Vec<char>,
+    /// The number of non-empty components, as counted by [`path_depth`](crate::path_depth)
+    pub depth: usize,
+    /// The length of the path in bytes
+    pub byte_length: usize,
+}
+
+/// Describe a path's security-relevant properties without failing
+///
+/// Where [`validate_path_with`](crate::validate_path_with) stops at the first
+/// problem, this always returns a full report, for tools that audit a list of
+/// paths and want to show what is wrong with each. The fields follow
+/// `options`:
+///
+/// - `is_absolute` ignores drive letters when the target platform is Unix
+/// - `has_traversal` applies the same rule as validation: a `..` component,
+///   or with Windows rules `..` followed by dots and spaces
+/// - `reserved_component` honours the configured reserved names, and is always
+///   `None` when reserved names are allowed
+/// - `invalid_chars` lists the characters the character rules would reject:
+///   control characters, invisible format characters, and forbidden
+///   characters including any extras. A `:` naming an NTFS stream is not
+///   reported, since it depends on where the colon appears.
+///
+/// # Examples
+/// ```
+/// use path_utils::{analyze_path, ValidationOptions};
+///
+/// let report = analyze_path("/srv/../aux<1>/CON.txt", &ValidationOptions::new());
+/// assert!(report.is_absolute);
+/// assert!(report.has_traversal);
+/// assert_eq!(report.reserved_component.as_deref(), Some("CON.txt"));
+/// assert_eq!(report.invalid_chars, ['<', '>']);
+/// assert_eq!(report.depth, 4);
+/// assert_eq!(report.byte_length, 22);
+/// ```
+pub fn analyze_path(path: &str, options: &ValidationOptions) -> ValidationReport {
+    let reserved_component = if options.allow_reserved_names {
+        None
+    } else {
+        find_reserved_component(
+            normalized_components(path),
+            &options.reserved_names,
+            options.windows_rules(),
+        )
+        .map(ToString::to_string)
+    };
+
+    let mut invalid_chars = Vec::new();
+    for c in path.chars() {
+        if is_invalid_char(c, options) && !invalid_chars.contains(&c) {
+            invalid_chars.push(c);
+        }
+    }
+
+    ValidationReport {
+        is_absolute: is_absolute_for(path, options),
+        has_traversal: has_traversal(path, options),
+        reserved_component,
+        invalid_chars,
+        depth: path_depth(path),
+        byte_length: path.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::PathError;
+    use crate::options::TargetPlatform;
+    use crate::reserved::ReservedNames;
+    use crate::validate::validate_path;
+
+    #[test]
+    fn test_analyze_clean_path() {
+        assert_eq!(
+            analyze_path("src/main.rs", &ValidationOptions::new()),
+            ValidationReport {
+                depth: 2,
+                byte_length: 11,
+                ..ValidationReport::default()
+            }
+        );
+        assert_eq!(
+            analyze_path("", &ValidationOptions::new()),
+            ValidationReport::default()
+        );
+    }
+
+    #[test]
+    fn test_analyze_traversal() {
+        let options = ValidationOptions::new();
+        let report = analyze_path("../../etc/passwd", &options);
+        assert!(report.has_traversal);
+        assert!(!report.is_absolute);
+        assert_eq!(report.depth, 4);
+
+        // Only a whole `..` component is traversal
+        assert!(!analyze_path("v1..2/notes", &options).has_traversal);
+        assert!(analyze_path("a\\..\\b", &options).has_traversal);
+        assert!(analyze_path("a/.. /b", &options).has_traversal);
+
+        // The report agrees with validation
+        for path in ["v1..2/notes", "a/../b", "a/.../b", "..x", "x.."] {
+            assert_eq!(
+                analyze_path(path, &options).has_traversal,
+                matches!(validate_path(path), Err(PathError::PathTraversal { .. })),
+                "{:?}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_analyze_absolute() {
+        let options = ValidationOptions::new();
+        for path in ["/etc/passwd", "C:\\Windows", "\\\\server\\share\\x"] {
+            assert!(analyze_path(path, &options).is_absolute, "{:?}", path);
+        }
+        assert!(!analyze_path("etc/passwd", &options).is_absolute);
+
+        // A drive letter is just a name on Unix
+        let unix = ValidationOptions::new().target_platform(TargetPlatform::Unix);
+        assert!(!analyze_path("C:\\Windows", &unix).is_absolute);
+        assert!(analyze_path("/etc", &unix).is_absolute);
+    }
+
+    #[test]
+    fn test_analyze_reserved_component() {
+        let options = ValidationOptions::new();
+        assert_eq!(
+            analyze_path("lib/aux.js/x", &options)
+                .reserved_component
+                .as_deref(),
+            Some("aux.js")
+        );
+
+        let allowed = ValidationOptions::new().allow_reserved_names(true);
+        assert_eq!(
+            analyze_path("lib/aux.js", &allowed).reserved_component,
+            None
+        );
+
+        let mut names = ReservedNames::with_defaults();
+        names.add(".htaccess");
+        let custom = ValidationOptions::new().reserved_names(names);
+        assert_eq!(
+            analyze_path("www\\.htaccess", &custom)
+                .reserved_component
+                .as_deref(),
+            Some(".htaccess")
+        );
+    }
+
+    #[test]
+    fn test_analyze_invalid_chars() {
+        let options = ValidationOptions::new();
+        let report = analyze_path("a<b>/c\0d<e\u{202E}", &options);
+        assert_eq!(report.invalid_chars, ['<', '>', '\0', '\u{202E}']);
+        assert_eq!(report.byte_length, "a<b>/c\0d<e\u{202E}".len());
+
+        // The lists follow the options
+        let unix = ValidationOptions::new().target_platform(TargetPlatform::Unix);
+        assert!(analyze_path("a<b>", &unix).invalid_chars.is_empty());
+        let extra = ValidationOptions::new().extra_forbidden_chars(&['#']);
+        assert_eq!(analyze_path("a#b", &extra).invalid_chars, ['#']);
+    }
+}
@@ -0,0 +1,369 @@
+//! Reserved filename handling
+//!
+//! A single shared definition of which file names are off-limits, consulted by
+//! both validation and sanitization so the two can't drift apart.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Windows device names that cannot be used as file names on any Windows filesystem
+const WINDOWS_RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// A set of forbidden file names, compared case-insensitively
+///
+/// A path component matches a reserved name when it is equal to the name, or
+/// when its base name (the part before the first `.`) is, mirroring how Windows
+/// treats `CON.txt` as the `CON` device. This means custom entries can be either
+/// device-style basenames (`CLOCK$`) or full file names (`web.config`).
+///
+/// # Examples
+/// ```
+/// use path_utils::{validate_path_with, ReservedNames, ValidationOptions};
+///
+/// let mut names = ReservedNames::with_defaults();
+/// names.add(".htaccess").add("web.config");
+/// let options = ValidationOptions::new().reserved_names(names);
+///
+/// assert!(validate_path_with("public/.htaccess", &options).is_err());
+/// assert!(validate_path_with("public/Web.Config", &options).is_err());
+/// assert!(validate_path_with("public/CON.txt", &options).is_err());
+/// assert!(validate_path_with("public/web.config.bak", &options).is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReservedNames {
+    /// Whether the Windows device names are included, checked by [`is_reserved_basename`]
+    windows_devices: bool,
+    /// Custom names, stored uppercased
+    names: Vec<String>,
+}
+
+impl ReservedNames {
+    /// The Windows device names (`CON`, `PRN`, `AUX`, `NUL`, `COM1`-`COM9`, `LPT1`-`LPT9`)
+    pub fn with_defaults() -> Self {
+        Self {
+            windows_devices: true,
+            names: Vec::new(),
+        }
+    }
+
+    /// Add a name to the set
+    pub fn add(&mut self, name: &str) -> &mut Self {
+        self.names.push(name.to_uppercase());
+        self
+    }
+
+    /// Remove every name from the set, including the defaults
+    pub fn clear(&mut self) -> &mut Self {
+        self.windows_devices = false;
+        self.names.clear();
+        self
+    }
+
+    /// Check whether a single path component matches a reserved name
+    ///
+    /// Trailing dots and spaces are ignored, since Windows strips them when
+    /// resolving a name: `CON.`, `CON `, `CON .txt` and `con.txt.` all open the
+    /// `CON` device.
+    pub fn matches(&self, component: &str) -> bool {
+        self.matches_with(component, true)
+    }
+
+    /// Check a component, skipping the Windows device names unless `windows_devices` is set
+    ///
+    /// Custom names always apply, so a Unix target still honours `.htaccess`.
+    pub(crate) fn matches_with(&self, component: &str, windows_devices: bool) -> bool {
+        if windows_devices && self.windows_devices && is_reserved_basename(component.as_bytes()) {
+            return true;
+        }
+
+        let component = trim_trailing_dots_and_spaces(component);
+        let base_name = component
+            .split('.')
+            .next()
+            .unwrap_or("")
+            .trim_end_matches(' ');
+        self.names
+            .iter()
+            .any(|name| eq_reserved(name, component) || eq_reserved(name, base_name))
+    }
+}
+
+/// Compare a candidate against an uppercased reserved name, ignoring case
+///
+/// This runs for every component of every path, so the common all-ASCII case
+/// is compared without allocating. Anything else falls back to a full Unicode
+/// uppercase conversion, since non-ASCII letters can uppercase to ASCII ones
+/// (`ß` becomes `SS`).
+fn eq_reserved(name: &str, candidate: &str) -> bool {
+    if name.is_ascii() && candidate.is_ascii() {
+        name.eq_ignore_ascii_case(candidate)
+    } else {
+        candidate.to_uppercase() == name
+    }
+}
+
+/// Check whether a path component names a Windows device, usable in `const` contexts
+///
+/// Applies the same rules as [`ReservedNames::matches`] with the default names:
+/// trailing dots and spaces are ignored, the base name before the first `.` is
+/// compared, and ASCII letters match regardless of case. Since `const fn`
+/// can't iterate or allocate, this is written as plain byte loops; the device
+/// names are all ASCII, so no Unicode case folding is needed.
+///
+/// # Examples
+/// ```
+/// use path_utils::is_reserved_basename;
+///
+/// const IS_RESERVED: bool = is_reserved_basename(b"con.txt");
+/// assert!(IS_RESERVED);
+/// assert!(is_reserved_basename(b"LPT1. "));
+/// assert!(!is_reserved_basename(b"CONSOLE"));
+/// ```
+pub const fn is_reserved_basename(name: &[u8]) -> bool {
+    is_reserved_basename_in(name, 0, name.len())
+}
+
+/// [`is_reserved_basename`] for `bytes[start..end]`
+///
+/// Takes the bounds separately because slicing by a range isn't possible in a
+/// `const fn` on the supported Rust versions.
+pub(crate) const fn is_reserved_basename_in(bytes: &[u8], start: usize, end: usize) -> bool {
+    let mut end = end;
+    while end > start && (bytes[end - 1] == b'.' || bytes[end - 1] == b' ') {
+        end -= 1;
+    }
+
+    let mut base_end = start;
+    while base_end < end && bytes[base_end] != b'.' {
+        base_end += 1;
+    }
+    while base_end > start && bytes[base_end - 1] == b' ' {
+        base_end -= 1;
+    }
+
+    let mut index = 0;
+    while index < WINDOWS_RESERVED_NAMES.len() {
+        if eq_ascii_uppercase(
+            WINDOWS_RESERVED_NAMES[index].as_bytes(),
+            bytes,
+            start,
+            base_end,
+        ) {
+            return true;
+        }
+        index += 1;
+    }
+    false
+}
+
+/// Compare `candidate[start..end]` against an uppercase ASCII name
+const fn eq_ascii_uppercase(name: &[u8], candidate: &[u8], start: usize, end: usize) -> bool {
+    if name.len() != end - start {
+        return false;
+    }
+
+    let mut index = 0;
+    while index < name.len() {
+        if candidate[start + index].to_ascii_uppercase() != name[index] {
+            return false;
+        }
+        index += 1;
+    }
+    true
+}
+
+impl Default for ReservedNames {
+    fn default() -> Self {
+        Self::with_defaults()
+    }
+}
+
+/// Strip the trailing dots and spaces that Windows ignores in file names
+fn trim_trailing_dots_and_spaces(component: &str) -> &str {
+    component.trim_end_matches(['.', ' '])
+}
+
+/// Check whether a component consists only of dots and whitespace
+///
+/// Windows strips trailing dots and spaces, so such a name resolves to nothing
+/// (or to its parent directory), and names made of other whitespace are just
+/// as invisible in a listing. Empty components (from `a//b`) and the `.`
+/// current-directory component are not considered dots-and-whitespace names.
+pub(crate) fn is_dots_and_whitespace(component: &str) -> bool {
+    !component.is_empty()
+        && component != "."
+        && component.chars().all(|c| c == '.' || c.is_whitespace())
+}
+
+/// Find the first component that matches a reserved name
+///
+/// The Windows device names are only considered when `windows_devices` is set.
+pub(crate) fn find_reserved_component<'a>(
+    components: impl IntoIterator<Item = &'a str>,
+    names: &ReservedNames,
+    windows_devices: bool,
+) -> Option<&'a str> {
+    components
+        .into_iter()
+        .find(|component| names.matches_with(component, windows_devices))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_names() {
+        let names = ReservedNames::default();
+        for name in WINDOWS_RESERVED_NAMES {
+            assert!(names.matches(name));
+            assert!(names.matches(&name.to_lowercase()));
+            assert!(names.matches(&format!("{}.txt", name)));
+        }
+
+        // Any mix of case matches
+        for component in ["CoM1", "Lpt9", "cOn.TxT", "nUl", "aUx.tar.gz", "pRn . "] {
+            assert!(names.matches(component), "{:?} should match", component);
+        }
+
+        assert!(!names.matches("CONSOLE"));
+        assert!(!names.matches("COM10"));
+        assert!(!names.matches("my.con"));
+        assert!(!names.matches(""));
+    }
+
+    #[test]
+    fn test_add_custom_names() {
+        let mut names = ReservedNames::with_defaults();
+        names.add("CLOCK$").add("web.config");
+
+        assert!(names.matches("CLOCK$"));
+        assert!(names.matches("clock$.txt"));
+        assert!(names.matches("web.config"));
+        assert!(names.matches("WEB.CONFIG"));
+        assert!(!names.matches("web.config.bak"));
+        assert!(!names.matches("web"));
+
+        // Non-ASCII names are compared with full Unicode case mapping
+        names.add("straße.ini");
+        assert!(names.matches("STRASSE.INI"));
+        assert!(names.matches("Straße.ini"));
+
+        // Defaults are still present
+        assert!(names.matches("CON"));
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut names = ReservedNames::with_defaults();
+        names.clear();
+        assert!(!names.matches("CON"));
+        assert!(!names.matches("lpt1.txt"));
+
+        names.add("secret");
+        assert!(names.matches("Secret.TXT"));
+        assert!(!names.matches("NUL"));
+    }
+
+    #[test]
+    fn test_trailing_dots_and_spaces() {
+        let names = ReservedNames::default();
+        assert!(names.matches("CON "));
+        assert!(names.matches("CON."));
+        assert!(names.matches("nul..."));
+        assert!(names.matches("lpt1.txt."));
+        assert!(names.matches("Aux . ."));
+        assert!(names.matches("con .txt"));
+        assert!(!names.matches("CONX."));
+    }
+
+    #[test]
+    fn test_is_dots_and_whitespace() {
+        assert!(is_dots_and_whitespace("..."));
+        assert!(is_dots_and_whitespace(" "));
+        assert!(is_dots_and_whitespace(". ."));
+        assert!(is_dots_and_whitespace(".."));
+        assert!(is_dots_and_whitespace("\t"));
+        assert!(is_dots_and_whitespace(" .\u{3000}"));
+
+        assert!(!is_dots_and_whitespace(""));
+        assert!(!is_dots_and_whitespace("."));
+        assert!(!is_dots_and_whitespace("...foo"));
+        assert!(!is_dots_and_whitespace(" a "));
+    }
+
+    #[test]
+    fn test_is_reserved_basename_matches_string_rules() {
+        let names = ReservedNames::default();
+        for name in WINDOWS_RESERVED_NAMES {
+            let lower = name.to_lowercase();
+            let mixed: String = name
+                .chars()
+                .enumerate()
+                .map(|(i, c)| {
+                    if i % 2 == 0 {
+                        c
+                    } else {
+                        c.to_ascii_lowercase()
+                    }
+                })
+                .collect();
+
+            for component in [
+                name.to_string(),
+                lower.clone(),
+                mixed.clone(),
+                format!("{}.txt", mixed),
+                format!("{} .tar.gz", lower),
+                format!("{}. .", name),
+                format!("{}X", name),
+                format!("X{}", lower),
+                format!("{}0.txt", mixed),
+            ] {
+                assert_eq!(
+                    is_reserved_basename(component.as_bytes()),
+                    names.matches(&component),
+                    "{:?}",
+                    component
+                );
+            }
+        }
+
+        for component in ["", ".", " ", "...", "CONSOLE", "COM10", "my.con", "ÇON"] {
+            assert_eq!(
+                is_reserved_basename(component.as_bytes()),
+                names.matches(component),
+                "{:?}",
+                component
+            );
+        }
+    }
+
+    #[test]
+    fn test_find_reserved_component() {
+        let names = ReservedNames::default();
+        assert_eq!(
+            find_reserved_component("lib/aux.js".split('/'), &names, true),
+            Some("aux.js")
+        );
+        assert_eq!(
+            find_reserved_component("src/main.rs".split('/'), &names, true),
+            None
+        );
+
+        // Without the device names, only custom names are found
+        let mut names = ReservedNames::with_defaults();
+        names.add(".htaccess");
+        assert_eq!(
+            find_reserved_component("lib/aux.js".split('/'), &names, false),
+            None
+        );
+        assert_eq!(
+            find_reserved_component("www/.htaccess".split('/'), &names, false),
+            Some(".htaccess")
+        );
+    }
+}
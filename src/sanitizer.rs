@@ -0,0 +1,128 @@
+//! Reusable path sanitizer bound to a fixed root
+//!
+//! [`safe_repository_join`](crate::safe_repository_join) canonicalizes its
+//! workdir on every call. [`PathSanitizer`] does that once up front, which
+//! matters when joining many files into the same repository.
+
+use crate::error::Result;
+use crate::normalize::{canonicalize_workdir, join_within_root, sanitize_directory_file_path_with};
+use crate::options::ValidationOptions;
+use std::path::{Path, PathBuf};
+
+/// Joins untrusted file paths onto a fixed, pre-canonicalized root
+///
+/// # Examples
+/// ```
+/// use path_utils::PathSanitizer;
+/// use tempfile::TempDir;
+///
+/// let temp_dir = TempDir::new().unwrap();
+/// let sanitizer = PathSanitizer::new(temp_dir.path()).unwrap();
+///
+/// for file in ["/args.js", "lib/generator.js"] {
+///     let path = sanitizer.join("testing/framework", file).unwrap();
+///     assert!(path.starts_with(sanitizer.root()));
+/// }
+/// assert!(sanitizer.join("testing", "../../etc/passwd").is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct PathSanitizer {
+    root: PathBuf,
+    options: ValidationOptions,
+}
+
+impl PathSanitizer {
+    /// Create a sanitizer rooted at `workdir`, canonicalizing it once
+    ///
+    /// Fails with [`PathError::IoError`](crate::PathError::IoError) if the
+    /// directory doesn't exist or can't be resolved.
+    pub fn new<P: AsRef<Path>>(workdir: P) -> Result<Self> {
+        Ok(Self {
+            root: canonicalize_workdir(workdir.as_ref())?,
+            options: ValidationOptions::default(),
+        })
+    }
+
+    /// Use a custom validation policy when sanitizing file paths
+    pub fn with_options(mut self, options: ValidationOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// The canonical root directory
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Join a target directory and a file path from directory content onto the root
+    ///
+    /// Equivalent to [`safe_repository_join`](crate::safe_repository_join) with
+    /// this sanitizer's root, using the configured validation policy.
+    pub fn join<P: AsRef<Path>>(&self, target_path: P, file_path: &str) -> Result<PathBuf> {
+        let sanitized_file_path = sanitize_directory_file_path_with(file_path, &self.options)?;
+        join_within_root(&self.root, target_path.as_ref(), &sanitized_file_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::PathError;
+    use crate::normalize::safe_repository_join;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_join_many() {
+        let temp_dir = TempDir::new().unwrap();
+        let sanitizer = PathSanitizer::new(temp_dir.path()).unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        assert_eq!(sanitizer.root(), root);
+
+        for i in 0..100 {
+            let file = format!("/dir{}/file{}.txt", i % 7, i);
+            let joined = sanitizer.join("target", &file).unwrap();
+            assert_eq!(
+                joined,
+                root.join(format!("target/dir{}/file{}.txt", i % 7, i))
+            );
+            assert_eq!(
+                joined,
+                safe_repository_join(temp_dir.path(), "target", &file).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_join_rejects_unsafe_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let sanitizer = PathSanitizer::new(temp_dir.path()).unwrap();
+
+        assert!(matches!(
+            sanitizer.join("test", "../../../etc/passwd"),
+            Err(PathError::PathTraversal { .. })
+        ));
+        assert!(sanitizer.join("test", "file<script>").is_err());
+        assert!(sanitizer.join("test", "").is_err());
+        assert!(sanitizer.join("../outside", "file.txt").is_err());
+    }
+
+    #[test]
+    fn test_join_with_options() {
+        let temp_dir = TempDir::new().unwrap();
+        let sanitizer = PathSanitizer::new(temp_dir.path()).unwrap();
+        assert!(sanitizer.join("logs", "CON").is_err());
+
+        let sanitizer = sanitizer.with_options(ValidationOptions::new().allow_reserved_names(true));
+        assert!(sanitizer.join("logs", "CON").is_ok());
+    }
+
+    #[test]
+    fn test_new_fails_for_missing_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let missing = temp_dir.path().join("does-not-exist");
+
+        let err = PathSanitizer::new(&missing).unwrap_err();
+        assert!(matches!(err, PathError::IoError { .. }));
+        assert!(err.to_string().contains("Cannot canonicalize workdir"));
+    }
+}
@@ -0,0 +1,73 @@
+//! Unicode-aware measurements (requires the `unicode` feature)
+//!
+//! There are three sensible ways to measure a path component, and they answer
+//! different questions:
+//! - **Bytes** (`str::len`): what filesystems limit. ext4's 255-byte name limit
+//!   and Linux's 4096-byte `PATH_MAX` are byte counts of the UTF-8 encoding.
+//! - **Chars** (`str::chars().count()`): Unicode scalar values. Rarely the right
+//!   answer on its own; it matches neither storage nor what users see.
+//! - **Graphemes** ([`grapheme_count`]): what a user perceives as one character,
+//!   so `e` plus a combining accent counts once. Use this for user-facing rules
+//!   such as "names may be at most 20 characters".
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Count the extended grapheme clusters in a path component
+///
+/// # Examples
+/// ```
+/// use path_utils::grapheme_count;
+///
+/// // "café" spelled with a combining acute accent
+/// let name = "cafe\u{301}";
+/// assert_eq!(name.len(), 6);
+/// assert_eq!(name.chars().count(), 5);
+/// assert_eq!(grapheme_count(name), 4);
+/// ```
+pub fn grapheme_count(component: &str) -> usize {
+    component.graphemes(true).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::ValidationOptions;
+    use crate::validate::validate_path_with;
+
+    #[test]
+    fn test_grapheme_count() {
+        assert_eq!(grapheme_count(""), 0);
+        assert_eq!(grapheme_count("abc"), 3);
+        assert_eq!(grapheme_count("e\u{301}\u{323}"), 1);
+        assert_eq!(grapheme_count("🇳🇱"), 1);
+        assert_eq!(grapheme_count("👩‍👩‍👧"), 1);
+        assert_eq!(grapheme_count("\r\n"), 1);
+    }
+
+    #[test]
+    fn test_max_component_graphemes() {
+        let options = ValidationOptions::new().max_component_graphemes(Some(5));
+
+        // Five graphemes, but ten chars and fifteen bytes
+        let at_limit = "a\u{301}".repeat(5);
+        assert_eq!(at_limit.chars().count(), 10);
+        assert!(validate_path_with(&at_limit, &options).is_ok());
+
+        let over_limit = format!("{}e\u{301}", at_limit);
+        assert!(matches!(
+            validate_path_with(&over_limit, &options),
+            Err(crate::PathError::ValidationFailed {
+                rule: "max_component_graphemes",
+                ..
+            })
+        ));
+
+        // The limit applies per component, not to the whole path
+        let nested = format!("{0}/{0}\\{0}", at_limit);
+        assert!(validate_path_with(&nested, &options).is_ok());
+        assert!(validate_path_with(format!("{}/{}", at_limit, over_limit), &options).is_err());
+
+        // Unlimited by default
+        assert!(validate_path_with("abcdef".repeat(40), &ValidationOptions::new()).is_ok());
+    }
+}
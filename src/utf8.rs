@@ -0,0 +1,132 @@
+//! `camino` integration (requires the `camino` feature)
+//!
+//! [`Utf8Path`] is guaranteed to be valid UTF-8, so these functions work on the
+//! string directly instead of going through `to_string_lossy`, and return
+//! [`Utf8PathBuf`] so callers never have to convert back.
+
+use crate::error::{PathError, Result};
+use crate::normalize::{
+    canonicalize_workdir, join_within_root, normalize_path_str, sanitize_directory_file_path,
+};
+use camino::{Utf8Path, Utf8PathBuf};
+
+/// Normalize a UTF-8 path
+///
+/// Performs the same transformation as [`normalize_path_str`].
+///
+/// # Examples
+/// ```
+/// use camino::{Utf8Path, Utf8PathBuf};
+/// use path_utils::normalize_utf8;
+///
+/// assert_eq!(normalize_utf8(Utf8Path::new("a//b\\c/")), Utf8PathBuf::from("a/b/c"));
+/// ```
+pub fn normalize_utf8(path: &Utf8Path) -> Utf8PathBuf {
+    Utf8PathBuf::from(normalize_path_str(path.as_str()))
+}
+
+/// Safe repository path joining for UTF-8 paths
+///
+/// Performs the same sanitization, canonicalization and containment checks as
+/// [`safe_repository_join`](crate::safe_repository_join).
+///
+/// # Errors
+/// As for [`safe_repository_join`](crate::safe_repository_join), plus
+/// [`PathError::ConstructionFailed`] if canonicalizing the workdir resolves a
+/// symlink to a path that is not valid UTF-8.
+///
+/// # Examples
+/// ```
+/// use camino::{Utf8Path, Utf8PathBuf};
+/// use path_utils::safe_repository_join_utf8;
+/// use tempfile::TempDir;
+///
+/// let temp_dir = TempDir::new().unwrap();
+/// let workdir = Utf8Path::from_path(temp_dir.path()).unwrap();
+///
+/// let result = safe_repository_join_utf8(workdir, Utf8Path::new("src"), "/main.rs").unwrap();
+/// assert!(result.ends_with("src/main.rs"));
+/// ```
+pub fn safe_repository_join_utf8(
+    workdir: &Utf8Path,
+    target_path: &Utf8Path,
+    file_path: &str,
+) -> Result<Utf8PathBuf> {
+    // Sanitize the file path from directory content
+    let sanitized_file_path = sanitize_directory_file_path(file_path)?;
+
+    let workdir_canonical = canonicalize_workdir(workdir.as_std_path())?;
+    let final_path = join_within_root(
+        &workdir_canonical,
+        target_path.as_std_path(),
+        &sanitized_file_path,
+    )?;
+
+    Utf8PathBuf::from_path_buf(final_path).map_err(|path| PathError::ConstructionFailed {
+        message: format!("Path construction failed - result is not UTF-8: {:?}", path),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::normalize::{normalize_path_buf, safe_repository_join};
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_normalize_utf8_matches_normalize_path_buf() {
+        for path in [
+            "a//b",
+            "a\\b\\c",
+            "/leading/slash/",
+            "",
+            "café//ünïcode\\名前",
+        ] {
+            assert_eq!(
+                normalize_utf8(Utf8Path::new(path)).as_std_path(),
+                normalize_path_buf(path),
+                "mismatch for {:?}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_safe_repository_join_utf8_matches_safe_repository_join() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("docs")).unwrap();
+        let workdir = Utf8Path::from_path(temp_dir.path()).unwrap();
+
+        for (target, file) in [
+            ("testing/framework", "/args.js"),
+            ("docs", "guides\\intro.md"),
+            ("", "café/名前.txt"),
+        ] {
+            let utf8 = safe_repository_join_utf8(workdir, Utf8Path::new(target), file).unwrap();
+            let std = safe_repository_join(temp_dir.path(), Path::new(target), file).unwrap();
+            assert_eq!(utf8.as_std_path(), std);
+        }
+    }
+
+    #[test]
+    fn test_safe_repository_join_utf8_rejects_unsafe_input() {
+        let temp_dir = TempDir::new().unwrap();
+        let workdir = Utf8Path::from_path(temp_dir.path()).unwrap();
+        let target = Utf8Path::new("target");
+
+        assert!(matches!(
+            safe_repository_join_utf8(workdir, target, "../../etc/passwd"),
+            Err(PathError::PathTraversal { .. })
+        ));
+        assert!(matches!(
+            safe_repository_join_utf8(workdir, target, ""),
+            Err(PathError::EmptyPath)
+        ));
+        assert!(matches!(
+            safe_repository_join_utf8(Utf8Path::new("/does/not/exist"), target, "a.txt"),
+            Err(PathError::IoError { .. })
+        ));
+    }
+}
@@ -2,13 +2,39 @@
 //!
 //! Additional validation functions for path safety checks.
 
+use crate::classify::{classify, file_extension, PathKind};
 use crate::error::{PathError, Result};
-use std::path::Path;
+use crate::git::is_dotgit_confusable;
+#[cfg(feature = "std")]
+use crate::normalize::normalize_path_str;
+use crate::normalize::{normalized_components, path_depth};
+use crate::options::{ExtensionPolicy, PathLimits, ValidationOptions};
+use crate::reserved::{find_reserved_component, is_dots_and_whitespace};
+use alloc::string::ToString;
+use alloc::vec::Vec;
+#[cfg(feature = "rayon")]
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
+
+/// Characters Windows refuses in file names, besides control characters and separators
+pub(crate) const FORBIDDEN_CHARS: [char; 6] = ['<', '>', '|', '?', '*', '"'];
+
+/// Unicode format characters that change how a path is displayed without being visible
+///
+/// The bidirectional embeddings, overrides, isolates and marks, plus the
+/// zero-width characters, the Mongolian vowel separator and the byte order mark.
+pub(crate) const DECEPTIVE_FORMAT_CHARS: [char; 18] = [
+    '\u{061C}', '\u{180E}', '\u{200B}', '\u{200C}', '\u{200D}', '\u{200E}', '\u{200F}', '\u{202A}',
+    '\u{202B}', '\u{202C}', '\u{202D}', '\u{202E}', '\u{2060}', '\u{2066}', '\u{2067}', '\u{2068}',
+    '\u{2069}', '\u{FEFF}',
+];
 
 /// Check if a path is safe for use
 ///
 /// This performs basic safety checks on a path without modifying it.
-/// Useful for validation before path operations.
+/// Useful for validation before path operations. Equivalent to
+/// `validate_path(path).is_ok()`.
 ///
 /// # Examples
 /// ```
@@ -18,117 +44,741 @@ use std::path::Path;
 /// assert!(!is_safe_path("../etc/passwd"));
 /// assert!(!is_safe_path(""));
 /// ```
-pub fn is_safe_path<P: AsRef<Path>>(path: P) -> bool {
+pub fn is_safe_path<P: AsRef<str>>(path: P) -> bool {
+    check_path(path.as_ref(), &ValidationOptions::default()).is_ok()
+}
+
+/// Check if a path given as any path-like value is safe for use
+///
+/// The [`is_safe_path`] counterpart for callers holding a `Path` or `PathBuf`.
+/// The path is converted with `to_string_lossy`, so bytes that aren't valid
+/// UTF-8 become U+FFFD. Use [`is_safe_os_path`](crate::is_safe_os_path) to
+/// check such paths exactly.
+///
+/// # Examples
+/// ```
+/// use path_utils::is_safe_path_p;
+/// use std::path::Path;
+///
+/// assert!(is_safe_path_p(Path::new("safe/path/file.txt")));
+/// assert!(!is_safe_path_p(Path::new("../etc/passwd")));
+/// ```
+#[cfg(feature = "std")]
+pub fn is_safe_path_p<P: AsRef<Path>>(path: P) -> bool {
+    is_safe_path(path.as_ref().to_string_lossy())
+}
+
+/// Validate a path and return detailed error information
+///
+/// This function performs comprehensive validation and returns specific error types
+/// for different validation failures.
+///
+/// # Examples
+/// ```
+/// use path_utils::validate_path;
+///
+/// assert!(validate_path("safe/path/file.txt").is_ok());
+/// assert!(validate_path("../etc/passwd").is_err());
+/// ```
+pub fn validate_path<P: AsRef<str>>(path: P) -> Result<()> {
+    validate_path_with(path, &ValidationOptions::default())
+}
+
+/// Validate a path given as any path-like value
+///
+/// The [`validate_path`] counterpart for callers holding a `Path` or
+/// `PathBuf`, converting it with `to_string_lossy` like [`is_safe_path_p`].
+///
+/// # Examples
+/// ```
+/// use path_utils::validate_path_p;
+/// use std::path::PathBuf;
+///
+/// assert!(validate_path_p(PathBuf::from("safe/path/file.txt")).is_ok());
+/// assert!(validate_path_p(PathBuf::from("../etc/passwd")).is_err());
+/// ```
+#[cfg(feature = "std")]
+pub fn validate_path_p<P: AsRef<Path>>(path: P) -> Result<()> {
+    validate_path(path.as_ref().to_string_lossy())
+}
+
+/// Validate a path against a configurable policy
+///
+/// Runs the same checks as [`validate_path`], adjusted by `options`. See
+/// [`ValidationOptions`] for the available knobs.
+///
+/// # Examples
+/// ```
+/// use path_utils::{validate_path_with, ValidationOptions};
+///
+/// let options = ValidationOptions::new().max_length(Some(8));
+/// assert!(validate_path_with("a/b.txt", &options).is_ok());
+/// assert!(validate_path_with("a/longer.txt", &options).is_err());
+/// ```
+pub fn validate_path_with<P: AsRef<str>>(path: P, options: &ValidationOptions) -> Result<()> {
+    check_path(path.as_ref(), options)
+}
+
+/// Validate a path given as any path-like value against a configurable policy
+///
+/// The [`validate_path_with`] counterpart for callers holding a `Path` or
+/// `PathBuf`, converting it with `to_string_lossy` like [`is_safe_path_p`].
+#[cfg(feature = "std")]
+pub fn validate_path_with_p<P: AsRef<Path>>(path: P, options: &ValidationOptions) -> Result<()> {
+    validate_path_with(path.as_ref().to_string_lossy(), options)
+}
+
+/// Validate a path and return its normalized form
+///
+/// Runs the checks of [`validate_path`] and, if they pass, returns the path
+/// normalized as [`normalize_path_buf`](crate::normalize_path_buf) would,
+/// converting the path to a string only once. Like `normalize_path_buf`, the
+/// result has forward slashes and no empty components, so a leading `/` is
+/// dropped, and a path of nothing but separators fails with
+/// [`PathError::EmptyPath`]. Nothing is returned for an invalid path, so
+/// callers can't end up using a normalized form that hides the problem.
+///
+/// # Examples
+/// ```
+/// use path_utils::validate_and_normalize;
+/// use std::path::PathBuf;
+///
+/// assert_eq!(validate_and_normalize("src//bin\\main.rs").unwrap(), PathBuf::from("src/bin/main.rs"));
+/// assert!(validate_and_normalize("src//../main.rs").is_err());
+/// ```
+#[cfg(feature = "std")]
+pub fn validate_and_normalize<P: AsRef<Path>>(path: P) -> Result<PathBuf> {
     let path_str = path.as_ref().to_string_lossy();
+    check_path(&path_str, &ValidationOptions::default())?;
 
+    let normalized = normalize_path_str(&path_str);
+    if normalized.is_empty() {
+        return Err(PathError::EmptyPath);
+    }
+    Ok(PathBuf::from(normalized))
+}
+
+/// The checks behind [`is_safe_path`] and [`validate_path_with`]
+///
+/// Both go through here so the boolean and detailed forms can't disagree.
+fn check_path(path_str: &str, options: &ValidationOptions) -> Result<()> {
     // Check for empty paths
     if path_str.trim().is_empty() {
-        return false;
+        return Err(PathError::EmptyPath);
     }
 
     // Check for path traversal
-    if path_str.contains("..") {
-        return false;
+    if has_traversal(path_str, options) {
+        return Err(PathError::PathTraversal {
+            path: path_str.to_string(),
+        });
     }
 
-    // Check for null bytes and dangerous control characters
-    if path_str.contains('\0')
-        || path_str
-            .chars()
-            .any(|c| c.is_control() && c != '\n' && c != '\t')
-    {
-        return false;
+    check_contents(path_str, path_str, options)
+}
+
+/// Check whether any component of a path climbs to its parent directory
+///
+/// A `..` component is traversal. With Windows rules, so is `..` followed by
+/// more dots and spaces (`...`, `.. `), since Windows strips those trailing
+/// characters when resolving a name. Dots inside a name, as in `v1..2`, are
+/// not traversal.
+pub(crate) fn has_traversal(path_str: &str, options: &ValidationOptions) -> bool {
+    path_str.split(['/', '\\']).any(|component| {
+        component == ".."
+            || (options.windows_rules()
+                && component.starts_with("..")
+                && component.trim_end_matches(['.', ' ']).is_empty())
+    })
+}
+
+/// Validate a single file name, such as one typed into a "new file" dialog
+///
+/// Applies the same rules as [`validate_path`], and additionally rejects any
+/// `/` or `\` with [`PathError::ContainsSeparator`], since a name with a
+/// separator would create the file somewhere other than the chosen directory.
+///
+/// # Examples
+/// ```
+/// use path_utils::{validate_filename, PathError};
+///
+/// assert!(validate_filename("a.txt").is_ok());
+/// assert!(matches!(validate_filename("a/b"), Err(PathError::ContainsSeparator { .. })));
+/// assert!(validate_filename("CON").is_err());
+/// ```
+pub fn validate_filename(name: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        return Err(PathError::EmptyPath);
     }
 
-    // Check for Windows-problematic characters
-    for invalid_char in ['<', '>', '|', '?', '*', '"'] {
-        if path_str.contains(invalid_char) {
-            return false;
-        }
+    if name.contains(['/', '\\']) {
+        return Err(PathError::ContainsSeparator {
+            name: name.to_string(),
+        });
     }
 
-    // Check for Windows reserved names
-    let reserved_names = [
-        "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
-        "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
-    ];
+    check_path(name, &ValidationOptions::default())
+}
 
-    for component in path_str.split('/').chain(path_str.split('\\')) {
-        let component_upper = component.to_uppercase();
-        let base_name = component_upper.split('.').next().unwrap_or("");
-        if reserved_names.contains(&base_name) {
-            return false;
+/// Check that a path is at most `max` bytes long
+///
+/// Filesystem limits are byte-based (4096 bytes for a path on Linux, 255 per
+/// name on most filesystems), so the UTF-8 length is measured rather than the
+/// number of characters. Legacy Windows paths are limited to 260 UTF-16 units,
+/// which is never more than 260 bytes for ASCII but can be for other text.
+///
+/// # Examples
+/// ```
+/// use path_utils::{validate_length, PathError};
+///
+/// assert!(validate_length("src/main.rs", 11).is_ok());
+/// assert_eq!(
+///     validate_length("src/main.rs", 10),
+///     Err(PathError::PathTooLong { length: 11, max: 10 })
+/// );
+/// ```
+pub fn validate_length(path: &str, max: usize) -> Result<()> {
+    if path.len() > max {
+        return Err(PathError::PathTooLong {
+            length: path.len(),
+            max,
+        });
+    }
+    Ok(())
+}
+
+/// Check a path against a set of size limits
+///
+/// One call for the filesystem pre-checks that are otherwise spread over
+/// [`validate_length`], [`path_depth`] and
+/// [`max_component_length`](ValidationOptions::max_component_length). Only
+/// the sizes are checked, not the characters or names; combine with
+/// [`validate_path`] for that. Each limit fails with its own error, checked
+/// in this order:
+///
+/// - [`PathError::PathTooLong`] for `max_total_bytes`, measured on the path as given
+/// - [`PathError::PathTooDeep`] for `max_depth`
+/// - [`PathError::ComponentTooLong`] for `max_component_bytes`, naming the
+///   first component over the limit
+///
+/// A path exactly at a limit passes.
+///
+/// # Examples
+/// ```
+/// use path_utils::{validate_limits, PathError, PathLimits};
+///
+/// let limits = PathLimits::new().max_depth(Some(2));
+/// assert!(validate_limits("a/b", &limits).is_ok());
+/// assert_eq!(
+///     validate_limits("a/b/c", &limits),
+///     Err(PathError::PathTooDeep { depth: 3, max: 2 })
+/// );
+/// ```
+pub fn validate_limits(path: &str, limits: &PathLimits) -> Result<()> {
+    if let Some(max) = limits.max_total_bytes {
+        validate_length(path, max)?;
+    }
+
+    if let Some(max) = limits.max_depth {
+        let depth = path_depth(path);
+        if depth > max {
+            return Err(PathError::PathTooDeep { depth, max });
         }
     }
 
-    true
+    if let Some(max) = limits.max_component_bytes {
+        if let Some(component) = normalized_components(path).find(|component| component.len() > max)
+        {
+            return Err(PathError::ComponentTooLong {
+                component: component.to_string(),
+                length: component.len(),
+            });
+        }
+    }
+
+    Ok(())
 }
 
-/// Validate a path and return detailed error information
+/// A single content rule: checks `path_str`, reporting `reported` in errors
+type ContentRule = fn(&str, &str, &ValidationOptions) -> Result<()>;
+
+/// The content rules, in the order they are applied
+const CONTENT_RULES: &[ContentRule] = &[
+    check_relative,
+    check_max_length,
+    check_component_length,
+    #[cfg(feature = "unicode")]
+    check_component_graphemes,
+    check_control_chars,
+    check_bidi_controls,
+    check_forbidden_chars,
+    check_ads,
+    check_dot_whitespace_components,
+    check_short_names,
+    check_extension,
+    check_dotgit,
+    check_reserved_names,
+    check_surrounding_whitespace,
+];
+
+/// Check the character, length and filename rules shared by validation and sanitization
 ///
-/// This function performs comprehensive validation and returns specific error types
-/// for different validation failures.
+/// `path_str` is the string being checked, while errors report `reported`, so
+/// callers that check a normalized form can still surface the original input.
+pub(crate) fn check_contents(
+    path_str: &str,
+    reported: &str,
+    options: &ValidationOptions,
+) -> Result<()> {
+    CONTENT_RULES
+        .iter()
+        .try_for_each(|rule| rule(path_str, reported, options))
+}
+
+/// Validate a path against every rule, collecting all violations
+///
+/// Applies the same rules as [`validate_path_with`], but instead of stopping
+/// at the first failure it reports every rule that fails, in rule order, so a
+/// form can show all problems at once. A single failure is returned as is;
+/// several are wrapped in [`PathError::Multiple`], so the result still works
+/// with `?`. [`PathError::iter_all`] yields the individual errors either way.
+/// An empty path only reports [`PathError::EmptyPath`], since no other rule
+/// is meaningful for it.
 ///
 /// # Examples
 /// ```
-/// use path_utils::validate_path;
+/// use path_utils::{validate_path_all, PathError, ValidationOptions};
 ///
-/// assert!(validate_path("safe/path/file.txt").is_ok());
-/// assert!(validate_path("../etc/passwd").is_err());
+/// let options = ValidationOptions::new().max_length(Some(8));
+/// let err = validate_path_all("../CON<1>", &options).unwrap_err();
+/// let errors: Vec<&PathError> = err.iter_all().collect();
+/// assert_eq!(errors.len(), 4);
+/// assert!(matches!(errors[0], PathError::PathTraversal { .. }));
+///
+/// assert!(validate_path_all("src/main.rs", &ValidationOptions::new()).is_ok());
 /// ```
-pub fn validate_path<P: AsRef<Path>>(path: P) -> Result<()> {
-    let path_str = path.as_ref().to_string_lossy();
-    let path_string = path_str.to_string();
+pub fn validate_path_all<P: AsRef<str>>(path: P, options: &ValidationOptions) -> Result<()> {
+    validate_str_all(path.as_ref(), options)
+}
 
-    // Check for empty paths
+/// Validate a path given as any path-like value against every rule
+///
+/// The [`validate_path_all`] counterpart for callers holding a `Path` or
+/// `PathBuf`, converting it with `to_string_lossy` like [`is_safe_path_p`].
+#[cfg(feature = "std")]
+pub fn validate_path_all_p<P: AsRef<Path>>(path: P, options: &ValidationOptions) -> Result<()> {
+    validate_path_all(path.as_ref().to_string_lossy(), options)
+}
+
+/// The checks behind [`validate_path_all`]
+fn validate_str_all(path_str: &str, options: &ValidationOptions) -> Result<()> {
     if path_str.trim().is_empty() {
         return Err(PathError::EmptyPath);
     }
 
-    // Check for path traversal
-    if path_str.contains("..") {
-        return Err(PathError::PathTraversal { path: path_string });
+    let mut errors = Vec::new();
+    if has_traversal(path_str, options) {
+        errors.push(PathError::PathTraversal {
+            path: path_str.to_string(),
+        });
     }
+    errors.extend(
+        CONTENT_RULES
+            .iter()
+            .filter_map(|rule| rule(path_str, path_str, options).err()),
+    );
 
-    // Check for null bytes and dangerous control characters
-    if path_str.contains('\0')
-        || path_str
-            .chars()
-            .any(|c| c.is_control() && c != '\n' && c != '\t')
-    {
-        return Err(PathError::InvalidCharacters { path: path_string });
+    match errors.len() {
+        0 => Ok(()),
+        1 => Err(errors.remove(0)),
+        _ => Err(PathError::Multiple(errors)),
+    }
+}
+
+/// Validate a batch of paths, pairing each with its result
+///
+/// Runs the same checks as [`validate_path`] on every path and returns the
+/// results in input order. Each input is moved into the output alongside its
+/// result rather than copied, so owned strings aren't cloned and borrowed ones
+/// stay borrowed.
+///
+/// # Examples
+/// ```
+/// use path_utils::validate_paths;
+///
+/// let results = validate_paths(["src/main.rs", "../etc/passwd"]);
+/// assert_eq!(results[0], ("src/main.rs", Ok(())));
+/// assert!(results[1].1.is_err());
+/// ```
+pub fn validate_paths<I, S>(paths: I) -> Vec<(S, Result<()>)>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let options = ValidationOptions::default();
+    paths
+        .into_iter()
+        .map(|path| {
+            let result = check_path(path.as_ref(), &options);
+            (path, result)
+        })
+        .collect()
+}
+
+/// Count how many paths in a batch are rejected as path traversal
+///
+/// For security logging over incoming path lists: a path is counted when
+/// [`validate_path`] would fail with [`PathError::PathTraversal`], meaning it
+/// has a `..` component. Dots inside a name, as in `v1..2`, are not
+/// traversal. Paths rejected for any other reason, such as a reserved name or
+/// a forbidden character, are not counted, and neither are empty paths.
+///
+/// # Examples
+/// ```
+/// use path_utils::count_traversal_attempts;
+///
+/// let paths = ["src/main.rs", "../etc/passwd", "lib/CON", "a/../../b"];
+/// assert_eq!(count_traversal_attempts(paths), 2);
+/// ```
+pub fn count_traversal_attempts<I, S>(paths: I) -> usize
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let options = ValidationOptions::default();
+    paths
+        .into_iter()
+        .filter(|path| {
+            matches!(
+                check_path(path.as_ref(), &options),
+                Err(PathError::PathTraversal { .. })
+            )
+        })
+        .count()
+}
+
+/// Validate a batch of paths in parallel, pairing each with its result
+///
+/// The parallel counterpart of [`validate_paths`], for very large manifests.
+/// The input must be an indexed parallel iterator (a `Vec`, slice or range,
+/// for example), which lets the results come back in input order regardless
+/// of which thread checked each path. Requires the `rayon` feature.
+///
+/// # Examples
+/// ```
+/// use path_utils::validate_paths_par;
+///
+/// let results = validate_paths_par(vec!["src/main.rs", "../etc/passwd"]);
+/// assert!(results[0].1.is_ok());
+/// assert!(results[1].1.is_err());
+/// ```
+#[cfg(feature = "rayon")]
+pub fn validate_paths_par<I>(paths: I) -> Vec<(I::Item, Result<()>)>
+where
+    I: IntoParallelIterator,
+    I::Iter: IndexedParallelIterator,
+    I::Item: AsRef<str>,
+{
+    let options = ValidationOptions::default();
+    paths
+        .into_par_iter()
+        .map(|path| {
+            let result = check_path(path.as_ref(), &options);
+            (path, result)
+        })
+        .collect()
+}
+
+/// Check the length in bytes, since filesystem limits are byte-based
+fn check_max_length(path_str: &str, _reported: &str, options: &ValidationOptions) -> Result<()> {
+    match options.max_length {
+        Some(max) => validate_length(path_str, max),
+        None => Ok(()),
+    }
+}
+
+/// Reject rooted, drive and UNC paths when the caller needs a relative one
+fn check_relative(path_str: &str, reported: &str, options: &ValidationOptions) -> Result<()> {
+    if options.require_relative && is_absolute_for(path_str, options) {
+        return Err(PathError::AbsolutePath {
+            path: reported.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Whether a path is rooted, UNC, or starts with a drive letter the target honours
+pub(crate) fn is_absolute_for(path_str: &str, options: &ValidationOptions) -> bool {
+    match classify(path_str) {
+        PathKind::Relative => false,
+        PathKind::DriveAbsolute => options.windows_rules(),
+        PathKind::AbsoluteRoot | PathKind::Unc => true,
+    }
+}
+
+/// Check each component too, since names have their own byte limit
+fn check_component_length(
+    path_str: &str,
+    _reported: &str,
+    options: &ValidationOptions,
+) -> Result<()> {
+    if let Some(max) = options.max_component_length {
+        if let Some(component) = path_str
+            .split(['/', '\\'])
+            .find(|component| component.len() > max)
+        {
+            return Err(PathError::ComponentTooLong {
+                component: component.to_string(),
+                length: component.len(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Check the length of each component as the user perceives it
+#[cfg(feature = "unicode")]
+fn check_component_graphemes(
+    path_str: &str,
+    reported: &str,
+    options: &ValidationOptions,
+) -> Result<()> {
+    if let Some(max) = options.max_component_graphemes {
+        if let Some(component) = path_str
+            .split(['/', '\\'])
+            .find(|component| crate::unicode::grapheme_count(component) > max)
+        {
+            return Err(PathError::validation_failed(
+                "max_component_graphemes",
+                alloc::format!(
+                    "component {} is longer than {} graphemes in {}",
+                    component,
+                    max,
+                    reported
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check for null bytes and dangerous control characters
+fn check_control_chars(path_str: &str, reported: &str, options: &ValidationOptions) -> Result<()> {
+    if path_str.chars().any(|c| is_forbidden_control(c, options)) {
+        return Err(PathError::InvalidCharacters {
+            path: reported.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Whether a character fails [`check_control_chars`]
+///
+/// Null is always rejected; other control characters except newline and tab
+/// unless the options allow them.
+fn is_forbidden_control(c: char, options: &ValidationOptions) -> bool {
+    c == '\0' || (!options.allow_control_chars && c.is_control() && c != '\n' && c != '\t')
+}
+
+/// Whether a single character is rejected by any of the character rules
+///
+/// Covers control characters, deceptive format characters and forbidden
+/// characters as configured, but not `:`, whose meaning depends on where it
+/// appears.
+pub(crate) fn is_invalid_char(c: char, options: &ValidationOptions) -> bool {
+    is_forbidden_control(c, options)
+        || (options.reject_bidi_controls && DECEPTIVE_FORMAT_CHARS.contains(&c))
+        || (options.windows_rules() && FORBIDDEN_CHARS.contains(&c))
+        || options.extra_forbidden_chars.contains(&c)
+}
+
+/// Check for bidirectional controls and zero-width characters
+fn check_bidi_controls(path_str: &str, reported: &str, options: &ValidationOptions) -> Result<()> {
+    if options.reject_bidi_controls && path_str.contains(DECEPTIVE_FORMAT_CHARS) {
+        return Err(PathError::InvalidCharacters {
+            path: reported.to_string(),
+        });
     }
+    Ok(())
+}
 
-    // Check for Windows-problematic characters
-    for invalid_char in ['<', '>', '|', '?', '*', '"'] {
-        if path_str.contains(invalid_char) {
-            return Err(PathError::InvalidCharacters { path: path_string });
+/// Check for Windows-problematic characters and any caller-supplied extras
+fn check_forbidden_chars(
+    path_str: &str,
+    reported: &str,
+    options: &ValidationOptions,
+) -> Result<()> {
+    let builtin: &[char] = if options.windows_rules() {
+        &FORBIDDEN_CHARS
+    } else {
+        &[]
+    };
+    for invalid_char in builtin.iter().chain(&options.extra_forbidden_chars) {
+        if path_str.contains(*invalid_char) {
+            return Err(PathError::InvalidCharacters {
+                path: reported.to_string(),
+            });
         }
     }
+    Ok(())
+}
+
+/// Check for colons naming an NTFS alternate data stream
+fn check_ads(path_str: &str, reported: &str, options: &ValidationOptions) -> Result<()> {
+    if !options.reject_ads || !options.windows_rules() {
+        return Ok(());
+    }
+
+    // A colon after a leading drive letter is a drive prefix, not a stream, as
+    // long as a separator or nothing follows: `a:b` is the stream `b` of `a`
+    let bytes = path_str.as_bytes();
+    let is_drive_prefix = bytes.len() >= 2
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && matches!(bytes.get(2), None | Some(b'/' | b'\\'));
+    let after_drive = if is_drive_prefix {
+        &path_str[2..]
+    } else {
+        path_str
+    };
+    if after_drive.contains(':') {
+        return Err(PathError::InvalidCharacters {
+            path: reported.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Check for components that are invisible or that Windows would strip down to nothing
+fn check_dot_whitespace_components(
+    path_str: &str,
+    reported: &str,
+    options: &ValidationOptions,
+) -> Result<()> {
+    if options.reject_dot_whitespace_components
+        && options.windows_rules()
+        && path_str.split(['/', '\\']).any(is_dots_and_whitespace)
+    {
+        return Err(PathError::InvalidCharacters {
+            path: reported.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Check for whitespace before the first component or after the last
+fn check_surrounding_whitespace(
+    path_str: &str,
+    reported: &str,
+    options: &ValidationOptions,
+) -> Result<()> {
+    if !options.reject_surrounding_whitespace {
+        return Ok(());
+    }
 
-    // Check for Windows reserved names
-    let reserved_names = [
-        "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
-        "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
-    ];
+    let mut components = normalized_components(path_str);
+    let first = components.next().unwrap_or("");
+    let last = components.last().unwrap_or(first);
+    if first.starts_with(char::is_whitespace) || last.ends_with(char::is_whitespace) {
+        return Err(PathError::InvalidCharacters {
+            path: reported.to_string(),
+        });
+    }
+    Ok(())
+}
 
-    for component in path_str.split('/').chain(path_str.split('\\')) {
-        let component_upper = component.to_uppercase();
-        let base_name = component_upper.split('.').next().unwrap_or("");
-        if reserved_names.contains(&base_name) {
+/// Reject 8.3 short-name aliases, which can reach a file under a second name
+fn check_short_names(path_str: &str, reported: &str, options: &ValidationOptions) -> Result<()> {
+    if !options.reject_short_names || !options.windows_rules() {
+        return Ok(());
+    }
+    if let Some(component) = path_str.split(['/', '\\']).find(|c| is_short_name(c)) {
+        return Err(PathError::validation_failed(
+            "short_name",
+            alloc::format!("{} is an 8.3 short-name alias in {}", component, reported),
+        ));
+    }
+    Ok(())
+}
+
+/// Check whether a component has the `NAME~N` or `NAME~N.EXT` shape of an 8.3 short name
+fn is_short_name(component: &str) -> bool {
+    let (base, extension) = component.split_once('.').unwrap_or((component, ""));
+    let Some((stem, number)) = base.rsplit_once('~') else {
+        return false;
+    };
+
+    !stem.is_empty()
+        && !number.is_empty()
+        && number.bytes().all(|b| b.is_ascii_digit())
+        && base.chars().count() <= 8
+        && extension.chars().count() <= 3
+        && !extension.contains('.')
+}
+
+/// Check the final component's extension against the caller's policy
+fn check_extension(path_str: &str, reported: &str, options: &ValidationOptions) -> Result<()> {
+    if options.extension_policy == ExtensionPolicy::Any {
+        return Ok(());
+    }
+
+    let file_name = path_str
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or("")
+        .trim_end_matches(['.', ' ']);
+    let extension = file_extension(file_name).unwrap_or("").to_ascii_lowercase();
+    let forbidden = match &options.extension_policy {
+        ExtensionPolicy::Any => false,
+        ExtensionPolicy::Deny(denied) => !extension.is_empty() && denied.contains(&extension),
+        ExtensionPolicy::Allow(allowed) => !allowed.contains(&extension),
+    };
+    if forbidden {
+        return Err(PathError::ForbiddenExtension {
+            extension,
+            path: reported.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Check for components that would write into a Git repository's internals
+fn check_dotgit(path_str: &str, reported: &str, options: &ValidationOptions) -> Result<()> {
+    if options.reject_dotgit {
+        if let Some(component) = path_str
+            .split(['/', '\\'])
+            .find(|c| is_dotgit_confusable(c))
+        {
             return Err(PathError::ReservedFilename {
                 filename: component.to_string(),
-                path: path_string,
+                path: reported.to_string(),
             });
         }
     }
+    Ok(())
+}
 
+/// Check for reserved names
+fn check_reserved_names(path_str: &str, reported: &str, options: &ValidationOptions) -> Result<()> {
+    if !options.allow_reserved_names {
+        let components = path_str.split('/').chain(path_str.split('\\'));
+        if let Some(component) =
+            find_reserved_component(components, &options.reserved_names, options.windows_rules())
+        {
+            return Err(PathError::ReservedFilename {
+                filename: component.to_string(),
+                path: reported.to_string(),
+            });
+        }
+    }
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::options::{TargetPlatform, DEFAULT_MAX_COMPONENT_LENGTH};
+    use crate::reserved::ReservedNames;
 
     #[test]
     fn test_is_safe_path() {
@@ -147,6 +797,35 @@ mod tests {
         assert!(!is_safe_path("PRN.txt"));
     }
 
+    #[test]
+    fn test_is_safe_path_agrees_with_validate_path() {
+        // One path per rule, with backslash separators where they matter,
+        // since separate implementations of these rules used to drift apart
+        let long_component = "x".repeat(DEFAULT_MAX_COMPONENT_LENGTH + 1);
+        let paths = [
+            "a\\CON/b".to_string(),
+            "a/b\\nul.txt".to_string(),
+            "dir\\. .\\file".to_string(),
+            format!("dir\\{}", long_component),
+            "a\\b..c".to_string(),
+            "tab\tname".to_string(),
+            "bell\u{7}".to_string(),
+            "name\u{202E}txt.exe".to_string(),
+            "what?.txt".to_string(),
+            "   ".to_string(),
+            "plain\\windows\\path.txt".to_string(),
+        ];
+
+        for path in &paths {
+            assert_eq!(
+                is_safe_path(path),
+                validate_path(path).is_ok(),
+                "disagreement for {:?}",
+                path
+            );
+        }
+    }
+
     #[test]
     fn test_validate_path() {
         // Valid paths
@@ -178,4 +857,844 @@ mod tests {
             Err(PathError::ReservedFilename { .. })
         ));
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_validate_path_with_default_matches_validate_path() {
+        let defaults = ValidationOptions::default();
+        for path in [
+            "safe/path/file.txt",
+            "../etc/passwd",
+            "",
+            "file\0null",
+            "file\x01control",
+            "file<script>",
+            "CON",
+            "lib/aux.js",
+        ] {
+            assert_eq!(validate_path_with(path, &defaults), validate_path(path));
+        }
+    }
+
+    #[test]
+    fn test_validate_path_with_allow_reserved_names() {
+        let options = ValidationOptions::new().allow_reserved_names(true);
+        assert!(validate_path_with("CON", &options).is_ok());
+        assert!(validate_path_with("logs/lpt1.txt", &options).is_ok());
+
+        // Other rules still apply
+        assert!(validate_path_with("../CON", &options).is_err());
+    }
+
+    #[test]
+    fn test_validate_path_with_allow_control_chars() {
+        let options = ValidationOptions::new().allow_control_chars(true);
+        assert!(validate_path_with("file\x01control", &options).is_ok());
+        assert!(validate_path_with("file\x1Fescape", &options).is_ok());
+
+        // Null bytes are never allowed
+        assert!(matches!(
+            validate_path_with("file\0null", &options),
+            Err(PathError::InvalidCharacters { .. })
+        ));
+    }
+
+    #[test]
+    fn test_reject_bidi_controls() {
+        // A right-to-left override makes this display as "invoice_fdp.exe"
+        let trojan = "docs/invoice_\u{202E}exe.pdf";
+        assert!(matches!(
+            validate_path(trojan),
+            Err(PathError::InvalidCharacters { .. })
+        ));
+        assert!(!is_safe_path(trojan));
+        assert!(crate::sanitize_directory_file_path(trojan).is_err());
+
+        // A zero-width joiner makes two names look identical
+        let zwj = "src/ma\u{200D}in.rs";
+        assert!(matches!(
+            validate_path(zwj),
+            Err(PathError::InvalidCharacters { .. })
+        ));
+        assert!(!is_safe_path(zwj));
+
+        for c in DECEPTIVE_FORMAT_CHARS {
+            let path = alloc::format!("a{}b", c);
+            assert!(validate_path(&path).is_err(), "{:?} should be rejected", c);
+        }
+
+        // Opt out for names that need a zero-width joiner
+        let options = ValidationOptions::new().reject_bidi_controls(false);
+        assert!(validate_path_with(zwj, &options).is_ok());
+        assert!(validate_path_with(trojan, &options).is_ok());
+
+        // Ordinary right-to-left text is fine
+        assert!(validate_path("docs/שלום.txt").is_ok());
+    }
+
+    #[test]
+    fn test_validate_filename() {
+        assert!(validate_filename("a.txt").is_ok());
+        assert!(validate_filename(".gitignore").is_ok());
+        assert!(validate_filename("résumé final.pdf").is_ok());
+
+        for name in ["a/b", "a\\b", "/a.txt", "dir/", "..\\a"] {
+            assert_eq!(
+                validate_filename(name),
+                Err(PathError::ContainsSeparator {
+                    name: name.to_string()
+                })
+            );
+        }
+
+        // The usual rules still apply
+        assert_eq!(validate_filename(""), Err(PathError::EmptyPath));
+        assert_eq!(validate_filename("  "), Err(PathError::EmptyPath));
+        assert!(matches!(
+            validate_filename("a<b>.txt"),
+            Err(PathError::InvalidCharacters { .. })
+        ));
+        assert!(matches!(
+            validate_filename("nul.txt"),
+            Err(PathError::ReservedFilename { .. })
+        ));
+        assert!(matches!(
+            validate_filename(".."),
+            Err(PathError::PathTraversal { .. })
+        ));
+    }
+
+    #[test]
+    fn test_reject_ads() {
+        for path in [
+            "a:b",
+            "file.txt:$DATA",
+            "docs/notes.txt:hidden",
+            "C:/x:y",
+            "::",
+        ] {
+            assert!(
+                matches!(
+                    validate_path(path),
+                    Err(PathError::InvalidCharacters { .. })
+                ),
+                "{:?} should be rejected",
+                path
+            );
+            assert!(!is_safe_path(path));
+        }
+
+        // A drive prefix is not a stream
+        assert!(validate_path("C:/Windows/file.txt").is_ok());
+        assert!(validate_path("d:\\data").is_ok());
+        assert!(validate_path("e:").is_ok());
+
+        // Sanitization still reports drive prefixes as such
+        assert!(matches!(
+            crate::sanitize_directory_file_path("C:\\x.txt"),
+            Err(PathError::DriveLetterPath { .. })
+        ));
+        assert!(matches!(
+            crate::sanitize_directory_file_path("file.txt:$DATA"),
+            Err(PathError::InvalidCharacters { .. })
+        ));
+
+        let options = ValidationOptions::new().reject_ads(false);
+        assert!(validate_path_with("file.txt:$DATA", &options).is_ok());
+    }
+
+    #[test]
+    fn test_reject_dot_whitespace_components() {
+        for path in [
+            "a/ /b",
+            "a/   /b",
+            "a/.../b",
+            "a\\. .\\b",
+            "a/\u{3000}/b",
+            "trailing/ ",
+        ] {
+            assert!(
+                matches!(
+                    validate_path(path),
+                    Err(PathError::InvalidCharacters { .. }) | Err(PathError::PathTraversal { .. })
+                ),
+                "{:?} should be rejected",
+                path
+            );
+            assert!(!is_safe_path(path));
+        }
+
+        // Dots or spaces alongside other characters are fine, and since
+        // traversal is component-based, so is a name like `...foo`
+        let options = ValidationOptions::default();
+        assert!(check_dot_whitespace_components("a/...foo/b", "", &options).is_ok());
+        assert!(validate_path("a/...foo/b").is_ok());
+        assert!(validate_path("a/ b /c").is_ok());
+        assert!(validate_path("a/./b").is_ok());
+
+        let options = ValidationOptions::new().reject_dot_whitespace_components(false);
+        assert!(validate_path_with("a/ /b", &options).is_ok());
+        assert!(validate_path_with("a/\u{3000}/b", &options).is_ok());
+    }
+
+    #[test]
+    fn test_validate_path_with_max_length() {
+        let options = ValidationOptions::new().max_length(Some(10));
+        assert!(validate_path_with("abcde/f.rs", &options).is_ok());
+        assert_eq!(
+            validate_path_with("abcdef/g.rs", &options),
+            Err(PathError::PathTooLong {
+                length: 11,
+                max: 10
+            })
+        );
+
+        // Length is measured in bytes, not chars
+        assert!(validate_path_with("ééééé", &options).is_ok());
+        assert!(validate_path_with("éééééé", &options).is_err());
+
+        let unlimited = ValidationOptions::new().max_length(None);
+        assert!(validate_path_with("abc/".repeat(2_500), &unlimited).is_ok());
+    }
+
+    #[test]
+    fn test_validate_length() {
+        let at_limit = "a".repeat(255);
+        assert!(validate_length(&at_limit, 255).is_ok());
+        assert_eq!(
+            validate_length(&format!("{}b", at_limit), 255),
+            Err(PathError::PathTooLong {
+                length: 256,
+                max: 255
+            })
+        );
+
+        // 127 two-byte chars fill 254 bytes; one more pushes past the limit
+        let multibyte = "é".repeat(127);
+        assert!(validate_length(&multibyte, 255).is_ok());
+        assert!(validate_length(&format!("{}a", multibyte), 255).is_ok());
+        assert_eq!(
+            validate_length(&format!("{}é", multibyte), 255),
+            Err(PathError::PathTooLong {
+                length: 256,
+                max: 255
+            })
+        );
+
+        assert!(validate_length("", 0).is_ok());
+        assert!(validate_length("a", 0).is_err());
+    }
+
+    #[test]
+    fn test_max_component_length() {
+        let at_limit = "a".repeat(255);
+        assert!(validate_path(format!("dir/{}", at_limit)).is_ok());
+        assert!(is_safe_path(format!("dir/{}", at_limit)));
+
+        let over_limit = "a".repeat(256);
+        assert_eq!(
+            validate_path(format!("dir\\{}/x", over_limit)),
+            Err(PathError::ComponentTooLong {
+                component: over_limit.clone(),
+                length: 256
+            })
+        );
+        assert!(!is_safe_path(format!("dir\\{}/x", over_limit)));
+
+        // 200 chars, but 400 bytes
+        let multibyte = "é".repeat(200);
+        assert!(matches!(
+            validate_path(&multibyte),
+            Err(PathError::ComponentTooLong { length: 400, .. })
+        ));
+        assert!(!is_safe_path(&multibyte));
+
+        // A long path made of short components is fine
+        let long_path = vec!["segment"; 100].join("/");
+        assert!(validate_path(&long_path).is_ok());
+
+        // The limit is configurable and can be disabled
+        let options = ValidationOptions::new().max_component_length(Some(8));
+        assert!(validate_path_with("12345678/x", &options).is_ok());
+        assert!(validate_path_with("123456789/x", &options).is_err());
+        let options = ValidationOptions::new().max_component_length(None);
+        assert!(validate_path_with(&over_limit, &options).is_ok());
+    }
+
+    #[test]
+    fn test_validate_path_with_deny_extensions() {
+        let options = ValidationOptions::new().deny_extensions(&["exe", ".SH", "php"]);
+
+        assert!(validate_path_with("uploads/photo.jpg", &options).is_ok());
+        assert_eq!(
+            validate_path_with("uploads/setup.EXE", &options),
+            Err(PathError::ForbiddenExtension {
+                extension: "exe".to_string(),
+                path: "uploads/setup.EXE".to_string()
+            })
+        );
+        assert!(validate_path_with("run.sh", &options).is_err());
+        assert!(validate_path_with("shell.php.", &options).is_err());
+
+        // Only the last extension counts
+        assert!(validate_path_with("index.php.txt", &options).is_ok());
+        assert!(validate_path_with("script.sh.gz", &options).is_ok());
+
+        // Extension-less files and dotfiles pass a deny list
+        assert!(validate_path_with("bin/tool", &options).is_ok());
+        assert!(validate_path_with(".sh", &options).is_ok());
+        // ...but a directory named like a denied extension does not matter
+        assert!(validate_path_with("exe.d/readme.md", &options).is_ok());
+    }
+
+    #[test]
+    fn test_validate_path_with_allow_extensions() {
+        let options = ValidationOptions::new().allow_extensions(&["png", "jpg", "gz"]);
+
+        assert!(validate_path_with("img/cat.PNG", &options).is_ok());
+        assert!(validate_path_with("backup.tar.gz", &options).is_ok());
+        assert!(matches!(
+            validate_path_with("img/cat.svg", &options),
+            Err(PathError::ForbiddenExtension { .. })
+        ));
+
+        // Extension-less files need an explicit ""
+        assert!(validate_path_with("Makefile", &options).is_err());
+        assert!(validate_path_with(".env", &options).is_err());
+        let options = ValidationOptions::new().allow_extensions(&["png", ""]);
+        assert!(validate_path_with("Makefile", &options).is_ok());
+
+        // The modes replace each other
+        let options = ValidationOptions::new()
+            .deny_extensions(&["png"])
+            .allow_extensions(&["png"]);
+        assert!(validate_path_with("a.png", &options).is_ok());
+        assert!(validate_path_with("a.txt", &options).is_err());
+    }
+
+    #[test]
+    fn test_validate_path_all() {
+        // Too long, a forbidden character, and a reserved name
+        let options = ValidationOptions::new().max_length(Some(12));
+        let err = validate_path_all("logs/CON/a|b.txt", &options).unwrap_err();
+        assert!(matches!(err, PathError::Multiple(_)));
+        let errors: Vec<&PathError> = err.iter_all().collect();
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(
+            errors[0],
+            PathError::PathTooLong {
+                length: 16,
+                max: 12
+            }
+        ));
+        assert!(matches!(errors[1], PathError::InvalidCharacters { .. }));
+        assert!(matches!(errors[2], PathError::ReservedFilename { .. }));
+
+        // The first collected error is what validate_path_with reports
+        assert_eq!(
+            validate_path_with("logs/CON/a|b.txt", &options),
+            Err(errors[0].clone())
+        );
+
+        // The messages are joined for display
+        assert_eq!(
+            err.to_string(),
+            errors
+                .iter()
+                .map(|error| error.to_string())
+                .collect::<Vec<_>>()
+                .join("; ")
+        );
+
+        // A single failure isn't wrapped
+        assert_eq!(
+            validate_path_all("   ", &options),
+            Err(PathError::EmptyPath)
+        );
+        assert!(matches!(
+            validate_path_all("a|b", &options),
+            Err(PathError::InvalidCharacters { .. })
+        ));
+        assert_eq!(validate_path_all("src/lib.rs", &options), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_path_with_extra_forbidden_chars() {
+        let options = ValidationOptions::new().extra_forbidden_chars(&['#', ' ']);
+        assert!(validate_path_with("notes.txt", &options).is_ok());
+        assert!(matches!(
+            validate_path_with("notes#1.txt", &options),
+            Err(PathError::InvalidCharacters { .. })
+        ));
+        assert!(validate_path_with("my notes.txt", &options).is_err());
+
+        // Built-in forbidden characters are still rejected
+        assert!(validate_path_with("file|pipe", &options).is_err());
+    }
+
+    #[test]
+    fn test_validate_path_with_custom_reserved_names() {
+        let mut names = ReservedNames::with_defaults();
+        names.add("CLOCK$").add("web.config");
+        let options = ValidationOptions::new().reserved_names(names);
+
+        assert!(matches!(
+            validate_path_with("CLOCK$", &options),
+            Err(PathError::ReservedFilename { .. })
+        ));
+        assert!(matches!(
+            validate_path_with("site\\Web.config", &options),
+            Err(PathError::ReservedFilename { .. })
+        ));
+        assert!(validate_path_with("CON", &options).is_err());
+        assert!(validate_path_with("site/index.html", &options).is_ok());
+
+        // An empty set drops the Windows names entirely
+        let mut names = ReservedNames::with_defaults();
+        names.clear();
+        let options = ValidationOptions::new().reserved_names(names);
+        assert!(validate_path_with("CON", &options).is_ok());
+        assert!(validate_path_with("lib/aux.js", &options).is_ok());
+    }
+
+    #[test]
+    fn test_reserved_names_with_trailing_dots_and_spaces() {
+        for path in ["CON ", "CON.", "nul. .", "lpt1.txt.", "dir/Aux .txt"] {
+            assert!(
+                matches!(validate_path(path), Err(PathError::ReservedFilename { .. })),
+                "reserved name should be rejected: {:?}",
+                path
+            );
+            assert!(!is_safe_path(path));
+        }
+
+        // Trailing runs of dots don't hide the name either
+        assert!(validate_path("nul...").is_err());
+        assert!(!is_safe_path("nul..."));
+
+        // Components made only of dots and spaces
+        for path in ["a/ /b", "a/. ./b", "  /b"] {
+            assert!(matches!(
+                validate_path(path),
+                Err(PathError::InvalidCharacters { .. })
+            ));
+            assert!(!is_safe_path(path));
+        }
+
+        assert!(validate_path("a/./b").is_ok());
+        assert!(validate_path("file.").is_ok());
+    }
+
+    #[test]
+    fn test_validate_paths() {
+        let results = validate_paths([
+            "src/main.rs",
+            "../etc/passwd",
+            "docs/guide.md",
+            "",
+            "a<b",
+            "lib/CON",
+        ]);
+
+        let inputs: Vec<&str> = results.iter().map(|(path, _)| *path).collect();
+        assert_eq!(
+            inputs,
+            [
+                "src/main.rs",
+                "../etc/passwd",
+                "docs/guide.md",
+                "",
+                "a<b",
+                "lib/CON"
+            ]
+        );
+        assert_eq!(results[0].1, Ok(()));
+        assert!(matches!(results[1].1, Err(PathError::PathTraversal { .. })));
+        assert_eq!(results[2].1, Ok(()));
+        assert_eq!(results[3].1, Err(PathError::EmptyPath));
+        assert!(matches!(
+            results[4].1,
+            Err(PathError::InvalidCharacters { .. })
+        ));
+        assert!(matches!(
+            results[5].1,
+            Err(PathError::ReservedFilename { .. })
+        ));
+
+        // Each result agrees with validating the path on its own
+        for (path, result) in &results {
+            assert_eq!(*result, validate_path(path));
+        }
+    }
+
+    #[test]
+    fn test_validate_paths_owned_input() {
+        let paths = vec!["a.txt".to_string(), "b/../c".to_string()];
+        let results = validate_paths(paths);
+        assert_eq!(results[0].0, "a.txt");
+        assert!(results[0].1.is_ok());
+        assert!(results[1].1.is_err());
+
+        assert!(validate_paths(Vec::<String>::new()).is_empty());
+    }
+
+    #[test]
+    fn test_count_traversal_attempts() {
+        let paths = [
+            "src/main.rs",
+            "../etc/passwd",
+            "lib/CON",
+            "a<b",
+            "",
+            "..\\..\\windows",
+            "docs/../../secret",
+            "file\0name",
+            "/abs/path",
+            "v1..2/notes.txt",
+            "a...b",
+        ];
+        assert_eq!(count_traversal_attempts(paths), 3);
+
+        // A path that is both traversal and otherwise invalid still counts
+        assert_eq!(count_traversal_attempts(["../CON", "../a<b"]), 2);
+
+        assert_eq!(count_traversal_attempts(Vec::<String>::new()), 0);
+        assert_eq!(count_traversal_attempts(["a".to_string()]), 0);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_validate_paths_par_matches_sequential() {
+        let paths: Vec<String> = (0..2000)
+            .map(|i| match i % 5 {
+                0 => format!("src/module_{}.rs", i),
+                1 => format!("../escape/{}", i),
+                2 => format!("dir/CON{}", i % 2),
+                3 => format!("bad<{}>", i),
+                _ => String::new(),
+            })
+            .collect();
+
+        let parallel = validate_paths_par(paths.clone());
+        let sequential = validate_paths(paths);
+        assert_eq!(parallel, sequential);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_validate_and_normalize() {
+        assert_eq!(
+            validate_and_normalize("a//b\\c.txt").unwrap(),
+            PathBuf::from("a/b/c.txt")
+        );
+        assert_eq!(
+            validate_and_normalize(Path::new("/src/./lib.rs/")).unwrap(),
+            PathBuf::from("src/./lib.rs")
+        );
+        assert_eq!(
+            validate_and_normalize(PathBuf::from("file.txt")).unwrap(),
+            PathBuf::from("file.txt")
+        );
+
+        // Matches the two-step form
+        for path in ["x\\y//z", "//lead/trail//", "docs/README.md"] {
+            assert_eq!(
+                validate_and_normalize(path).unwrap(),
+                crate::normalize_path_buf(path)
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_validate_and_normalize_rejects_before_normalizing() {
+        // Normalizing would hide none of these, and none is returned
+        assert!(matches!(
+            validate_and_normalize("a//..//b"),
+            Err(PathError::PathTraversal { .. })
+        ));
+        assert!(matches!(
+            validate_and_normalize("logs//CON"),
+            Err(PathError::ReservedFilename { .. })
+        ));
+        assert!(matches!(
+            validate_and_normalize("a\\b<c"),
+            Err(PathError::InvalidCharacters { .. })
+        ));
+        assert_eq!(
+            validate_and_normalize("//"),
+            Err(PathError::EmptyPath).map(|()| PathBuf::new())
+        );
+    }
+
+    #[test]
+    fn test_validate_limits_total_bytes() {
+        let limits = PathLimits::new().max_total_bytes(Some(10));
+        assert!(validate_limits("abcde/ghij", &limits).is_ok());
+        assert_eq!(
+            validate_limits("abcde/ghijk", &limits),
+            Err(PathError::PathTooLong {
+                length: 11,
+                max: 10
+            })
+        );
+        // Bytes, not characters
+        assert!(validate_limits("ééééé", &limits).is_ok());
+        assert!(validate_limits("éééééé", &limits).is_err());
+    }
+
+    #[test]
+    fn test_validate_limits_depth() {
+        let limits = PathLimits::new().max_depth(Some(3));
+        assert!(validate_limits("a/b/c", &limits).is_ok());
+        assert!(validate_limits("/a//b\\c/", &limits).is_ok());
+        assert_eq!(
+            validate_limits("a/b/c/d", &limits),
+            Err(PathError::PathTooDeep { depth: 4, max: 3 })
+        );
+    }
+
+    #[test]
+    fn test_validate_limits_component_bytes() {
+        let limits = PathLimits::new().max_component_bytes(Some(4));
+        assert!(validate_limits("abcd/efgh", &limits).is_ok());
+        assert_eq!(
+            validate_limits("abcd/efghi/jklmn", &limits),
+            Err(PathError::ComponentTooLong {
+                component: "efghi".to_string(),
+                length: 5
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_limits_boundary() {
+        let limits = PathLimits::new()
+            .max_total_bytes(Some(14))
+            .max_depth(Some(3))
+            .max_component_bytes(Some(4));
+        assert!(validate_limits("abcd/efgh/ijkl", &limits).is_ok());
+
+        // No limits accepts anything, including paths validation would reject
+        let long = "a/".repeat(5000);
+        assert!(validate_limits(&long, &PathLimits::new()).is_ok());
+        assert!(validate_limits("../CON", &PathLimits::new()).is_ok());
+    }
+
+    #[test]
+    fn test_require_relative() {
+        let options = ValidationOptions::new().require_relative(true);
+        for path in [
+            "/etc/passwd",
+            "C:\\x",
+            "c:/x",
+            "D:x",
+            "\\\\server\\share",
+            "\\temp",
+        ] {
+            assert_eq!(
+                validate_path_with(path, &options),
+                Err(PathError::AbsolutePath {
+                    path: path.to_string()
+                }),
+                "{:?}",
+                path
+            );
+            // Accepted without the flag (`D:x` would otherwise look like a stream)
+            let default = ValidationOptions::new().reject_ads(false);
+            assert!(validate_path_with(path, &default).is_ok(), "{:?}", path);
+        }
+
+        for path in ["src/main.rs", "a", "./x", "dir:name"] {
+            assert!(
+                validate_path_with(path, &options.clone().reject_ads(false)).is_ok(),
+                "{:?}",
+                path
+            );
+        }
+
+        // Drive letters are ordinary names on a Unix target, but a root isn't
+        let unix = options.target_platform(TargetPlatform::Unix);
+        assert!(validate_path_with("C:x", &unix).is_ok());
+        assert!(validate_path_with("/etc/passwd", &unix).is_err());
+    }
+
+    #[test]
+    fn test_reject_surrounding_whitespace() {
+        for path in [
+            " a/b",
+            "a/b ",
+            "\ta/b",
+            "a/b\u{3000}",
+            "/ a/b",
+            "a/b /",
+            "x ",
+        ] {
+            assert!(
+                matches!(
+                    validate_path(path),
+                    Err(PathError::InvalidCharacters { .. })
+                ),
+                "{:?}",
+                path
+            );
+        }
+
+        // Whitespace inside the path is fine
+        for path in ["a/ b/c", "a /b", "my file.txt", "dir/my file"] {
+            assert!(validate_path(path).is_ok(), "{:?}", path);
+        }
+
+        let options = ValidationOptions::new().reject_surrounding_whitespace(false);
+        assert!(validate_path_with(" a/b", &options).is_ok());
+        assert!(validate_path_with("a/b ", &options).is_ok());
+    }
+
+    #[test]
+    fn test_reject_short_names() {
+        let options = ValidationOptions::new().reject_short_names(true);
+        for path in [
+            "PROGRA~1",
+            "C:/PROGRA~1/app.exe",
+            "docs/FOO~12.TXT",
+            "progra~1/x",
+            "A~123456.b",
+            "dir\\LONGNA~2.HTM",
+        ] {
+            assert_eq!(
+                validate_path_with(path, &options).unwrap_err().rule(),
+                Some("short_name"),
+                "{:?} should be rejected",
+                path
+            );
+            // Off by default
+            assert!(validate_path(path).is_ok(), "{:?}", path);
+        }
+
+        let err = validate_path_with("C:/PROGRA~1/app.exe", &options).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("PROGRA~1 is an 8.3 short-name alias in C:/PROGRA~1/app.exe"));
+
+        for path in [
+            "foo~bar.txt",
+            "backup~",
+            "~1",
+            "notes.txt~1",
+            "TOOLONGNA~1",
+            "FOO~1.HTML",
+            "FOO~1.tar.gz",
+            "a~b~c/d",
+        ] {
+            assert!(
+                validate_path_with(path, &options).is_ok(),
+                "{:?} should be accepted",
+                path
+            );
+        }
+
+        // Windows rule only
+        let unix = options.target_platform(TargetPlatform::Unix);
+        assert!(validate_path_with("PROGRA~1", &unix).is_ok());
+    }
+
+    #[test]
+    fn test_target_platform() {
+        let unix = ValidationOptions::new().target_platform(TargetPlatform::Unix);
+        let windows = ValidationOptions::new().target_platform(TargetPlatform::Windows);
+
+        // Windows rules, applied or skipped regardless of the host
+        for path in [
+            "logs/CON",
+            "aux.txt",
+            "file.txt:stream",
+            "a/ /b",
+            "a<b",
+            "what?",
+        ] {
+            assert!(validate_path_with(path, &unix).is_ok(), "{:?}", path);
+            assert!(validate_path_with(path, &windows).is_err(), "{:?}", path);
+            assert!(validate_path(path).is_err(), "{:?}", path);
+        }
+
+        // Rules shared by every platform still apply to Unix
+        for path in ["../etc/passwd", "a\0b", "", "x\u{202E}y"] {
+            assert!(validate_path_with(path, &unix).is_err(), "{:?}", path);
+        }
+    }
+
+    #[test]
+    fn test_target_platform_keeps_explicit_rules() {
+        let mut names = ReservedNames::with_defaults();
+        names.add(".htaccess");
+        let unix = ValidationOptions::new()
+            .target_platform(TargetPlatform::Unix)
+            .reserved_names(names)
+            .extra_forbidden_chars(&['#']);
+
+        assert!(validate_path_with("logs/CON", &unix).is_ok());
+        assert!(matches!(
+            validate_path_with("www/.htaccess", &unix),
+            Err(PathError::ReservedFilename { .. })
+        ));
+        assert!(matches!(
+            validate_path_with("notes#1", &unix),
+            Err(PathError::InvalidCharacters { .. })
+        ));
+
+        // A Windows target still honours the individual switches
+        let windows = ValidationOptions::new()
+            .target_platform(TargetPlatform::Windows)
+            .allow_reserved_names(true);
+        assert!(validate_path_with("logs/CON", &windows).is_ok());
+    }
+
+    #[test]
+    fn test_traversal_is_component_based() {
+        for path in ["..", "a/..", "../a", "a\\..\\b", "a/.../b", "a/.. /b"] {
+            assert!(
+                matches!(validate_path(path), Err(PathError::PathTraversal { .. })),
+                "{:?}",
+                path
+            );
+        }
+
+        // Dots inside a name are not traversal
+        for path in ["v1..2/notes.txt", "file...txt", "a..b/c", "..hidden"] {
+            assert!(validate_path(path).is_ok(), "{:?}", path);
+        }
+
+        // Only Windows strips the trailing dots and spaces that turn `...` into `..`
+        let unix = ValidationOptions::new().target_platform(TargetPlatform::Unix);
+        assert!(validate_path_with("a/.../b", &unix).is_ok());
+        assert!(validate_path_with("a/../b", &unix).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_validate_fns_take_the_same_bounds_with_std() {
+        use alloc::borrow::Cow;
+        use alloc::boxed::Box;
+
+        let options = ValidationOptions::new();
+        let cow: Cow<str> = Cow::Borrowed("src/main.rs");
+        let boxed: Box<str> = "../etc/passwd".into();
+
+        assert!(is_safe_path(cow.clone()));
+        assert!(validate_path(cow.clone()).is_ok());
+        assert!(validate_path_with(cow.clone(), &options).is_ok());
+        assert!(validate_path_all(cow, &options).is_ok());
+
+        assert!(!is_safe_path(boxed.clone()));
+        assert!(validate_path(boxed.clone()).is_err());
+        assert!(validate_path_with(boxed.clone(), &options).is_err());
+        assert!(validate_path_all(boxed, &options).is_err());
+
+        // Path-like values go through the `_p` counterparts
+        assert!(is_safe_path_p(Path::new("src/main.rs")));
+        assert!(validate_path_p(PathBuf::from("src/main.rs")).is_ok());
+        assert!(matches!(
+            validate_path_with_p(Path::new("../etc/passwd"), &options),
+            Err(PathError::PathTraversal { .. })
+        ));
+        assert!(validate_path_all_p(Path::new("a/CON"), &options).is_err());
+    }
+}
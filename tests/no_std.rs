@@ -0,0 +1,41 @@
+//! Checks for the `no_std` + `alloc` build
+//!
+//! Run with `cargo test --no-default-features --test no_std`. With the default
+//! `std` feature enabled this file compiles to nothing.
+
+#![cfg(not(feature = "std"))]
+
+use path_utils::*;
+
+#[test]
+fn string_api_without_std() {
+    assert_eq!(normalize_path_str("a//b\\c"), "a/b/c");
+    assert_eq!(sanitize_directory_file_path("/args.js").unwrap(), "args.js");
+    assert_eq!(
+        make_relative("/repo/src", "/repo/src/a.rs").unwrap(),
+        "a.rs"
+    );
+    assert_eq!(path_depth("/a//b/"), 2);
+}
+
+#[test]
+fn validation_takes_strings_without_std() {
+    assert!(is_safe_path("src/main.rs"));
+    assert!(!is_safe_path(String::from("../etc/passwd")));
+
+    assert!(validate_path("src/main.rs").is_ok());
+    assert!(matches!(
+        validate_path("CON"),
+        Err(PathError::ReservedFilename { .. })
+    ));
+
+    let options = ValidationOptions::new().max_length(Some(4));
+    assert!(validate_path_with("a/b", &options).is_ok());
+    assert_eq!(
+        validate_path_all("../CON/x", &options)
+            .unwrap_err()
+            .iter_all()
+            .count(),
+        4
+    );
+}
@@ -119,6 +119,22 @@ proptest! {
         );
     }
 
+    /// Property: The component iterator agrees with the normalized string
+    #[test]
+    fn normalized_components_match_normalized_string(
+        path in prop_oneof![PathGenerators::any_path(), "[a-c/\\\\.]{0,20}"]
+    ) {
+        let normalized = normalize_path_str(&path);
+        let expected: Vec<&str> = if normalized.is_empty() {
+            Vec::new()
+        } else {
+            normalized.split('/').collect()
+        };
+        let actual: Vec<&str> = normalized_components(&path).collect();
+
+        prop_assert_eq!(actual, expected);
+    }
+
     /// Property: Path join and normalize is associative for safe paths
     /// join(a, join(b, c)) == join(join(a, b), c) after normalization
     #[test]
@@ -131,8 +147,8 @@ proptest! {
         let path_b = PathBuf::from(b);
         let path_c = PathBuf::from(c);
 
-        let left_associative = join_and_normalize(&path_a, &join_and_normalize(&path_b, &path_c));
-        let right_associative = join_and_normalize(&join_and_normalize(&path_a, &path_b), &path_c);
+        let left_associative = join_and_normalize(&path_a, join_and_normalize(&path_b, &path_c));
+        let right_associative = join_and_normalize(join_and_normalize(&path_a, &path_b), &path_c);
 
         prop_assert_eq!(
             left_associative,
@@ -471,4 +487,4 @@ mod edge_cases {
             }
         }
     }
-}
\ No newline at end of file
+}